@@ -0,0 +1,85 @@
+//! Num command: read text from standard input and print statistics.
+//!
+//! Usage:
+//!
+//! ```sh
+//! seq 1 10 | num
+//! ping -c 10 example.com | num ping
+//! traceroute example.com | num traceroute
+//! ```
+
+use std::io::{self, Read};
+use std::process::ExitCode;
+use numeric_statistics::f64::{describe::*, ping::*};
+
+const USAGE: &str = "\
+Usage: num [MODE]
+
+Read text from standard input and print statistics.
+
+Modes:
+  (none)       numbers separated by whitespace
+  ping         output of ping, such as round trip times, jitter, and loss
+  traceroute   output of traceroute, per hop
+";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let mode = args.first().map(String::as_str);
+    if matches!(mode, Some("-h" | "--help" | "help")) {
+        print!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+    let mut text = String::new();
+    if let Err(e) = io::stdin().read_to_string(&mut text) {
+        eprintln!("num: cannot read standard input: {}", e);
+        return ExitCode::FAILURE;
+    }
+    match mode {
+        None => numbers(&text),
+        Some("ping") => ping(&text),
+        Some("traceroute") => traceroute(&text),
+        Some(other) => {
+            eprint!("num: unknown mode: {}\n\n{}", other, USAGE);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Print a description of every number in the text.
+fn numbers(text: &str) -> ExitCode {
+    let values: Vec<f64> = text.split_whitespace().filter_map(|x| x.parse().ok()).collect();
+    let options = DescribeOptions::default().sum(true);
+    print!("{}", describe(&values, &options).display().align(true));
+    ExitCode::SUCCESS
+}
+
+/// Print statistics of ping output, like its summary with more detail.
+fn ping(text: &str) -> ExitCode {
+    let ping = parse_ping(text);
+    if ping.rtts().is_empty() && ping.timeouts.is_empty() && ping.transmitted.is_none() {
+        eprintln!("num: no ping replies found");
+        return ExitCode::FAILURE;
+    }
+    print!("{}", ping.display().precision(3).unit("ms").align(true));
+    ExitCode::SUCCESS
+}
+
+/// Print statistics of traceroute output, with one line per hop.
+fn traceroute(text: &str) -> ExitCode {
+    let hops = parse_traceroute(text);
+    if hops.is_empty() {
+        eprintln!("num: no traceroute hops found");
+        return ExitCode::FAILURE;
+    }
+    println!("{:>3}  {:<32} {:>9} {:>9} {:>9} {:>6}", "hop", "host", "min", "average", "max", "loss");
+    for hop in hops {
+        let host = hop.probes.iter().find_map(|probe| probe.host.as_deref()).unwrap_or("*");
+        let all = hop.all();
+        println!(
+            "{:>3}  {:<32} {:>9.3} {:>9.3} {:>9.3} {:>5.0}%",
+            hop.hop, host, all.min, all.average, all.max, hop.loss_ratio() * 100.0
+        );
+    }
+    ExitCode::SUCCESS
+}
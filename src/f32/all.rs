@@ -13,6 +13,7 @@ use crate::f32::{
     average::*,
     variance::*,
     standard_deviation::*,
+    display::*,
 };
 
 impl All {
@@ -30,6 +31,36 @@ impl All {
             standard_deviation  
         }
     }
+
+    /// Create a display builder for formatting every field,
+    /// such as with precision, significant figures, and units.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use numeric_statistics::f32::all::All;
+    ///
+    /// let values = &[1.0, 2.0, 4.0];
+    /// let all = All::new(values);
+    /// let s = all.display().precision(3).significant(4).unit("ms").align(true).to_string();
+    /// assert_eq!(s, concat!(
+    ///     "min:                1.000 ms\n",
+    ///     "max:                4.000 ms\n",
+    ///     "average:            2.333 ms\n",
+    ///     "variance:           2.333 ms²\n",
+    ///     "standard deviation: 1.528 ms\n",
+    /// ));
+    /// ```
+    ///
+    pub fn display(&self) -> StatsDisplay {
+        StatsDisplay::new(vec![
+            Field::new("min", self.min, Kind::Value),
+            Field::new("max", self.max, Kind::Value),
+            Field::new("average", self.average, Kind::Value),
+            Field::new("variance", self.variance, Kind::Squared),
+            Field::new("standard deviation", self.standard_deviation, Kind::Value),
+        ])
+    }
 }
 
 use std::fmt;
@@ -114,12 +114,12 @@ mod test_assert_eq_f32_as_result {
             0.3333334
         }
 
-        assert_eq!(A.is_completed(), false);
-        assert_eq!(B.is_completed(), false);
+        assert!(!A.is_completed());
+        assert!(!B.is_completed());
         let result = assert_eq_f32_as_result!(a(), b());
         assert!(result.is_ok());
-        assert_eq!(A.is_completed(), true);
-        assert_eq!(B.is_completed(), true);
+        assert!(A.is_completed());
+        assert!(B.is_completed());
     }
 
     #[test]
@@ -236,8 +236,7 @@ mod test_assert_eq_f32 {
         let a: f32 = 0.3333333;
         let b: f32 = 0.3333334;
         for _ in 0..1 {
-            let actual = assert_eq_f32!(a, b);
-            assert_eq!(actual, ());
+            assert_eq_f32!(a, b);
         }
     }
 
@@ -246,7 +245,7 @@ mod test_assert_eq_f32 {
         let a: f32 = 0.3333333;
         let b: f32 = 0.3333336;
         let result = panic::catch_unwind(|| {
-            let _actual = assert_eq_f32!(a, b);
+            assert_eq_f32!(a, b);
         });
         let message = concat!(
             "assertion failed: `assert_eq_f32!(a, b)`\n",
@@ -272,7 +271,7 @@ mod test_assert_eq_f32 {
         let a: f32 = 0.3333336;
         let b: f32 = 0.3333333;
         let result = panic::catch_unwind(|| {
-            let _actual = assert_eq_f32!(a, b);
+            assert_eq_f32!(a, b);
         });
         let message = concat!(
             "assertion failed: `assert_eq_f32!(a, b)`\n",
@@ -60,25 +60,25 @@ mod test {
     #[test]
     fn test_values_ascending() {
         let x = &[1.0, 2.0, 4.0];
-        assert_eq_f32!(average(x), 2.3333333 as f32);
+        assert_eq_f32!(average(x), 2.3333333_f32);
     }
 
     #[test]
     fn test_values_ascending_and_nans() {
         let x = &[1.0, f32::NAN, 2.0, f32::NAN, 4.0];
-        assert_eq_f32!(average(x), 2.3333333 as f32);
+        assert_eq_f32!(average(x), 2.3333333_f32);
     }
 
     #[test]
     fn test_values_descending() {
         let x = &[4.0, 2.0, 1.0];
-        assert_eq_f32!(average(x), 2.3333333 as f32);
+        assert_eq_f32!(average(x), 2.3333333_f32);
     }
 
     #[test]
     fn test_values_descending_and_nans() {
         let x = &[4.0, f32::NAN, 2.0, f32::NAN, 1.0];
-        assert_eq_f32!(average(x), 2.3333333 as f32);
+        assert_eq_f32!(average(x), 2.3333333_f32);
     }
 
 }
@@ -1,7 +1,6 @@
 pub mod assert_eq_f32;
 
 pub mod all; 
-#[allow(unused_imports)] 
 
 pub mod min; 
 #[allow(unused_imports)] 
@@ -22,3 +21,7 @@ use variance::*;
 pub mod standard_deviation;
 #[allow(unused_imports)] 
 use standard_deviation::*;
+
+pub mod display;
+
+pub mod special;
@@ -0,0 +1,227 @@
+//! Special functions for distributions and statistical tests, such as
+//! the log gamma function, the regularized incomplete gamma and beta
+//! functions, and the error function.
+//!
+//! Each function calculates in f64 then rounds to f32, so the relative
+//! error is within about 1 unit in the last place of f32, which is about
+//! 1e-7. See the f64 module for the methods.
+//!
+//! # Example
+//!
+//! ```rust
+//! use numeric_statistics::f32::special::*;
+//!
+//! // ln Γ(10) = ln 9!
+//! assert!((ln_gamma(10.0) - 362880f32.ln()).abs() < 1e-5);
+//! ```
+
+use crate::f64::special as f64_special;
+
+/// Calculate the natural log of the absolute value of the gamma function, ln |Γ(x)|.
+///
+/// Return infinity for x = 0 and for negative integers, where Γ has poles.
+/// Return NaN for NaN.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f32::special::*;
+/// assert!((ln_gamma(0.5) - std::f32::consts::PI.sqrt().ln()).abs() < 1e-6);
+/// ```
+///
+pub fn ln_gamma(x: f32) -> f32 {
+    f64_special::ln_gamma(x as f64) as f32
+}
+
+/// Calculate the gamma function Γ(x), which is (x - 1)! for positive integers.
+///
+/// Return infinity for x > 35.04, where Γ overflows f32.
+/// Return NaN for x = 0 and for negative integers, where Γ has poles, and for NaN.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f32::special::*;
+/// assert_eq!(gamma(5.0), 24.0);
+/// ```
+///
+pub fn gamma(x: f32) -> f32 {
+    f64_special::gamma(x as f64) as f32
+}
+
+/// Calculate the digamma function ψ(x), which is the derivative of ln Γ(x).
+///
+/// Return NaN for x = 0 and for negative integers, where ψ has poles, and for NaN.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f32::special::*;
+/// // ψ(1) = -γ, the Euler–Mascheroni constant.
+/// assert!((digamma(1.0) + 0.5772157).abs() < 1e-6);
+/// ```
+///
+pub fn digamma(x: f32) -> f32 {
+    f64_special::digamma(x as f64) as f32
+}
+
+/// Calculate the natural log of the beta function, ln B(a, b).
+///
+/// Return NaN if a or b is not positive.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f32::special::*;
+/// // B(2, 3) = 1 / 12
+/// assert!((ln_beta(2.0, 3.0) - (1.0f32 / 12.0).ln()).abs() < 1e-6);
+/// ```
+///
+pub fn ln_beta(a: f32, b: f32) -> f32 {
+    f64_special::ln_beta(a as f64, b as f64) as f32
+}
+
+/// Calculate the regularized incomplete beta function I_x(a, b),
+/// which is the cumulative distribution function of the beta distribution.
+///
+/// Return NaN if a or b is not positive, or if x is NaN.
+/// Clamp x to 0.0 to 1.0.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f32::special::*;
+/// assert!((incomplete_beta(2.0, 3.0, 0.4) - 0.5248).abs() < 1e-6);
+/// ```
+///
+pub fn incomplete_beta(a: f32, b: f32, x: f32) -> f32 {
+    f64_special::incomplete_beta(a as f64, b as f64, x as f64) as f32
+}
+
+/// Calculate the inverse of the regularized incomplete beta function,
+/// which is x such that I_x(a, b) = p.
+///
+/// Return NaN if a or b is not positive, or if p is not within 0.0 to 1.0.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f32::special::*;
+/// assert!((inverse_incomplete_beta(2.0, 3.0, 0.5248) - 0.4).abs() < 1e-6);
+/// ```
+///
+pub fn inverse_incomplete_beta(a: f32, b: f32, p: f32) -> f32 {
+    f64_special::inverse_incomplete_beta(a as f64, b as f64, p as f64) as f32
+}
+
+/// Calculate the regularized lower incomplete gamma function P(a, x).
+///
+/// Return NaN if a is not positive, or if x is NaN.
+/// Return 0.0 for x <= 0.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f32::special::*;
+/// // P(1, x) = 1 - e^-x
+/// assert!((incomplete_gamma_p(1.0, 2.0) - (1.0 - (-2.0f32).exp())).abs() < 1e-6);
+/// ```
+///
+pub fn incomplete_gamma_p(a: f32, x: f32) -> f32 {
+    f64_special::incomplete_gamma_p(a as f64, x as f64) as f32
+}
+
+/// Calculate the regularized upper incomplete gamma function Q(a, x) = 1 - P(a, x),
+/// without cancellation for large x.
+///
+/// Return NaN if a is not positive, or if x is NaN.
+/// Return 1.0 for x <= 0.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f32::special::*;
+/// // Q(1, x) = e^-x
+/// assert!((incomplete_gamma_q(1.0, 50.0) / (-50.0f32).exp() - 1.0).abs() < 1e-6);
+/// ```
+///
+pub fn incomplete_gamma_q(a: f32, x: f32) -> f32 {
+    f64_special::incomplete_gamma_q(a as f64, x as f64) as f32
+}
+
+/// Calculate the error function erf(x).
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f32::special::*;
+/// assert!((erf(1.0) - 0.8427008).abs() < 1e-6);
+/// ```
+///
+pub fn erf(x: f32) -> f32 {
+    f64_special::erf(x as f64) as f32
+}
+
+/// Calculate the complementary error function erfc(x) = 1 - erf(x),
+/// without cancellation for large x.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f32::special::*;
+/// assert!((erfc(5.0) / 1.5374598e-12 - 1.0).abs() < 1e-6);
+/// ```
+///
+pub fn erfc(x: f32) -> f32 {
+    f64_special::erfc(x as f64) as f32
+}
+
+/// Calculate the inverse complementary error function, which is x such that erfc(x) = y.
+///
+/// Return infinity for y = 0, negative infinity for y = 2,
+/// and NaN for y outside 0.0 to 2.0.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f32::special::*;
+/// // The two-sided 95% normal critical value is sqrt(2) erfc_inv(0.05).
+/// let z = 2f32.sqrt() * erfc_inv(0.05);
+/// assert!((z - 1.959964).abs() < 1e-6);
+/// ```
+///
+pub fn erfc_inv(y: f32) -> f32 {
+    f64_special::erfc_inv(y as f64) as f32
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::assert_eq_f32;
+
+    #[test]
+    fn test_nan() {
+        assert!(ln_gamma(f32::NAN).is_nan());
+        assert!(gamma(-1.0).is_nan());
+        assert!(incomplete_beta(1.0, 1.0, f32::NAN).is_nan());
+        assert!(erfc_inv(3.0).is_nan());
+    }
+
+    #[test]
+    fn test_values() {
+        assert_eq_f32!(gamma(4.5), 11.631728_f32);
+        assert_eq_f32!(digamma(2.5), 0.70315665_f32);
+        assert_eq_f32!(incomplete_gamma_q(2.5, 2.0), 0.54941595_f32);
+        assert_eq_f32!(inverse_incomplete_beta(2.5, 4.0, 0.5), 0.37213242_f32);
+        assert_eq!(gamma(36.0), f32::INFINITY);
+    }
+
+    #[test]
+    fn test_erf() {
+        assert_eq!(erf(0.0), 0.0);
+        assert_eq_f32!(erf(-0.5), -0.5204999_f32);
+        assert_eq_f32!(erfc(-1.0), 1.8427008_f32);
+        assert_eq_f32!(erfc(erfc_inv(0.3)), 0.3_f32);
+    }
+
+}
@@ -56,8 +56,8 @@ pub fn variance_with_average<T: AsRef<[f32]>>(values: T, average: f32) -> f32 {
         }
     );
     match len {
-        0 => return f32::NAN,
-        1 => return 0.0,
+        0 => f32::NAN,
+        1 => 0.0,
         x => delta_square_sum / ((x - 1) as f32)
     }
 }
@@ -88,13 +88,13 @@ mod test {
     #[test]
     fn test_values_ascending() {
         let x = &[1.0, 2.0, 4.0];
-        assert_eq_f32!(variance(x), 2.3333333333333333 as f32);
+        assert_eq_f32!(variance(x), 2.3333333_f32);
     }
 
     #[test]
     fn test_values_ascending_and_nans() {
         let x = &[1.0, f32::NAN, 2.0, f32::NAN, 4.0];
-        assert_eq_f32!(variance(x), 2.3333333333333333 as f32);
+        assert_eq_f32!(variance(x), 2.3333333_f32);
     }
 
 }
@@ -0,0 +1,601 @@
+//! One-way analysis of variance, of whether groups have the same average,
+//! such as latency across regions: the ANOVA table, Welch's ANOVA for
+//! unequal variances, Levene's and Brown–Forsythe tests of equal
+//! variances, and Tukey's HSD to compare each pair of groups, with the
+//! studentized range distribution.
+//!
+//! Each function takes a slice of groups, where each group is values.
+//!
+//! # Example
+//!
+//! ```rust
+//! use numeric_statistics::f64::anova::*;
+//!
+//! let east = [4.17, 5.58, 5.18, 6.11, 4.50, 4.61, 5.17, 4.53, 5.33, 5.14];
+//! let west = [4.81, 4.17, 4.41, 3.59, 5.87, 3.83, 6.03, 4.89, 4.32, 4.69];
+//! let north = [6.31, 5.12, 5.54, 5.50, 5.37, 5.29, 4.92, 6.15, 5.80, 5.26];
+//! let groups = [east, west, north];
+//! let anova = one_way_anova(&groups);
+//! assert!(anova.p_value < 0.05);
+//! // Which pairs differ?
+//! let comparisons = tukey_hsd(&groups, 0.95);
+//! let differ: Vec<(usize, usize)> = comparisons.iter().filter(|c| c.p_value < 0.05).map(|c| (c.i, c.j)).collect();
+//! assert_eq!(differ, vec![(1, 2)]);
+//! ```
+
+use super::{
+    average,
+    distribution::{Distribution, FisherF, Normal},
+    hypothesis::*,
+    quantile::median,
+    special::{incomplete_gamma_p, incomplete_gamma_q},
+    variance,
+};
+use std::f64::consts::PI;
+
+/// Row of an ANOVA table.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnovaRow {
+    pub sum_of_squares: f64,
+    pub degrees_of_freedom: f64,
+    /// Sum of squares / degrees of freedom.
+    pub mean_square: f64,
+}
+
+impl AnovaRow {
+    fn new(sum_of_squares: f64, degrees_of_freedom: f64) -> AnovaRow {
+        AnovaRow { sum_of_squares, degrees_of_freedom, mean_square: sum_of_squares / degrees_of_freedom }
+    }
+}
+
+/// One-way ANOVA table.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Anova {
+    /// Between groups, with k - 1 degrees of freedom.
+    pub between: AnovaRow,
+    /// Within groups, which is the residual, with N - k degrees of freedom.
+    pub within: AnovaRow,
+    /// Total, with N - 1 degrees of freedom.
+    pub total: AnovaRow,
+    /// F = between mean square / within mean square.
+    pub f_statistic: f64,
+    pub p_value: f64,
+    /// Effect size η² = between sum of squares / total sum of squares.
+    pub eta_squared: f64,
+}
+
+/// Groups without NaN values, skipping groups without values.
+fn finite_groups<G: AsRef<[f64]>>(groups: &[G]) -> Vec<Vec<f64>> {
+    groups
+        .iter()
+        .map(|g| without_nan(g.as_ref()))
+        .filter(|g| !g.is_empty())
+        .collect()
+}
+
+/// Calculate the one-way ANOVA table, which tests whether groups have the
+/// same average, assuming normal values with equal variances.
+///
+/// Skip groups without values. Return a table with NaN statistics if there
+/// are fewer than 2 groups, or if there are no more values than groups.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::anova::*;
+/// let groups = [vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0], vec![7.0, 8.0, 9.0]];
+/// let anova = one_way_anova(&groups);
+/// assert_eq!(anova.between.sum_of_squares, 54.0);
+/// assert_eq!(anova.within.sum_of_squares, 6.0);
+/// assert_eq!(anova.within.degrees_of_freedom, 6.0);
+/// assert_eq!(anova.f_statistic, 27.0);
+/// assert!((anova.p_value - 0.001).abs() < 1e-15);
+/// ```
+///
+pub fn one_way_anova<G: AsRef<[f64]>>(groups: &[G]) -> Anova {
+    anova(&finite_groups(groups))
+}
+
+fn anova(groups: &[Vec<f64>]) -> Anova {
+    let k = groups.len();
+    let n: usize = groups.iter().map(|g| g.len()).sum();
+    if k < 2 || n <= k {
+        let nan = AnovaRow::new(f64::NAN, f64::NAN);
+        return Anova { between: nan, within: nan, total: nan, f_statistic: f64::NAN, p_value: f64::NAN, eta_squared: f64::NAN };
+    }
+    let grand = groups.iter().flatten().sum::<f64>() / n as f64;
+    let mut between = 0.0;
+    let mut within = 0.0;
+    for group in groups {
+        let average = average(group);
+        between += group.len() as f64 * (average - grand).powi(2);
+        within += group.iter().map(|x| (x - average).powi(2)).sum::<f64>();
+    }
+    let between = AnovaRow::new(between, (k - 1) as f64);
+    let within = AnovaRow::new(within, (n - k) as f64);
+    let total = AnovaRow::new(between.sum_of_squares + within.sum_of_squares, (n - 1) as f64);
+    let f_statistic = between.mean_square / within.mean_square;
+    Anova {
+        between,
+        within,
+        total,
+        f_statistic,
+        p_value: FisherF::new(between.degrees_of_freedom, within.degrees_of_freedom).sf(f_statistic),
+        eta_squared: between.sum_of_squares / total.sum_of_squares,
+    }
+}
+
+/// Calculate Welch's one-way ANOVA, which tests whether groups have the
+/// same average without assuming equal variances, like R's `oneway.test`.
+///
+/// The statistic is Welch's F, with k - 1 numerator degrees of freedom,
+/// and the degrees of freedom field is the approximate denominator degrees
+/// of freedom. The other fields are NaN.
+///
+/// Skip groups without values. Return NaN statistics if there are fewer
+/// than 2 groups, or if any group has fewer than 2 values or variance 0.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::anova::*;
+/// let groups = [vec![10.0, 11.0, 12.0, 11.5], vec![20.0, 30.0, 25.0, 35.0], vec![12.0, 13.0, 11.0, 12.5]];
+/// let result = welch_anova(&groups);
+/// assert!(result.p_value < 0.05);
+/// ```
+///
+pub fn welch_anova<G: AsRef<[f64]>>(groups: &[G]) -> TestResult {
+    let groups = finite_groups(groups);
+    let k = groups.len() as f64;
+    if k < 2.0 || groups.iter().any(|g| g.len() < 2) { return TestResult::nan(); }
+    let sizes: Vec<f64> = groups.iter().map(|g| g.len() as f64).collect();
+    let averages: Vec<f64> = groups.iter().map(average).collect();
+    let weights: Vec<f64> = groups.iter().zip(&sizes).map(|(g, n)| n / variance(g)).collect();
+    if weights.iter().any(|w| !w.is_finite()) { return TestResult::nan(); }
+    let weight_sum: f64 = weights.iter().sum();
+    let weighted_average = weights.iter().zip(&averages).map(|(w, m)| w * m).sum::<f64>() / weight_sum;
+    let a = weights.iter().zip(&averages).map(|(w, m)| w * (m - weighted_average).powi(2)).sum::<f64>() / (k - 1.0);
+    let lambda: f64 = weights.iter().zip(&sizes).map(|(w, n)| (1.0 - w / weight_sum).powi(2) / (n - 1.0)).sum();
+    let b = 1.0 + 2.0 * (k - 2.0) / (k * k - 1.0) * lambda;
+    let f_statistic = a / b;
+    let df = (k * k - 1.0) / (3.0 * lambda);
+    TestResult {
+        statistic: f_statistic,
+        degrees_of_freedom: df,
+        p_value: FisherF::new(k - 1.0, df).sf(f_statistic),
+        ..TestResult::nan()
+    }
+}
+
+/// ANOVA F-test of the absolute deviations of each group from its center.
+fn deviation_test(groups: &[Vec<f64>], center: fn(&Vec<f64>) -> f64) -> TestResult {
+    let deviations: Vec<Vec<f64>> = groups
+        .iter()
+        .map(|g| {
+            let c = center(g);
+            g.iter().map(|x| (x - c).abs()).collect()
+        })
+        .collect();
+    let anova = anova(&deviations);
+    TestResult {
+        statistic: anova.f_statistic,
+        degrees_of_freedom: anova.within.degrees_of_freedom,
+        p_value: anova.p_value,
+        ..TestResult::nan()
+    }
+}
+
+/// Calculate Levene's test of whether groups have equal variances, which
+/// is the ANOVA F-test of the absolute deviations from each group average.
+///
+/// The statistic is F, with k - 1 numerator degrees of freedom, and the
+/// degrees of freedom field is the denominator degrees of freedom N - k.
+/// The other fields are NaN.
+///
+/// Skip groups without values. Return NaN statistics if there are fewer
+/// than 2 groups, or if there are no more values than groups.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::anova::*;
+/// let groups = [vec![10.0, 11.0, 12.0, 11.5, 10.5], vec![0.0, 30.0, 15.0, 35.0, 5.0]];
+/// let result = levene(&groups);
+/// assert!(result.p_value < 0.01);
+/// ```
+///
+pub fn levene<G: AsRef<[f64]>>(groups: &[G]) -> TestResult {
+    deviation_test(&finite_groups(groups), |g| average(g))
+}
+
+/// Calculate the Brown–Forsythe test of whether groups have equal
+/// variances, which is Levene's test with deviations from each group
+/// median, so is robust for values that are not normal. This is the
+/// default of R's `car::leveneTest`.
+///
+/// Return values like `levene`.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::anova::*;
+/// let groups = [vec![10.0, 11.0, 12.0, 11.5, 10.5], vec![0.0, 30.0, 15.0, 35.0, 5.0]];
+/// let result = brown_forsythe(&groups);
+/// assert!(result.p_value < 0.05);
+/// ```
+///
+pub fn brown_forsythe<G: AsRef<[f64]>>(groups: &[G]) -> TestResult {
+    deviation_test(&finite_groups(groups), |g| median(g))
+}
+
+/// Comparison of a pair of groups by Tukey's HSD.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TukeyComparison {
+    /// Index of the first group.
+    pub i: usize,
+    /// Index of the second group, greater than i.
+    pub j: usize,
+    /// Average of group j - average of group i.
+    pub difference: f64,
+    /// Simultaneous confidence bounds of the difference.
+    pub confidence_lower: f64,
+    pub confidence_upper: f64,
+    /// P-value adjusted for the count of comparisons.
+    pub p_value: f64,
+}
+
+/// Calculate Tukey's honestly significant difference (HSD) test, which
+/// compares the averages of each pair of groups, with p-values and
+/// simultaneous confidence intervals for the family of comparisons, like
+/// R's `TukeyHSD`.
+///
+/// For unequal group sizes this is the Tukey–Kramer method. The indexes
+/// are of the groups with values.
+///
+/// Skip groups without values. Return no comparisons if there are fewer
+/// than 2 groups, or if there are no more values than groups. Return NaN
+/// bounds if the confidence is not within 0.0 to 1.0 exclusive.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::anova::*;
+/// let groups = [vec![1.0, 2.0, 3.0], vec![1.5, 2.5, 3.5], vec![7.0, 8.0, 9.0]];
+/// let comparisons = tukey_hsd(&groups, 0.95);
+/// assert_eq!(comparisons.len(), 3);
+/// assert_eq!((comparisons[0].i, comparisons[0].j), (0, 1));
+/// assert_eq!(comparisons[0].difference, 0.5);
+/// assert!(comparisons[0].p_value > 0.5);
+/// assert!(comparisons[1].p_value < 0.001);
+/// ```
+///
+pub fn tukey_hsd<G: AsRef<[f64]>>(groups: &[G], confidence: f64) -> Vec<TukeyComparison> {
+    let groups = finite_groups(groups);
+    let anova = anova(&groups);
+    if anova.f_statistic.is_nan() && anova.within.mean_square.is_nan() { return Vec::new(); }
+    let k = groups.len() as f64;
+    let df = anova.within.degrees_of_freedom;
+    let critical = if confidence > 0.0 && confidence < 1.0 {
+        studentized_range_quantile(confidence, k, df)
+    } else {
+        f64::NAN
+    };
+    let averages: Vec<f64> = groups.iter().map(average).collect();
+    let mut comparisons = Vec::new();
+    for i in 0..groups.len() {
+        for j in i + 1..groups.len() {
+            let difference = averages[j] - averages[i];
+            let standard_error =
+                (anova.within.mean_square / 2.0 * (1.0 / groups[i].len() as f64 + 1.0 / groups[j].len() as f64)).sqrt();
+            comparisons.push(TukeyComparison {
+                i,
+                j,
+                difference,
+                confidence_lower: difference - critical * standard_error,
+                confidence_upper: difference + critical * standard_error,
+                p_value: studentized_range_sf(difference.abs() / standard_error, k, df),
+            });
+        }
+    }
+    comparisons
+}
+
+/// Gauss–Legendre nodes and weights on [-1, 1], for x >= 0, which mirror.
+const GAUSS_LEGENDRE: [(f64, f64); 5] = [
+    (0.14887433898163122, 0.29552422471475287),
+    (0.4333953941292472, 0.26926671930999635),
+    (0.6794095682990244, 0.21908636251598204),
+    (0.8650633666889845, 0.1494513491505806),
+    (0.9739065285171717, 0.06667134430868814),
+];
+
+/// Integrate f from a to b, by Gauss–Legendre quadrature on each of the
+/// pieces.
+fn integrate(f: impl Fn(f64) -> f64, a: f64, b: f64, pieces: usize) -> f64 {
+    let width = (b - a) / pieces as f64;
+    (0..pieces)
+        .map(|i| {
+            let center = a + (i as f64 + 0.5) * width;
+            let half = width / 2.0;
+            GAUSS_LEGENDRE
+                .iter()
+                .map(|(x, w)| w * (f(center - half * x) + f(center + half * x)))
+                .sum::<f64>()
+                * half
+        })
+        .sum()
+}
+
+/// Density of the range of k standard normal values, which is
+/// k (k - 1) ∫ φ(z) φ(z - t) (Φ(z) - Φ(z - t))^(k - 2) dz.
+fn range_pdf(t: f64, k: f64) -> f64 {
+    if t <= 0.0 { return 0.0; }
+    // The range of 2 normal values is √2 |Z|.
+    if k == 2.0 { return (-t * t / 4.0).exp() / PI.sqrt(); }
+    let normal = Normal::standard();
+    // The integrand is symmetric about z = t/2, so integrate z = t/2 + u for u >= 0.
+    let f = |u: f64| {
+        let width = normal.cdf(t / 2.0 + u) - normal.cdf(u - t / 2.0);
+        if width <= 0.0 { 0.0 } else { normal.pdf(t / 2.0 + u) * normal.pdf(u - t / 2.0) * width.powf(k - 2.0) }
+    };
+    // φ(z) φ(z - t) is e^(-t²/4) / (2√π) times a normal density at t/2
+    // with standard deviation 1/√2, which is negligible beyond 6.5. The
+    // power of the width narrows the peak by about √k.
+    let pieces = (3.5 * (k / 2.0).sqrt()).ceil() as usize;
+    2.0 * k * (k - 1.0) * integrate(f, 0.0, 6.5, pieces)
+}
+
+/// Integrate the range density t times the probability that the ratio of
+/// the estimate to the standard deviation is above t/q, for the cdf, or
+/// below t/q, for the sf, which avoids cancellation in either tail.
+fn studentized_range_integral(q: f64, k: f64, df: f64, upper: bool) -> f64 {
+    // df s² has a chi-squared distribution, so P(s < t/q) = P(df/2, df t² / 2q²).
+    let ratio = |t: f64| {
+        if df.is_infinite() { return if (t < q) == upper { 0.0 } else { 1.0 }; }
+        let x = df * t * t / (2.0 * q * q);
+        if upper { incomplete_gamma_p(df / 2.0, x) } else { incomplete_gamma_q(df / 2.0, x) }
+    };
+    // The range density is at most k (k - 1) e^(-t²/4) / (2√π) (2Φ(t/2) - 1)^(k - 2),
+    // which is negligible beyond 2 sqrt(750 + 2 ln k).
+    let end = 2.0 * (750.0 + 2.0 * k.ln()).sqrt();
+    let mut points: Vec<f64> = (0..=end.ceil() as usize).map(|t| t as f64).collect();
+    // The ratio changes from 1 to 0 near t = q, over about q / √(2 df),
+    // which for large df is much narrower than the range density.
+    let spread = q / (2.0 * df).sqrt();
+    points.push(q);
+    if spread < 0.5 {
+        points.extend((-8..=8).map(|i| q + i as f64 * spread).filter(|t| *t > 0.0 && *t < end));
+    }
+    points.sort_by(f64::total_cmp);
+    points.dedup();
+    // Integrate the pieces in order of their bound, until the rest are negligible.
+    let normal = Normal::standard();
+    let bound = |a: f64, b: f64| {
+        let width = 2.0 * normal.cdf(b / 2.0) - 1.0;
+        let density = k * (k - 1.0) * (-a * a / 4.0).exp() / (2.0 * PI.sqrt()) * width.powf(k - 2.0);
+        density * (b - a) * if upper { ratio(b) } else { ratio(a) }
+    };
+    let mut pieces: Vec<(f64, f64, f64)> = points.windows(2).map(|w| (w[0], w[1], bound(w[0], w[1]))).collect();
+    pieces.sort_by(|a, b| b.2.total_cmp(&a.2));
+    let mut rest: f64 = pieces.iter().map(|p| p.2).sum();
+    let mut sum = 0.0;
+    for (a, b, bound) in pieces {
+        if rest <= sum * 1e-14 { break; }
+        rest -= bound;
+        if bound > 0.0 { sum += integrate(|t| range_pdf(t, k) * ratio(t), a, b, 1); }
+    }
+    sum
+}
+
+/// Calculate the cdf P(Q < q) of the studentized range distribution, which
+/// is the range of k standard normal values divided by an independent
+/// estimate of the standard deviation with df degrees of freedom.
+///
+/// Integrate the range density times the chi distribution of the estimate,
+/// with relative error about 1e-10 for any df, including infinity, where
+/// the estimate is exact.
+///
+/// Return NaN if k < 2 or df < 1, or if any argument is NaN.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::anova::*;
+/// // The 95% critical value for 3 groups and 10 degrees of freedom.
+/// assert!((studentized_range_cdf(3.876777, 3.0, 10.0) - 0.95).abs() < 1e-6);
+/// ```
+///
+pub fn studentized_range_cdf(q: f64, k: f64, df: f64) -> f64 {
+    if q.is_nan() || k.is_nan() || df.is_nan() || k < 2.0 || df < 1.0 { return f64::NAN; }
+    if q <= 0.0 { return 0.0; }
+    if q.is_infinite() { return 1.0; }
+    studentized_range_integral(q, k, df, false).min(1.0)
+}
+
+/// Calculate the survival function P(Q >= q) = 1 - cdf of the studentized
+/// range distribution, without cancellation for large q.
+///
+/// Return NaN like `studentized_range_cdf`.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::anova::*;
+/// assert!((studentized_range_sf(3.876777, 3.0, 10.0) - 0.05).abs() < 1e-6);
+/// ```
+///
+pub fn studentized_range_sf(q: f64, k: f64, df: f64) -> f64 {
+    if q.is_nan() || k.is_nan() || df.is_nan() || k < 2.0 || df < 1.0 { return f64::NAN; }
+    if q <= 0.0 { return 1.0; }
+    if q.is_infinite() { return 0.0; }
+    studentized_range_integral(q, k, df, true).min(1.0)
+}
+
+/// Calculate the quantile of the studentized range distribution, which is
+/// q such that P(Q < q) = p, such as the critical value for Tukey's HSD.
+///
+/// Return NaN if p is not within 0.0 to 1.0, and like
+/// `studentized_range_cdf`.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::anova::*;
+/// assert!((studentized_range_quantile(0.95, 3.0, 10.0) - 3.876777).abs() < 1e-6);
+/// ```
+///
+pub fn studentized_range_quantile(p: f64, k: f64, df: f64) -> f64 {
+    if p.is_nan() || !(0.0..=1.0).contains(&p) || studentized_range_cdf(1.0, k, df).is_nan() { return f64::NAN; }
+    if p == 0.0 { return 0.0; }
+    if p == 1.0 { return f64::INFINITY; }
+    // Use the sf for the upper tail, where the cdf is near 1.
+    let f = |q: f64| if p > 0.5 { 1.0 - p - studentized_range_sf(q, k, df) } else { studentized_range_cdf(q, k, df) - p };
+    // Bracket the root, then solve by the Illinois method.
+    let (mut lower, mut upper) = (0.0, 4.0);
+    while f(upper) < 0.0 {
+        lower = upper;
+        upper *= 2.0;
+    }
+    let (mut f_lower, mut f_upper) = (f(lower), f(upper));
+    let mut side = 0;
+    let mut previous = f64::NAN;
+    for _ in 0..100 {
+        let q = (lower * f_upper - upper * f_lower) / (f_upper - f_lower);
+        // Stop within the error of the cdf.
+        if (q - previous).abs() < 1e-10 * q { return q; }
+        previous = q;
+        let f_q = f(q);
+        if f_q.abs() < 1e-12 * p.min(1.0 - p) { return q; }
+        if f_q < 0.0 {
+            lower = q;
+            f_lower = f_q;
+            if side == -1 { f_upper /= 2.0; }
+            side = -1;
+        } else {
+            upper = q;
+            f_upper = f_q;
+            if side == 1 { f_lower /= 2.0; }
+            side = 1;
+        }
+    }
+    (lower + upper) / 2.0
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::f64::assert_eq_f64::assert_close;
+
+    // R's PlantGrowth data.
+    const CONTROL: [f64; 10] = [4.17, 5.58, 5.18, 6.11, 4.50, 4.61, 5.17, 4.53, 5.33, 5.14];
+    const TREATMENT_1: [f64; 10] = [4.81, 4.17, 4.41, 3.59, 5.87, 3.83, 6.03, 4.89, 4.32, 4.69];
+    const TREATMENT_2: [f64; 10] = [6.31, 5.12, 5.54, 5.50, 5.37, 5.29, 4.92, 6.15, 5.80, 5.26];
+    const GROUPS: [[f64; 10]; 3] = [CONTROL, TREATMENT_1, TREATMENT_2];
+
+    #[test]
+    fn test_empty() {
+        let groups: [Vec<f64>; 2] = [vec![1.0], vec![f64::NAN]];
+        assert!(one_way_anova(&groups).p_value.is_nan());
+        assert!(welch_anova(&groups).p_value.is_nan());
+        assert!(levene(&groups).p_value.is_nan());
+        assert!(tukey_hsd(&groups, 0.95).is_empty());
+    }
+
+    #[test]
+    fn test_one_way_anova() {
+        let anova = one_way_anova(&GROUPS);
+        assert_close!(anova.between.sum_of_squares, 3.76634, 1e-5);
+        assert_close!(anova.within.sum_of_squares, 10.49209, 1e-5);
+        assert_eq!(anova.between.degrees_of_freedom, 2.0);
+        assert_eq!(anova.within.degrees_of_freedom, 27.0);
+        assert_close!(anova.within.mean_square, 0.38860, 1e-5);
+        assert_close!(anova.f_statistic, 4.846088, 1e-6);
+        assert_close!(anova.p_value, 0.01590996, 1e-8);
+        assert_close!(anova.total.sum_of_squares, 14.25843, 1e-5);
+    }
+
+    #[test]
+    fn test_welch_anova() {
+        let result = welch_anova(&GROUPS);
+        assert_close!(result.statistic, 5.181, 1e-3);
+        assert_close!(result.degrees_of_freedom, 17.128, 1e-3);
+        assert_close!(result.p_value, 0.01739, 1e-5);
+    }
+
+    #[test]
+    fn test_levene() {
+        let result = brown_forsythe(&GROUPS);
+        assert_close!(result.statistic, 1.1192, 1e-4);
+        assert_close!(result.p_value, 0.3412, 1e-4);
+        assert_eq!(result.degrees_of_freedom, 27.0);
+    }
+
+    #[test]
+    fn test_tukey_hsd() {
+        let comparisons = tukey_hsd(&GROUPS, 0.95);
+        let expected = [
+            (0, 1, -0.371, -1.0622161, 0.3202161, 0.3908711),
+            (0, 2, 0.494, -0.1972161, 1.1852161, 0.1979960),
+            (1, 2, 0.865, 0.1737839, 1.5562161, 0.0120064),
+        ];
+        for (c, (i, j, difference, lower, upper, p)) in comparisons.iter().zip(expected) {
+            assert_eq!((c.i, c.j), (i, j));
+            assert_close!(c.difference, difference, 1e-12);
+            assert_close!(c.confidence_lower, lower, 1e-6);
+            assert_close!(c.confidence_upper, upper, 1e-6);
+            assert_close!(c.p_value, p, 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_studentized_range() {
+        // The range of 2 normal values is √2 |Z|.
+        assert_close!(studentized_range_cdf(1.959963984540054 * 2f64.sqrt(), 2.0, f64::INFINITY), 0.95, 1e-12);
+        assert_close!(studentized_range_quantile(0.95, 2.0, 1e9), 2.7718076, 1e-6);
+        assert_close!(studentized_range_quantile(0.95, 4.0, 20.0), 3.958293, 1e-6);
+        assert_close!(studentized_range_quantile(0.99, 5.0, 5.0), 8.421490577747939, 1e-7);
+        // From mpmath.
+        assert_close!(studentized_range_sf(5.0, 10.0, 20.0) / 0.0505679234168748, 1.0, 1e-10);
+        assert!(studentized_range_cdf(1.0, 1.0, 10.0).is_nan());
+        assert!(studentized_range_quantile(1.5, 3.0, 10.0).is_nan());
+    }
+
+    #[test]
+    fn test_studentized_range_two_groups() {
+        use crate::f64::distribution::{Distribution, StudentT};
+        // For k = 2, Q = √2 |T|, so the sf is 2 P(T > q/√2).
+        for df in [1.0, 2.0, 3.0, 5.0, 10.0, 30.0] {
+            for q in [0.01, 0.5, 1.0, 3.0, 6.0, 10.0, 30.0, 100.0, 300.0] {
+                let sf = 2.0 * StudentT::new(df).sf(q / 2f64.sqrt());
+                assert_close!(studentized_range_sf(q, 2.0, df) / sf, 1.0, 1e-10);
+                assert_close!(studentized_range_cdf(q, 2.0, df) / (1.0 - sf), 1.0, 1e-10);
+            }
+        }
+        // Large df, from mpmath.
+        let cases = [
+            (10.0, 100.0, 2.1218306803575242e-10),
+            (10.0, 1000.0, 2.884146857172615e-12),
+            (30.0, 1000.0, 9.352984454234356e-83),
+            (3.0, 26000.0, 0.033904288190291384),
+            (10.0, 26000.0, 1.5763177501928374e-12),
+            (3.0, 1e7, 0.033894878054063397),
+        ];
+        for (q, df, sf) in cases {
+            assert_close!(studentized_range_sf(q, 2.0, df) / sf, 1.0, 1e-10);
+        }
+        // For df = 1, the quantile is √2 tan(πp/2).
+        assert_close!(studentized_range_quantile(0.99, 2.0, 1.0) / 90.02422668900682, 1.0, 1e-10);
+        assert_close!(studentized_range_quantile(0.999, 2.0, 1.0) / 900.3155756765157, 1.0, 1e-10);
+    }
+
+}
@@ -114,12 +114,12 @@ mod test_assert_eq_f64_as_result {
             0.3333333333333334
         }
 
-        assert_eq!(A.is_completed(), false);
-        assert_eq!(B.is_completed(), false);
+        assert!(!A.is_completed());
+        assert!(!B.is_completed());
         let result = assert_eq_f64_as_result!(a(), b());
         assert!(result.is_ok());
-        assert_eq!(A.is_completed(), true);
-        assert_eq!(B.is_completed(), true);
+        assert!(A.is_completed());
+        assert!(B.is_completed());
     }
 
     #[test]
@@ -236,8 +236,7 @@ mod test_assert_eq_f64 {
         let a: f64 = 0.3333333333333333;
         let b: f64 = 0.3333333333333334;
         for _ in 0..1 {
-            let actual = assert_eq_f64!(a, b);
-            assert_eq!(actual, ());
+            assert_eq_f64!(a, b);
         }
     }
 
@@ -246,7 +245,7 @@ mod test_assert_eq_f64 {
         let a: f64 = 0.3333333333333333;
         let b: f64 = 0.3333333333333338;
         let result = panic::catch_unwind(|| {
-            let _actual = assert_eq_f64!(a, b);
+            assert_eq_f64!(a, b);
         });
         let message = concat!(
             "assertion failed: `assert_eq_f64!(a, b)`\n",
@@ -272,7 +271,7 @@ mod test_assert_eq_f64 {
         let a: f64 = 0.3333333333333338;
         let b: f64 = 0.3333333333333333;
         let result = panic::catch_unwind(|| {
-            let _actual = assert_eq_f64!(a, b);
+            assert_eq_f64!(a, b);
         });
         let message = concat!(
             "assertion failed: `assert_eq_f64!(a, b)`\n",
@@ -333,3 +332,58 @@ macro_rules! debug_assert_eq_f64 {
         }
     };
 }
+
+/// Assert two floating point numbers are equal within a tolerance, for
+/// tests of approximations, such as distributions and statistical tests.
+///
+/// Pseudocode:<br>
+/// |a - b| <= tolerance, or |a - b| <= tolerance * |b| with `relative = tolerance`
+///
+#[cfg(test)]
+macro_rules! assert_close {
+    ($a:expr, $b:expr, relative = $tolerance:expr $(,)?) => {
+        match ($a as f64, $b as f64, $tolerance as f64) {
+            (a, b, tolerance) => {
+                let delta = (a - b).abs();
+                assert!(
+                    delta <= tolerance * b.abs(),
+                    "assertion failed: `assert_close!({}, {}, relative = {})`\n a: `{:?}`,\n b: `{:?}`,\n Δ: `{}`",
+                    stringify!($a), stringify!($b), stringify!($tolerance), a, b, delta,
+                );
+            }
+        }
+    };
+    ($a:expr, $b:expr, $tolerance:expr $(,)?) => {
+        match ($a as f64, $b as f64, $tolerance as f64) {
+            (a, b, tolerance) => {
+                let delta = (a - b).abs();
+                assert!(
+                    delta <= tolerance,
+                    "assertion failed: `assert_close!({}, {}, {})`\n a: `{:?}`,\n b: `{:?}`,\n Δ: `{}`",
+                    stringify!($a), stringify!($b), stringify!($tolerance), a, b, delta,
+                );
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+pub(crate) use assert_close;
+
+#[cfg(test)]
+mod test_assert_close {
+    use std::panic;
+
+    #[test]
+    fn absolute() {
+        assert_close!(1.0, 1.0 + 1e-10, 1e-9);
+        assert!(panic::catch_unwind(|| assert_close!(1.0, 1.1, 1e-9)).is_err());
+        assert!(panic::catch_unwind(|| assert_close!(f64::NAN, 1.0, 1e-9)).is_err());
+    }
+
+    #[test]
+    fn relative() {
+        assert_close!(1e20, 1e20 + 1e10, relative = 1e-9);
+        assert!(panic::catch_unwind(|| assert_close!(1e-20, 2e-20, relative = 1e-9)).is_err());
+    }
+}
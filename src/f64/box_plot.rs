@@ -0,0 +1,143 @@
+//! Tukey box plot statistics, with fences, whiskers, and outliers.
+//!
+//! * Inner fences: Q1 - 1.5 IQR and Q3 + 1.5 IQR.
+//!
+//! * Outer fences: Q1 - 3 IQR and Q3 + 3 IQR.
+//!
+//! * Whiskers: the most extreme values within the inner fences.
+//!
+//! * Mild outliers: values between the inner and outer fences.
+//!
+//! * Extreme outliers: values beyond the outer fences.
+//!
+//! # Example
+//!
+//! ```rust
+//! use numeric_statistics::f64::box_plot::*;
+//!
+//! let values = &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 16.0, 100.0];
+//! let stats = BoxPlotStats::new(values);
+//! assert_eq!(stats.whisker_high, 8.0);
+//! assert_eq!(stats.mild_outliers, vec![Outlier { index: 8, value: 16.0 }]);
+//! assert_eq!(stats.extreme_outliers, vec![Outlier { index: 9, value: 100.0 }]);
+//! ```
+
+use super::five_number_summary::*;
+
+/// Multiple of the IQR from the quartiles to the inner fences.
+pub const INNER_FENCE: f64 = 1.5;
+
+/// Multiple of the IQR from the quartiles to the outer fences.
+pub const OUTER_FENCE: f64 = 3.0;
+
+/// An outlier with its index in the input values.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Outlier {
+    pub index: usize,
+    pub value: f64,
+}
+
+/// Tukey box plot statistics.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoxPlotStats {
+    pub summary: FiveNumberSummary,
+    pub lower_inner_fence: f64,
+    pub upper_inner_fence: f64,
+    pub lower_outer_fence: f64,
+    pub upper_outer_fence: f64,
+    /// Lowest value within the inner fences.
+    pub whisker_low: f64,
+    /// Highest value within the inner fences.
+    pub whisker_high: f64,
+    /// Values between the inner and outer fences, in input order.
+    pub mild_outliers: Vec<Outlier>,
+    /// Values beyond the outer fences, in input order.
+    pub extreme_outliers: Vec<Outlier>,
+}
+
+impl BoxPlotStats {
+    /// Calculate Tukey box plot statistics for values.
+    ///
+    /// Return NaN fields and no outliers if the values are empty.
+    ///
+    /// Filter NaN values in the stream; outlier indices still refer to
+    /// positions in the input values.
+    ///
+    pub fn new<T: AsRef<[f64]>>(values: T) -> BoxPlotStats {
+        let values = values.as_ref();
+        let summary = five_number_summary(values);
+        let iqr = summary.interquartile_range();
+        let lower_inner_fence = summary.q1 - INNER_FENCE * iqr;
+        let upper_inner_fence = summary.q3 + INNER_FENCE * iqr;
+        let lower_outer_fence = summary.q1 - OUTER_FENCE * iqr;
+        let upper_outer_fence = summary.q3 + OUTER_FENCE * iqr;
+        let mut whisker_low = f64::NAN;
+        let mut whisker_high = f64::NAN;
+        let mut mild_outliers = Vec::new();
+        let mut extreme_outliers = Vec::new();
+        values.iter().enumerate().filter(|(_, x)| !x.is_nan()).for_each(|(index, x)| {
+            let outlier = Outlier { index, value: *x };
+            if *x < lower_outer_fence || *x > upper_outer_fence {
+                extreme_outliers.push(outlier);
+            } else if *x < lower_inner_fence || *x > upper_inner_fence {
+                mild_outliers.push(outlier);
+            } else {
+                whisker_low = whisker_low.min(*x);
+                whisker_high = whisker_high.max(*x);
+            }
+        });
+        BoxPlotStats {
+            summary,
+            lower_inner_fence,
+            upper_inner_fence,
+            lower_outer_fence,
+            upper_outer_fence,
+            whisker_low,
+            whisker_high,
+            mild_outliers,
+            extreme_outliers,
+        }
+    }
+
+    /// Iterate all outliers, mild and extreme.
+    pub fn outliers(&self) -> impl Iterator<Item = &Outlier> {
+        self.mild_outliers.iter().chain(self.extreme_outliers.iter())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_empty() {
+        let x: &[f64] = &[];
+        let stats = BoxPlotStats::new(x);
+        assert!(stats.whisker_low.is_nan());
+        assert!(stats.upper_inner_fence.is_nan());
+        assert_eq!(stats.outliers().count(), 0);
+    }
+
+    #[test]
+    fn test_no_outliers() {
+        let x = &[1.0, 2.0, 3.0, 4.0, 5.0];
+        let stats = BoxPlotStats::new(x);
+        assert_eq!(stats.lower_inner_fence, -1.0);
+        assert_eq!(stats.upper_inner_fence, 7.0);
+        assert_eq!(stats.lower_outer_fence, -4.0);
+        assert_eq!(stats.upper_outer_fence, 10.0);
+        assert_eq!((stats.whisker_low, stats.whisker_high), (1.0, 5.0));
+        assert_eq!(stats.outliers().count(), 0);
+    }
+
+    #[test]
+    fn test_outliers_with_nans_keep_indices() {
+        let x = &[-20.0, f64::NAN, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, -8.0];
+        let stats = BoxPlotStats::new(x);
+        assert_eq!(stats.summary.q1, 1.25);
+        assert_eq!(stats.mild_outliers, vec![Outlier { index: 10, value: -8.0 }]);
+        assert_eq!(stats.extreme_outliers, vec![Outlier { index: 0, value: -20.0 }]);
+        assert_eq!((stats.whisker_low, stats.whisker_high), (1.0, 8.0));
+    }
+
+}
@@ -0,0 +1,601 @@
+//! Chi-squared tests and contingency tables, for categorical data such as
+//! error codes by region: chi-squared goodness-of-fit and independence,
+//! with Yates' correction, the G-test, Fisher's exact test for 2 × 2
+//! tables, and the effect sizes Cramér's V and phi.
+//!
+//! A contingency table counts pairs of categories, such as (region, code),
+//! with a row per first category and a column per second category.
+//!
+//! # Example
+//!
+//! ```rust
+//! use numeric_statistics::f64::contingency::*;
+//!
+//! // Do request outcomes depend on the datacenter?
+//! let mut requests = Vec::new();
+//! requests.extend(std::iter::repeat(("east", "ok")).take(950));
+//! requests.extend(std::iter::repeat(("east", "error")).take(50));
+//! requests.extend(std::iter::repeat(("west", "ok")).take(900));
+//! requests.extend(std::iter::repeat(("west", "error")).take(100));
+//! let table = ContingencyTable::from_pairs(requests);
+//! assert_eq!(table.rows, vec!["east", "west"]);
+//! assert_eq!(table.counts, vec![vec![950, 50], vec![900, 100]]);
+//! let result = chi_squared_independence(&table, false);
+//! assert!(result.p_value < 0.001);
+//! ```
+
+use super::{
+    distribution::{ChiSquared, Distribution},
+    hypothesis::*,
+    special::ln_gamma,
+};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Table of counts of pairs of categories, with a row per row category and
+/// a column per column category, each in order of first appearance.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContingencyTable<R, C> {
+    /// Row categories.
+    pub rows: Vec<R>,
+    /// Column categories.
+    pub columns: Vec<C>,
+    /// Counts, indexed by row then column.
+    pub counts: Vec<Vec<u64>>,
+}
+
+impl<R: Eq + Hash + Clone, C: Eq + Hash + Clone> ContingencyTable<R, C> {
+    /// Create a table by counting pairs of row and column categories.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use numeric_statistics::f64::contingency::*;
+    /// let regions = ["east", "west", "east", "east"];
+    /// let codes = [200, 500, 500, 200];
+    /// let table = ContingencyTable::from_pairs(regions.into_iter().zip(codes));
+    /// assert_eq!(table.columns, vec![200, 500]);
+    /// assert_eq!(table.counts, vec![vec![2, 1], vec![0, 1]]);
+    /// ```
+    ///
+    pub fn from_pairs<I: IntoIterator<Item = (R, C)>>(pairs: I) -> ContingencyTable<R, C> {
+        let mut rows = Vec::new();
+        let mut columns = Vec::new();
+        let mut row_index = HashMap::new();
+        let mut column_index = HashMap::new();
+        let mut counts: Vec<Vec<u64>> = Vec::new();
+        for (row, column) in pairs {
+            let i = *row_index.entry(row.clone()).or_insert_with(|| {
+                rows.push(row);
+                counts.push(vec![0; columns.len()]);
+                rows.len() - 1
+            });
+            let j = *column_index.entry(column.clone()).or_insert_with(|| {
+                columns.push(column);
+                counts.iter_mut().for_each(|r| r.push(0));
+                columns.len() - 1
+            });
+            counts[i][j] += 1;
+        }
+        ContingencyTable { rows, columns, counts }
+    }
+}
+
+impl<R: Eq + Hash + Clone, C: Eq + Hash + Clone> FromIterator<(R, C)> for ContingencyTable<R, C> {
+    fn from_iter<I: IntoIterator<Item = (R, C)>>(pairs: I) -> Self {
+        ContingencyTable::from_pairs(pairs)
+    }
+}
+
+impl ContingencyTable<usize, usize> {
+    /// Create a table from counts, indexed by row then column, with
+    /// categories that are the indexes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rows have different lengths.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use numeric_statistics::f64::contingency::*;
+    /// let table = ContingencyTable::from_counts(vec![vec![3, 1], vec![1, 3]]);
+    /// assert_eq!(table.total(), 8);
+    /// ```
+    ///
+    pub fn from_counts(counts: Vec<Vec<u64>>) -> ContingencyTable<usize, usize> {
+        let width = counts.first().map_or(0, |row| row.len());
+        assert!(counts.iter().all(|row| row.len() == width), "rows must have the same length");
+        ContingencyTable {
+            rows: (0..counts.len()).collect(),
+            columns: (0..width).collect(),
+            counts,
+        }
+    }
+}
+
+impl<R, C> ContingencyTable<R, C> {
+    /// Total count of each row.
+    pub fn row_totals(&self) -> Vec<u64> {
+        self.counts.iter().map(|row| row.iter().sum()).collect()
+    }
+
+    /// Total count of each column.
+    pub fn column_totals(&self) -> Vec<u64> {
+        (0..self.columns.len()).map(|j| self.counts.iter().map(|row| row[j]).sum()).collect()
+    }
+
+    /// Total count of every pair.
+    pub fn total(&self) -> u64 {
+        self.counts.iter().flatten().sum()
+    }
+
+    /// Expected counts if rows and columns are independent, which are
+    /// row total × column total / total.
+    pub fn expected(&self) -> Vec<Vec<f64>> {
+        let total = self.total() as f64;
+        let column_totals = self.column_totals();
+        self.row_totals()
+            .iter()
+            .map(|r| column_totals.iter().map(|c| (*r as f64) * (*c as f64) / total).collect())
+            .collect()
+    }
+
+    /// Observed and expected counts, without rows and columns whose totals
+    /// are 0, since they have no expected counts.
+    fn observed_and_expected(&self) -> (Vec<Vec<f64>>, Vec<Vec<f64>>) {
+        let row_totals = self.row_totals();
+        let column_totals = self.column_totals();
+        let keep_rows: Vec<usize> = (0..row_totals.len()).filter(|i| row_totals[*i] > 0).collect();
+        let keep_columns: Vec<usize> = (0..column_totals.len()).filter(|j| column_totals[*j] > 0).collect();
+        let total = self.total() as f64;
+        let observed = keep_rows
+            .iter()
+            .map(|i| keep_columns.iter().map(|j| self.counts[*i][*j] as f64).collect())
+            .collect();
+        let expected = keep_rows
+            .iter()
+            .map(|i| keep_columns.iter().map(|j| row_totals[*i] as f64 * column_totals[*j] as f64 / total).collect())
+            .collect();
+        (observed, expected)
+    }
+}
+
+/// Expected counts from observed counts and probabilities, which are
+/// scaled to sum to 1. Return None if the lengths differ, if there are
+/// fewer than 2 categories, or if a probability is invalid.
+fn expected_counts(observed: &[u64], probabilities: &[f64]) -> Option<Vec<f64>> {
+    if observed.len() != probabilities.len() || observed.len() < 2 { return None; }
+    if probabilities.iter().any(|p| p.is_nan() || *p <= 0.0 || p.is_infinite()) { return None; }
+    let sum: f64 = probabilities.iter().sum();
+    let total = observed.iter().sum::<u64>() as f64;
+    if total == 0.0 { return None; }
+    Some(probabilities.iter().map(|p| p / sum * total).collect())
+}
+
+/// Pearson's chi-squared statistic Σ (o - e)² / e.
+fn pearson_statistic(observed: &[f64], expected: &[f64]) -> f64 {
+    observed.iter().zip(expected).map(|(o, e)| (o - e).powi(2) / e).sum()
+}
+
+/// G statistic 2 Σ o ln(o / e), where terms with o = 0 are 0.
+fn g_statistic(observed: &[f64], expected: &[f64]) -> f64 {
+    2.0 * observed
+        .iter()
+        .zip(expected)
+        .filter(|(o, _)| **o > 0.0)
+        .map(|(o, e)| o * (o / e).ln())
+        .sum::<f64>()
+}
+
+/// Result of a statistic with a chi-squared distribution.
+fn chi_squared_result(statistic: f64, degrees_of_freedom: f64, effect_size: f64) -> TestResult {
+    TestResult {
+        statistic,
+        degrees_of_freedom,
+        p_value: ChiSquared::new(degrees_of_freedom).sf(statistic),
+        effect_size,
+        ..TestResult::nan()
+    }
+}
+
+/// Calculate the chi-squared goodness-of-fit test of whether observed
+/// counts fit the probabilities of each category.
+///
+/// The probabilities are scaled to sum to 1, so equal weights such as
+/// `[1.0; k]` test for a uniform distribution.
+///
+/// The statistic is Pearson's χ² = Σ (o - e)² / e, with k - 1 degrees of
+/// freedom. The effect size is Cohen's w = √(χ² / n).
+///
+/// The p-value is accurate if the expected counts are at least 5.
+///
+/// Return NaN statistics if the lengths differ, if there are fewer than 2
+/// categories, if there are no counts, or if any probability is not
+/// positive.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::contingency::*;
+/// // Are errors uniform across 4 shards?
+/// let result = chi_squared_goodness_of_fit([30, 20, 25, 25], [1.0; 4]);
+/// assert_eq!(result.statistic, 2.0);
+/// assert_eq!(result.degrees_of_freedom, 3.0);
+/// assert!((result.p_value - 0.5724067044708798).abs() < 1e-12);
+/// ```
+///
+pub fn chi_squared_goodness_of_fit<O: AsRef<[u64]>, P: AsRef<[f64]>>(observed: O, probabilities: P) -> TestResult {
+    let observed = observed.as_ref();
+    let Some(expected) = expected_counts(observed, probabilities.as_ref()) else { return TestResult::nan(); };
+    let observed: Vec<f64> = observed.iter().map(|o| *o as f64).collect();
+    let statistic = pearson_statistic(&observed, &expected);
+    let total: f64 = observed.iter().sum();
+    chi_squared_result(statistic, (observed.len() - 1) as f64, (statistic / total).sqrt())
+}
+
+/// Calculate the G-test of goodness-of-fit, which is the likelihood ratio
+/// test, of whether observed counts fit the probabilities of each category.
+///
+/// The statistic is G = 2 Σ o ln(o / e), with k - 1 degrees of freedom.
+/// The other fields are NaN.
+///
+/// Return NaN statistics like `chi_squared_goodness_of_fit`.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::contingency::*;
+/// let result = g_test_goodness_of_fit([30, 20, 25, 25], [1.0; 4]);
+/// assert!((result.statistic - 2.0135513550688877).abs() < 1e-12);
+/// ```
+///
+pub fn g_test_goodness_of_fit<O: AsRef<[u64]>, P: AsRef<[f64]>>(observed: O, probabilities: P) -> TestResult {
+    let observed = observed.as_ref();
+    let Some(expected) = expected_counts(observed, probabilities.as_ref()) else { return TestResult::nan(); };
+    let observed: Vec<f64> = observed.iter().map(|o| *o as f64).collect();
+    chi_squared_result(g_statistic(&observed, &expected), (observed.len() - 1) as f64, f64::NAN)
+}
+
+/// Calculate the chi-squared test of independence of the rows and columns
+/// of the table.
+///
+/// The statistic is Pearson's χ² = Σ (o - e)² / e, with (r - 1)(c - 1)
+/// degrees of freedom, where rows and columns with total 0 are skipped.
+/// The effect size is Cramér's V.
+///
+/// If `yates` is true and the table is 2 × 2, subtract Yates' continuity
+/// correction from each |o - e|, which is 0.5 or |o - e| if less, like R's
+/// `chisq.test`.
+///
+/// The p-value is accurate if the expected counts are at least 5; for
+/// smaller 2 × 2 tables use `fisher_exact`.
+///
+/// Return NaN statistics if there are fewer than 2 rows or columns with
+/// counts.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::contingency::*;
+/// let table = ContingencyTable::from_counts(vec![vec![762, 327, 468], vec![484, 239, 477]]);
+/// let result = chi_squared_independence(&table, false);
+/// assert!((result.statistic - 30.070149095754687).abs() < 1e-9);
+/// assert_eq!(result.degrees_of_freedom, 2.0);
+/// assert!((result.p_value - 2.9535891832117357e-7).abs() < 1e-15);
+/// ```
+///
+pub fn chi_squared_independence<R, C>(table: &ContingencyTable<R, C>, yates: bool) -> TestResult {
+    let (observed, expected) = table.observed_and_expected();
+    let (r, c) = (observed.len(), observed.first().map_or(0, |row| row.len()));
+    if r < 2 || c < 2 { return TestResult::nan(); }
+    let (observed, expected) = (observed.concat(), expected.concat());
+    let statistic = if yates && r == 2 && c == 2 {
+        let correction = observed.iter().zip(&expected).map(|(o, e)| (o - e).abs()).fold(0.5, f64::min);
+        observed.iter().zip(&expected).map(|(o, e)| ((o - e).abs() - correction).powi(2) / e).sum()
+    } else {
+        pearson_statistic(&observed, &expected)
+    };
+    chi_squared_result(statistic, ((r - 1) * (c - 1)) as f64, cramers_v(table))
+}
+
+/// Calculate the G-test of independence, which is the likelihood ratio
+/// test, of the rows and columns of the table.
+///
+/// The statistic is G = 2 Σ o ln(o / e), with (r - 1)(c - 1) degrees of
+/// freedom, where rows and columns with total 0 are skipped. The other
+/// fields are NaN.
+///
+/// Return NaN statistics if there are fewer than 2 rows or columns with
+/// counts.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::contingency::*;
+/// let table = ContingencyTable::from_counts(vec![vec![762, 327, 468], vec![484, 239, 477]]);
+/// let result = g_test(&table);
+/// assert!((result.statistic - 30.016692613239655).abs() < 1e-9);
+/// ```
+///
+pub fn g_test<R, C>(table: &ContingencyTable<R, C>) -> TestResult {
+    let (observed, expected) = table.observed_and_expected();
+    let (r, c) = (observed.len(), observed.first().map_or(0, |row| row.len()));
+    if r < 2 || c < 2 { return TestResult::nan(); }
+    chi_squared_result(g_statistic(&observed.concat(), &expected.concat()), ((r - 1) * (c - 1)) as f64, f64::NAN)
+}
+
+/// Calculate Cramér's V, the strength of association of the rows and
+/// columns of the table, from 0.0 for independent to 1.0 for determined,
+/// which is √(χ² / (n (min(r, c) - 1))), without Yates' correction.
+///
+/// Return NaN if there are fewer than 2 rows or columns with counts.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::contingency::*;
+/// let table = ContingencyTable::from_counts(vec![vec![10, 0, 0], vec![0, 10, 0], vec![0, 0, 10]]);
+/// assert!((cramers_v(&table) - 1.0).abs() < 1e-15);
+/// ```
+///
+pub fn cramers_v<R, C>(table: &ContingencyTable<R, C>) -> f64 {
+    let (observed, expected) = table.observed_and_expected();
+    let (r, c) = (observed.len(), observed.first().map_or(0, |row| row.len()));
+    if r < 2 || c < 2 { return f64::NAN; }
+    let statistic = pearson_statistic(&observed.concat(), &expected.concat());
+    (statistic / (table.total() as f64 * (r.min(c) - 1) as f64)).sqrt()
+}
+
+/// Calculate the phi coefficient of a 2 × 2 table, the correlation of the
+/// row and column indicators, from -1.0 to 1.0, which is
+/// (ad - bc) / √(row totals × column totals). Its absolute value equals
+/// Cramér's V.
+///
+/// Return NaN if the table is not 2 × 2, or if any row or column total is 0.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::contingency::*;
+/// let table = ContingencyTable::from_counts(vec![vec![3, 1], vec![1, 3]]);
+/// assert_eq!(phi(&table), 0.5);
+/// ```
+///
+pub fn phi<R, C>(table: &ContingencyTable<R, C>) -> f64 {
+    let Some([a, b, c, d]) = two_by_two(table) else { return f64::NAN; };
+    let product = [a + b, c + d, a + c, b + d].iter().map(|t| *t as f64).product::<f64>();
+    if product == 0.0 { return f64::NAN; }
+    ((a * d) as f64 - (b * c) as f64) / product.sqrt()
+}
+
+/// Counts [a, b, c, d] of a 2 × 2 table, with a and b in the first row.
+fn two_by_two<R, C>(table: &ContingencyTable<R, C>) -> Option<[u64; 4]> {
+    match table.counts.as_slice() {
+        [top, bottom] if top.len() == 2 && bottom.len() == 2 => Some([top[0], top[1], bottom[0], bottom[1]]),
+        _ => None,
+    }
+}
+
+/// Hypergeometric distribution of the top left count of a 2 × 2 table,
+/// given the margins, with noncentral probabilities for an odds ratio.
+struct Hypergeometric {
+    /// Least possible count.
+    lower: u64,
+    /// Log of C(m, x) C(n, k - x) for each possible count x from lower up.
+    ln_weights: Vec<f64>,
+}
+
+impl Hypergeometric {
+    fn new(row_1: u64, row_2: u64, column_1: u64) -> Hypergeometric {
+        let lower = column_1.saturating_sub(row_2);
+        let upper = column_1.min(row_1);
+        let ln_choose = |n: u64, k: u64| ln_gamma((n + 1) as f64) - ln_gamma((k + 1) as f64) - ln_gamma((n - k + 1) as f64);
+        let ln_weights = (lower..=upper).map(|x| ln_choose(row_1, x) + ln_choose(row_2, column_1 - x)).collect();
+        Hypergeometric { lower, ln_weights }
+    }
+
+    fn upper(&self) -> u64 {
+        self.lower + self.ln_weights.len() as u64 - 1
+    }
+
+    /// Probabilities of each possible count, for the log of the odds ratio.
+    fn probabilities(&self, ln_odds_ratio: f64) -> Vec<f64> {
+        let ln: Vec<f64> = self
+            .ln_weights
+            .iter()
+            .enumerate()
+            .map(|(i, w)| w + i as f64 * ln_odds_ratio)
+            .collect();
+        let max = ln.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let weights: Vec<f64> = ln.iter().map(|x| (x - max).exp()).collect();
+        let sum: f64 = weights.iter().sum();
+        weights.iter().map(|w| w / sum).collect()
+    }
+
+    /// P(X <= x) for the log of the odds ratio.
+    fn cdf(&self, x: u64, ln_odds_ratio: f64) -> f64 {
+        self.probabilities(ln_odds_ratio)[..=(x - self.lower) as usize].iter().sum()
+    }
+
+    /// P(X >= x) for the log of the odds ratio.
+    fn sf(&self, x: u64, ln_odds_ratio: f64) -> f64 {
+        self.probabilities(ln_odds_ratio)[(x - self.lower) as usize..].iter().sum()
+    }
+
+    /// Mean count for the log of the odds ratio.
+    fn mean(&self, ln_odds_ratio: f64) -> f64 {
+        self.probabilities(ln_odds_ratio)
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (self.lower + i as u64) as f64 * p)
+            .sum()
+    }
+}
+
+/// Solve for the log of the odds ratio where the increasing function
+/// equals the target, by bisection.
+fn solve_ln_odds_ratio(f: impl Fn(f64) -> f64, target: f64) -> f64 {
+    let (mut lower, mut upper) = (-100.0, 100.0);
+    for _ in 0..200 {
+        let middle = (lower + upper) / 2.0;
+        if f(middle) < target { lower = middle; } else { upper = middle; }
+    }
+    (lower + upper) / 2.0
+}
+
+/// Calculate Fisher's exact test of independence of the rows and columns of
+/// a 2 × 2 table, which is exact for any counts, like R's `fisher.test`.
+///
+/// The p-value is from the hypergeometric distribution of the top left
+/// count given the margins. For `Alternative::TwoSided` it sums the
+/// probabilities of tables no more likely than the observed table.
+///
+/// The estimate is the conditional maximum likelihood estimate of the odds
+/// ratio, with an exact confidence interval, both solved to more precision
+/// than R's. The effect size is phi. The statistic and degrees of freedom
+/// are NaN.
+///
+/// Return NaN statistics if the table is not 2 × 2 or if any row or column
+/// total is 0. Return NaN bounds if the confidence is not within 0.0 to
+/// 1.0 exclusive.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::{contingency::*, hypothesis::*};
+/// // Fisher's tea tasting: 3 of 4 cups with milk first were guessed.
+/// let table = ContingencyTable::from_counts(vec![vec![3, 1], vec![1, 3]]);
+/// let result = fisher_exact(&table, Alternative::Greater, 0.95);
+/// assert!((result.p_value - 17.0 / 70.0).abs() < 1e-12);
+/// assert!((result.estimate - 6.408319658199674).abs() < 1e-9);
+/// ```
+///
+pub fn fisher_exact<R, C>(table: &ContingencyTable<R, C>, alternative: Alternative, confidence: f64) -> TestResult {
+    let Some([a, b, c, d]) = two_by_two(table) else { return TestResult::nan(); };
+    if [a + b, c + d, a + c, b + d].contains(&0) { return TestResult::nan(); }
+    let distribution = Hypergeometric::new(a + b, c + d, a + c);
+    let (lower, upper) = (distribution.lower, distribution.upper());
+    let probabilities = distribution.probabilities(0.0);
+    let p_value = match alternative {
+        Alternative::Less => distribution.cdf(a, 0.0),
+        Alternative::Greater => distribution.sf(a, 0.0),
+        Alternative::TwoSided => {
+            // Relative tolerance for tables as likely as the observed table.
+            let observed = probabilities[(a - lower) as usize] * (1.0 + 1e-7);
+            probabilities.iter().filter(|p| **p <= observed).sum()
+        }
+    };
+
+    let estimate = if a == lower {
+        0.0
+    } else if a == upper {
+        f64::INFINITY
+    } else {
+        solve_ln_odds_ratio(|t| distribution.mean(t), a as f64).exp()
+    };
+    // Bounds where the tail probability of the observed count is alpha.
+    let lower_bound = |alpha: f64| {
+        if a == lower { return 0.0; }
+        solve_ln_odds_ratio(|t| distribution.sf(a, t), alpha).exp()
+    };
+    let upper_bound = |alpha: f64| {
+        if a == upper { return f64::INFINITY; }
+        solve_ln_odds_ratio(|t| -distribution.cdf(a, t), -alpha).exp()
+    };
+    let (confidence_lower, confidence_upper) = if !(confidence > 0.0 && confidence < 1.0) {
+        (f64::NAN, f64::NAN)
+    } else {
+        match alternative {
+            Alternative::TwoSided => (lower_bound((1.0 - confidence) / 2.0), upper_bound((1.0 - confidence) / 2.0)),
+            Alternative::Less => (0.0, upper_bound(1.0 - confidence)),
+            Alternative::Greater => (lower_bound(1.0 - confidence), f64::INFINITY),
+        }
+    };
+    TestResult {
+        p_value: p_value.min(1.0),
+        estimate,
+        confidence_lower,
+        confidence_upper,
+        effect_size: phi(table),
+        ..TestResult::nan()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::f64::assert_eq_f64::assert_close;
+
+    #[test]
+    fn test_empty() {
+        let table: ContingencyTable<&str, &str> = std::iter::empty().collect();
+        assert_eq!(table.total(), 0);
+        assert!(chi_squared_independence(&table, true).p_value.is_nan());
+        assert!(g_test(&table).p_value.is_nan());
+        assert!(cramers_v(&table).is_nan());
+        assert!(phi(&table).is_nan());
+        assert!(fisher_exact(&table, Alternative::TwoSided, 0.95).p_value.is_nan());
+        assert!(chi_squared_goodness_of_fit([1, 2], [1.0]).p_value.is_nan());
+        assert!(chi_squared_goodness_of_fit([1, 2], [1.0, 0.0]).p_value.is_nan());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_counts_ragged() {
+        ContingencyTable::from_counts(vec![vec![1, 2], vec![3]]);
+    }
+
+    #[test]
+    fn test_from_pairs() {
+        let table: ContingencyTable<char, bool> = [('a', true), ('b', false), ('a', false), ('c', true)].into_iter().collect();
+        assert_eq!(table.rows, vec!['a', 'b', 'c']);
+        assert_eq!(table.columns, vec![true, false]);
+        assert_eq!(table.counts, vec![vec![1, 1], vec![0, 1], vec![1, 0]]);
+        assert_eq!(table.row_totals(), vec![2, 1, 1]);
+        assert_eq!(table.column_totals(), vec![2, 2]);
+        assert_eq!(table.expected()[0], vec![1.0, 1.0]);
+    }
+
+    #[test]
+    fn test_chi_squared_yates() {
+        let table = ContingencyTable::from_counts(vec![vec![12, 5], vec![7, 9]]);
+        let result = chi_squared_independence(&table, true);
+        assert_close!(result.statistic, 1.455996378814684, 1e-12);
+        assert_close!(result.p_value, 0.22756821457580978, 1e-12);
+        let uncorrected = chi_squared_independence(&table, false);
+        assert_close!(uncorrected.statistic, 2.4305755196815575, 1e-12);
+        assert_close!(uncorrected.effect_size, phi(&table), 1e-15);
+    }
+
+    #[test]
+    fn test_zero_totals() {
+        // Skip the column with total 0.
+        let table = ContingencyTable::from_counts(vec![vec![12, 0, 5], vec![7, 0, 9]]);
+        let result = chi_squared_independence(&table, false);
+        assert_eq!(result.degrees_of_freedom, 1.0);
+        assert_close!(result.statistic, 2.4305755196815575, 1e-12);
+    }
+
+    #[test]
+    fn test_fisher_exact() {
+        let table = ContingencyTable::from_counts(vec![vec![3, 1], vec![1, 3]]);
+        let result = fisher_exact(&table, Alternative::TwoSided, 0.95);
+        assert_close!(result.p_value, 34.0 / 70.0, 1e-12);
+        // R's fisher.test solves with a loose tolerance, so matches to about 4 digits.
+        assert_close!(result.estimate, 6.408319658199674, 1e-9);
+        assert_close!(result.confidence_lower, 0.21173559544657857, 1e-9);
+        assert_close!(result.confidence_upper, 626.243530588814, 1e-6);
+        let greater = fisher_exact(&table, Alternative::Greater, 0.95);
+        assert_close!(greater.confidence_lower, 0.31357376750498517, 1e-9);
+        assert_eq!(greater.confidence_upper, f64::INFINITY);
+        let less = fisher_exact(&table, Alternative::Less, 0.95);
+        assert_close!(less.p_value, 69.0 / 70.0, 1e-12);
+        // At the edge of the support, the estimate is 0 or infinity.
+        let table = ContingencyTable::from_counts(vec![vec![4, 0], vec![0, 4]]);
+        let result = fisher_exact(&table, Alternative::TwoSided, 0.95);
+        assert_eq!(result.estimate, f64::INFINITY);
+        assert_close!(result.p_value, 2.0 / 70.0, 1e-12);
+    }
+
+}
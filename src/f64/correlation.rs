@@ -0,0 +1,281 @@
+//! Covariance and correlation of two variables: Pearson's r,
+//! Spearman's rho, and Kendall's tau-b.
+//!
+//! Each function takes paired values xs and ys, such as latency and packet
+//! size. NaN handling is pairwise: a pair is dropped if either side is NaN.
+//! Each function returns NaN if xs and ys have different lengths.
+//!
+//! # Example
+//!
+//! ```rust
+//! use numeric_statistics::f64::correlation::*;
+//!
+//! let sizes = [64.0, 128.0, 256.0, 512.0, 1024.0];
+//! let latencies = [1.1, 1.2, 1.5, 1.9, 3.1];
+//! assert!(pearson(sizes, latencies) > 0.99);
+//! assert_eq!(spearman(sizes, latencies), 1.0);
+//! assert_eq!(kendall_tau_b(sizes, latencies), 1.0);
+//! ```
+
+use super::{average, rank::*};
+
+/// Drop each pair where either side is NaN.
+///
+/// Return None if xs and ys have different lengths.
+fn pairs(xs: &[f64], ys: &[f64]) -> Option<(Vec<f64>, Vec<f64>)> {
+    if xs.len() != ys.len() { return None; }
+    Some(xs.iter().zip(ys).filter(|(x, y)| !x.is_nan() && !y.is_nan()).unzip())
+}
+
+/// Sum of products of deltas from the averages, and the count of pairs.
+fn co_delta_sum(xs: &[f64], ys: &[f64]) -> (f64, usize) {
+    let (x_average, y_average) = (average(xs), average(ys));
+    let sum = xs.iter().zip(ys).map(|(x, y)| (x - x_average) * (y - y_average)).sum();
+    (sum, xs.len())
+}
+
+/// Calculate statistical sample covariance for paired values,
+/// which divides by n - 1.
+///
+/// Return NaN if there are fewer than 2 pairs.
+///
+/// Filter pairs with a NaN value in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::correlation::*;
+/// let covariance = covariance([1.0, 2.0, 3.0], [1.0, 3.0, 2.0]);
+/// assert_eq!(covariance, 0.5);
+/// ```
+///
+pub fn covariance<X: AsRef<[f64]>, Y: AsRef<[f64]>>(xs: X, ys: Y) -> f64 {
+    let Some((xs, ys)) = pairs(xs.as_ref(), ys.as_ref()) else { return f64::NAN; };
+    if xs.len() < 2 { return f64::NAN; }
+    let (sum, n) = co_delta_sum(&xs, &ys);
+    sum / (n - 1) as f64
+}
+
+/// Calculate statistical population covariance for paired values,
+/// which divides by n.
+///
+/// Return NaN if there are no pairs.
+///
+/// Filter pairs with a NaN value in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::correlation::*;
+/// let covariance = covariance_population([1.0, 2.0, 3.0], [1.0, 3.0, 2.0]);
+/// assert_eq!(covariance, 1.0 / 3.0);
+/// ```
+///
+pub fn covariance_population<X: AsRef<[f64]>, Y: AsRef<[f64]>>(xs: X, ys: Y) -> f64 {
+    let Some((xs, ys)) = pairs(xs.as_ref(), ys.as_ref()) else { return f64::NAN; };
+    if xs.is_empty() { return f64::NAN; }
+    let (sum, n) = co_delta_sum(&xs, &ys);
+    sum / n as f64
+}
+
+/// Pearson correlation of paired values without NaN.
+fn pearson_pairs(xs: &[f64], ys: &[f64]) -> f64 {
+    if xs.len() < 2 { return f64::NAN; }
+    let (x_average, y_average) = (average(xs), average(ys));
+    let (mut xy, mut xx, mut yy) = (0.0, 0.0, 0.0);
+    for (x, y) in xs.iter().zip(ys) {
+        let (dx, dy) = (x - x_average, y - y_average);
+        xy += dx * dy;
+        xx += dx * dx;
+        yy += dy * dy;
+    }
+    if xx == 0.0 || yy == 0.0 { return f64::NAN; }
+    (xy / (xx * yy).sqrt()).clamp(-1.0, 1.0)
+}
+
+/// Calculate Pearson's correlation coefficient r for paired values,
+/// which measures linear association from -1.0 to 1.0.
+///
+/// Return NaN if there are fewer than 2 pairs, or if either side has all values equal.
+///
+/// Filter pairs with a NaN value in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::correlation::*;
+/// let r = pearson([1.0, 2.0, 3.0, 4.0, 5.0], [2.0, 4.0, 5.0, 4.0, 5.0]);
+/// assert!((r - 0.7745966692414834).abs() < 1e-12);
+/// ```
+///
+pub fn pearson<X: AsRef<[f64]>, Y: AsRef<[f64]>>(xs: X, ys: Y) -> f64 {
+    let Some((xs, ys)) = pairs(xs.as_ref(), ys.as_ref()) else { return f64::NAN; };
+    pearson_pairs(&xs, &ys)
+}
+
+/// Calculate Spearman's rank correlation coefficient rho for paired values,
+/// which measures monotonic association from -1.0 to 1.0.
+///
+/// This is Pearson's r of the ranks, where tied values share the average
+/// of their ranks, so it is exact with ties.
+///
+/// Return NaN if there are fewer than 2 pairs, or if either side has all values equal.
+///
+/// Filter pairs with a NaN value in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::correlation::*;
+/// let rho = spearman([1.0, 2.0, 3.0, 4.0, 5.0], [2.0, 4.0, 5.0, 4.0, 5.0]);
+/// assert!((rho - 0.7378647873726218).abs() < 1e-12);
+/// ```
+///
+pub fn spearman<X: AsRef<[f64]>, Y: AsRef<[f64]>>(xs: X, ys: Y) -> f64 {
+    let Some((xs, ys)) = pairs(xs.as_ref(), ys.as_ref()) else { return f64::NAN; };
+    pearson_pairs(&ranks(&xs), &ranks(&ys))
+}
+
+/// Count of pairs within groups of equal adjacent values.
+fn tied_pairs<T: PartialEq>(sorted: &[T]) -> f64 {
+    sorted.chunk_by(|a, b| a == b).map(|group| (group.len() * (group.len() - 1) / 2) as f64).sum()
+}
+
+/// Sort values with merge sort, and return the count of inversions,
+/// which are pairs where the greater value is before the lesser value.
+fn sort_counting_inversions(values: &mut [f64], buffer: &mut [f64]) -> u64 {
+    let n = values.len();
+    if n < 2 { return 0; }
+    let middle = n / 2;
+    let mut inversions = sort_counting_inversions(&mut values[..middle], &mut buffer[..middle])
+        + sort_counting_inversions(&mut values[middle..], &mut buffer[middle..]);
+    let (mut i, mut j) = (0, middle);
+    for slot in buffer[..n].iter_mut() {
+        if j == n || (i < middle && values[i] <= values[j]) {
+            *slot = values[i];
+            i += 1;
+        } else {
+            // Every remaining value on the left is greater than this one.
+            inversions += (middle - i) as u64;
+            *slot = values[j];
+            j += 1;
+        }
+    }
+    values.copy_from_slice(&buffer[..n]);
+    inversions
+}
+
+/// Calculate Kendall's rank correlation coefficient tau-b for paired values,
+/// which measures concordance of pairs from -1.0 to 1.0, corrected for ties.
+///
+/// This uses Knight's algorithm, which is O(n log n): sort by x, then
+/// count the discordant pairs as the swaps of a merge sort by y.
+///
+/// Return NaN if there are fewer than 2 pairs, or if either side has all values equal.
+///
+/// Filter pairs with a NaN value in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::correlation::*;
+/// let tau = kendall_tau_b([1.0, 2.0, 3.0, 4.0, 5.0], [2.0, 4.0, 5.0, 4.0, 5.0]);
+/// assert!((tau - 0.6708203932499369).abs() < 1e-12);
+/// ```
+///
+pub fn kendall_tau_b<X: AsRef<[f64]>, Y: AsRef<[f64]>>(xs: X, ys: Y) -> f64 {
+    let Some((xs, ys)) = pairs(xs.as_ref(), ys.as_ref()) else { return f64::NAN; };
+    let n = xs.len();
+    if n < 2 { return f64::NAN; }
+    let mut pairs: Vec<(f64, f64)> = xs.into_iter().zip(ys).collect();
+    pairs.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1)));
+    let total = (n * (n - 1) / 2) as f64;
+    let x_ties = tied_pairs(&pairs.iter().map(|p| p.0).collect::<Vec<_>>());
+    let xy_ties = tied_pairs(&pairs);
+    let mut ys: Vec<f64> = pairs.iter().map(|p| p.1).collect();
+    let mut buffer = vec![0.0; n];
+    let discordant = sort_counting_inversions(&mut ys, &mut buffer) as f64;
+    let y_ties = tied_pairs(&ys);
+    let denominator = ((total - x_ties) * (total - y_ties)).sqrt();
+    if denominator == 0.0 { return f64::NAN; }
+    let concordant_minus_discordant = total - x_ties - y_ties + xy_ties - 2.0 * discordant;
+    (concordant_minus_discordant / denominator).clamp(-1.0, 1.0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Kendall's tau-b by comparing every pair, in O(n^2).
+    fn kendall_naive(xs: &[f64], ys: &[f64]) -> f64 {
+        let (mut s, mut x_untied, mut y_untied) = (0.0, 0.0, 0.0);
+        for i in 0..xs.len() {
+            for j in i + 1..xs.len() {
+                let (dx, dy) = ((xs[i] - xs[j]).signum(), (ys[i] - ys[j]).signum());
+                let (dx, dy) = (if xs[i] == xs[j] { 0.0 } else { dx }, if ys[i] == ys[j] { 0.0 } else { dy });
+                s += dx * dy;
+                x_untied += dx.abs();
+                y_untied += dy.abs();
+            }
+        }
+        s / (x_untied * y_untied).sqrt()
+    }
+
+    #[test]
+    fn test_empty() {
+        let x: &[f64] = &[];
+        assert!(covariance(x, x).is_nan());
+        assert!(covariance_population(x, x).is_nan());
+        assert!(pearson(x, x).is_nan());
+        assert!(spearman(x, x).is_nan());
+        assert!(kendall_tau_b(x, x).is_nan());
+    }
+
+    #[test]
+    fn test_nan_pairwise() {
+        let xs = [1.0, f64::NAN, 2.0, 3.0, 100.0];
+        let ys = [1.0, 5.0, 3.0, 2.0, f64::NAN];
+        assert_eq!(covariance(xs, ys), 0.5);
+        assert_eq!(pearson(xs, ys), 0.5);
+    }
+
+    #[test]
+    fn test_lengths_differ() {
+        assert!(pearson([1.0, 2.0], [1.0, 2.0, 3.0]).is_nan());
+    }
+
+    #[test]
+    fn test_values_equal() {
+        let xs = [1.0, 2.0, 3.0];
+        let ys = [4.0, 4.0, 4.0];
+        assert_eq!(covariance(xs, ys), 0.0);
+        assert!(pearson(xs, ys).is_nan());
+        assert!(spearman(xs, ys).is_nan());
+        assert!(kendall_tau_b(xs, ys).is_nan());
+    }
+
+    #[test]
+    fn test_covariance() {
+        let xs = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let ys = [2.0, 4.0, 5.0, 4.0, 5.0];
+        assert_eq!(covariance(xs, ys), 1.5);
+        assert_eq!(covariance_population(xs, ys), 1.2);
+    }
+
+    #[test]
+    fn test_negative() {
+        let xs = [1.0, 2.0, 3.0, 4.0];
+        let ys = [8.0, 6.0, 4.0, 2.0];
+        assert_eq!(pearson(xs, ys), -1.0);
+        assert_eq!(spearman(xs, ys), -1.0);
+        assert_eq!(kendall_tau_b(xs, ys), -1.0);
+    }
+
+    #[test]
+    fn test_kendall_matches_naive() {
+        let xs: Vec<f64> = (0..300).map(|i| ((i * 7919) % 37) as f64).collect();
+        let ys: Vec<f64> = (0..300).map(|i| ((i * 104729) % 23) as f64 + (i % 5) as f64).collect();
+        assert!((kendall_tau_b(&xs, &ys) - kendall_naive(&xs, &ys)).abs() < 1e-12);
+    }
+
+}
@@ -0,0 +1,233 @@
+//! Describe values with count, mean, standard deviation, min, percentiles,
+//! and max, like pandas `describe()`, with optional extra statistics.
+//!
+//! # Example
+//!
+//! ```rust
+//! use numeric_statistics::f64::describe::*;
+//!
+//! let values = &[1.0, 2.0, 3.0, 4.0, f64::NAN];
+//! let options = DescribeOptions::default().missing(true);
+//! let describe = describe(values, &options);
+//! println!("{}", describe.display().precision(2).unit("ms").align(true));
+//! ```
+//!
+//! Output:
+//!
+//! ```stdout
+//! count:      4
+//! mean:    2.50 ms
+//! std:     1.29 ms
+//! min:     1.00 ms
+//! 25%:     1.75 ms
+//! 50%:     2.50 ms
+//! 75%:     3.25 ms
+//! max:     4.00 ms
+//! missing:    1
+//! ```
+
+use std::fmt;
+use super::{
+    average,
+    display::*,
+    kurtosis::*,
+    quantile::*,
+    skewness::*,
+    standard_deviation::*,
+    variance::*,
+};
+
+/// Options for `describe`.
+///
+/// * `percentiles`: quantiles from 0.0 to 1.0; default 0.25, 0.5, 0.75.
+///
+/// * `skewness`, `kurtosis`, `missing`, `sum`: include the statistic; default false.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct DescribeOptions {
+    pub percentiles: Vec<f64>,
+    pub skewness: bool,
+    pub kurtosis: bool,
+    pub missing: bool,
+    pub sum: bool,
+}
+
+impl Default for DescribeOptions {
+    fn default() -> Self {
+        DescribeOptions {
+            percentiles: vec![0.25, 0.5, 0.75],
+            skewness: false,
+            kurtosis: false,
+            missing: false,
+            sum: false,
+        }
+    }
+}
+
+impl DescribeOptions {
+    pub fn percentiles<T: AsRef<[f64]>>(mut self, percentiles: T) -> Self {
+        self.percentiles = percentiles.as_ref().to_vec();
+        self
+    }
+
+    pub fn skewness(mut self, skewness: bool) -> Self {
+        self.skewness = skewness;
+        self
+    }
+
+    pub fn kurtosis(mut self, kurtosis: bool) -> Self {
+        self.kurtosis = kurtosis;
+        self
+    }
+
+    pub fn missing(mut self, missing: bool) -> Self {
+        self.missing = missing;
+        self
+    }
+
+    pub fn sum(mut self, sum: bool) -> Self {
+        self.sum = sum;
+        self
+    }
+}
+
+/// Description of values. Optional statistics are None unless chosen.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Describe {
+    /// Count of non-NaN values.
+    pub count: usize,
+    pub mean: f64,
+    /// Sample standard deviation.
+    pub std: f64,
+    pub min: f64,
+    /// Pairs of (p, quantile) for each chosen percentile.
+    pub percentiles: Vec<(f64, f64)>,
+    pub max: f64,
+    pub skewness: Option<f64>,
+    pub kurtosis: Option<f64>,
+    /// Count of NaN values.
+    pub missing: Option<usize>,
+    pub sum: Option<f64>,
+}
+
+/// Describe values with count, mean, standard deviation, min, percentiles,
+/// max, and any optional statistics.
+///
+/// Return NaN statistics and a zero count if the values are empty.
+///
+/// Filter NaN values in the stream; count them as missing.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::describe::*;
+/// let values = &[1.0, 2.0, 3.0, 4.0, 10.0];
+/// let options = DescribeOptions::default().percentiles([0.5, 0.9]).sum(true);
+/// let describe = describe(values, &options);
+/// assert_eq!(describe.count, 5);
+/// assert_eq!(describe.mean, 4.0);
+/// assert_eq!(describe.percentiles, vec![(0.5, 3.0), (0.9, 7.6000000000000005)]);
+/// assert_eq!(describe.sum, Some(20.0));
+/// ```
+///
+pub fn describe<T: AsRef<[f64]>>(values: T, options: &DescribeOptions) -> Describe {
+    let values = values.as_ref();
+    let sorted = sorted(values);
+    let mean = average(values);
+    Describe {
+        count: sorted.len(),
+        mean,
+        std: standard_deviation_with_variance(variance_with_average(values, mean)),
+        min: quantile_sorted(&sorted, 0.0),
+        percentiles: options.percentiles.iter().map(|p| (*p, quantile_sorted(&sorted, *p))).collect(),
+        max: quantile_sorted(&sorted, 1.0),
+        skewness: options.skewness.then(|| skewness(values)),
+        kurtosis: options.kurtosis.then(|| kurtosis(values)),
+        missing: options.missing.then(|| values.len() - sorted.len()),
+        sum: options.sum.then(|| sorted.iter().sum()),
+    }
+}
+
+impl Describe {
+    /// Create a display builder for formatting every field,
+    /// such as with precision, significant figures, and units.
+    pub fn display(&self) -> StatsDisplay {
+        let mut fields = vec![
+            Field::new("count", self.count as f64, Kind::Count),
+            Field::new("mean", self.mean, Kind::Value),
+            Field::new("std", self.std, Kind::Value),
+            Field::new("min", self.min, Kind::Value),
+        ];
+        fields.extend(self.percentiles.iter().map(|(p, x)| Field::new(percent_label(*p), *x, Kind::Value)));
+        fields.push(Field::new("max", self.max, Kind::Value));
+        if let Some(x) = self.skewness { fields.push(Field::new("skewness", x, Kind::Unitless)); }
+        if let Some(x) = self.kurtosis { fields.push(Field::new("kurtosis", x, Kind::Unitless)); }
+        if let Some(x) = self.missing { fields.push(Field::new("missing", x as f64, Kind::Count)); }
+        if let Some(x) = self.sum { fields.push(Field::new("sum", x, Kind::Value)); }
+        StatsDisplay::new(fields)
+    }
+}
+
+/// Label a quantile as a percent, such as 0.999 as "99.9%".
+fn percent_label(p: f64) -> String {
+    format!("{}%", (p * 100.0 * 1e9).round() / 1e9)
+}
+
+impl fmt::Display for Describe {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_empty() {
+        let x: &[f64] = &[];
+        let describe = describe(x, &DescribeOptions::default());
+        assert_eq!(describe.count, 0);
+        assert!(describe.mean.is_nan());
+        assert!(describe.percentiles.iter().all(|(_, x)| x.is_nan()));
+    }
+
+    #[test]
+    fn test_fmt() {
+        let x = &[1.0, 2.0, 4.0, f64::NAN];
+        let options = DescribeOptions::default().missing(true).sum(true);
+        assert_eq!(
+            describe(x, &options).to_string(),
+            concat!(
+                "count: 3\n",
+                "mean: 2.3333333333333335\n",
+                "std: 1.5275252316519465\n",
+                "min: 1.0\n",
+                "25%: 1.5\n",
+                "50%: 2.0\n",
+                "75%: 3.0\n",
+                "max: 4.0\n",
+                "missing: 1\n",
+                "sum: 7.0\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_skewness_and_kurtosis() {
+        let x = &[1.0, 2.0, 3.0, 4.0, 10.0];
+        let options = DescribeOptions::default().skewness(true).kurtosis(true);
+        let describe = describe(x, &options);
+        assert_eq!(describe.skewness, Some(skewness(x)));
+        assert_eq!(describe.kurtosis, Some(kurtosis(x)));
+        assert_eq!(describe.missing, None);
+    }
+
+    #[test]
+    fn test_percent_label() {
+        assert_eq!(percent_label(0.25), "25%");
+        assert_eq!(percent_label(0.999), "99.9%");
+        assert_eq!(percent_label(0.001), "0.1%");
+    }
+
+}
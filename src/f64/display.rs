@@ -0,0 +1,395 @@
+//! Display builder for formatting statistics with precision, significant
+//! figures, units, alignment, and scientific notation.
+//!
+//! # Example
+//!
+//! ```rust
+//! use numeric_statistics::f64::all::All;
+//!
+//! let values = &[1.0, 2.0, 4.0];
+//! let all = All::new(values);
+//! let s = all.display().precision(2).unit("ms").to_string();
+//! assert_eq!(s, concat!(
+//!     "min: 1.00 ms\n",
+//!     "max: 4.00 ms\n",
+//!     "average: 2.33 ms\n",
+//!     "variance: 2.33 ms²\n",
+//!     "standard deviation: 1.53 ms\n",
+//! ));
+//! ```
+
+use std::fmt;
+
+/// Kind of a displayed field, which determines how units apply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// A value in the display unit, such as min, max, or average.
+    Value,
+    /// A value in the display unit squared, such as variance.
+    Squared,
+    /// A count, which is shown as an integer without a unit.
+    Count,
+    /// A unitless value, such as skewness or a ratio.
+    Unitless,
+}
+
+/// Notation for displayed numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Notation {
+    /// Use scientific notation for very large or very small values.
+    Auto,
+    /// Always use fixed-point notation.
+    Fixed,
+    /// Always use scientific notation.
+    Scientific,
+}
+
+/// One labeled value to display.
+#[derive(Debug, Clone)]
+pub struct Field {
+    pub label: String,
+    pub value: f64,
+    pub kind: Kind,
+}
+
+impl Field {
+    pub fn new<S: Into<String>>(label: S, value: f64, kind: Kind) -> Field {
+        Field { label: label.into(), value, kind }
+    }
+}
+
+/// Values at or above this magnitude use scientific notation in auto mode.
+pub const SCIENTIFIC_ABOVE: f64 = 1e6;
+
+/// Nonzero values below this magnitude use scientific notation in auto mode.
+pub const SCIENTIFIC_BELOW: f64 = 1e-4;
+
+/// Time units in ascending order, with their size in seconds.
+const TIME_UNITS: [(&str, f64); 4] = [
+    ("ns", 1e-9),
+    ("µs", 1e-6),
+    ("ms", 1e-3),
+    ("s", 1.0),
+];
+
+/// Builder that formats a list of fields, one field per line.
+///
+/// The settings apply to every field:
+///
+/// * `precision(n)`: round to at most `n` decimal places.
+///
+/// * `significant(n)`: round to `n` significant figures; when combined
+///   with `precision`, the precision caps the number of decimal places.
+///   Integer digits beyond the significant figures show as zeros, such as
+///   1234.5 to 1230 and 999.96 to 1000 for 3 figures.
+///
+/// * `unit(u)`: append a unit; time units (ns, µs, us, ms, s) scale
+///   automatically so each value shows in the most readable unit.
+///
+/// * `align(true)`: pad labels and right-align values into columns.
+///
+/// * `notation(n)`: choose fixed, scientific, or automatic notation.
+///
+/// Without precision or significant figures, values use Rust's shortest
+/// round-trip representation, which matches the plain `Display` output.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::display::*;
+///
+/// let display = StatsDisplay::new(vec![
+///     Field::new("min", 0.00085, Kind::Value),
+///     Field::new("max", 2.5, Kind::Value),
+/// ]);
+/// let s = display.significant(3).unit("s").align(true).to_string();
+/// assert_eq!(s, "min:  850 µs\nmax: 2.50 s\n");
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct StatsDisplay {
+    fields: Vec<Field>,
+    precision: Option<usize>,
+    significant: Option<usize>,
+    unit: Option<String>,
+    auto_scale: bool,
+    align: bool,
+    notation: Notation,
+}
+
+impl StatsDisplay {
+    pub fn new(fields: Vec<Field>) -> StatsDisplay {
+        StatsDisplay {
+            fields,
+            precision: None,
+            significant: None,
+            unit: None,
+            auto_scale: true,
+            align: false,
+            notation: Notation::Auto,
+        }
+    }
+
+    /// Round to at most `precision` decimal places.
+    pub fn precision(mut self, precision: usize) -> Self {
+        self.precision = Some(precision);
+        self
+    }
+
+    /// Round to `significant` significant figures, minimum 1.
+    pub fn significant(mut self, significant: usize) -> Self {
+        self.significant = Some(significant.max(1));
+        self
+    }
+
+    /// Append a unit to each value, such as "ms".
+    pub fn unit<S: Into<String>>(mut self, unit: S) -> Self {
+        self.unit = Some(unit.into());
+        self
+    }
+
+    /// Scale time units automatically, such as µs to ms to s. Default true.
+    pub fn auto_scale(mut self, auto_scale: bool) -> Self {
+        self.auto_scale = auto_scale;
+        self
+    }
+
+    /// Align labels and values into columns. Default false.
+    pub fn align(mut self, align: bool) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Choose the notation. Default auto.
+    pub fn notation(mut self, notation: Notation) -> Self {
+        self.notation = notation;
+        self
+    }
+
+    /// Format one value with its unit, without any alignment padding.
+    pub fn format_value(&self, value: f64, kind: Kind) -> (String, String) {
+        if kind == Kind::Count {
+            return (format!("{}", value), String::new());
+        }
+        if !value.is_finite() {
+            return (format!("{:?}", value), String::new());
+        }
+        let (value, unit) = match (&self.unit, kind) {
+            (Some(unit), Kind::Value) => self.scale(value, unit, 1),
+            (Some(unit), Kind::Squared) => {
+                let (value, unit) = self.scale(value, unit, 2);
+                (value, format!("{}²", unit))
+            }
+            _ => (value, String::new()),
+        };
+        (self.format_number(value), unit)
+    }
+
+    /// Scale a value in `unit` raised to `power` into the most readable time unit.
+    fn scale(&self, value: f64, unit: &str, power: i32) -> (f64, String) {
+        let unit_name = if unit == "us" { "µs" } else { unit };
+        let from = TIME_UNITS.iter().position(|(name, _)| *name == unit_name);
+        let from = match from {
+            Some(from) if self.auto_scale && value != 0.0 => from,
+            _ => return (value, unit.to_string()),
+        };
+        let seconds = value.abs().powf(1.0 / power as f64) * TIME_UNITS[from].1;
+        let to = TIME_UNITS
+            .iter()
+            .rposition(|(_, size)| seconds >= *size * (1.0 - f64::EPSILON))
+            .unwrap_or(0);
+        let factor = (TIME_UNITS[from].1 / TIME_UNITS[to].1).powi(power);
+        (value * factor, TIME_UNITS[to].0.to_string())
+    }
+
+    /// Format a finite number per the precision, significant, and notation settings.
+    fn format_number(&self, value: f64) -> String {
+        // Round first, so a carry such as 999.96 to 1000 picks the notation and the decimals.
+        let value = match self.significant {
+            Some(significant) => format!("{:.*e}", significant - 1, value).parse().unwrap_or(value),
+            None => value,
+        };
+        let magnitude = value.abs();
+        let scientific = match self.notation {
+            Notation::Fixed => false,
+            Notation::Scientific => true,
+            Notation::Auto => {
+                (self.significant.is_some() || self.precision.is_some())
+                && (magnitude >= SCIENTIFIC_ABOVE || (magnitude != 0.0 && magnitude < SCIENTIFIC_BELOW))
+            }
+        };
+        if scientific {
+            return match (self.significant, self.precision) {
+                (Some(significant), _) => format!("{:.*e}", significant - 1, value),
+                (None, Some(precision)) => format!("{:.*e}", precision, value),
+                (None, None) => format!("{:e}", value),
+            };
+        }
+        match (self.significant, self.precision) {
+            (Some(significant), _) if magnitude >= 10f64.powi(significant as i32) => {
+                // Round away the integer digits beyond the significant figures, such as 1234.5 to 1230.
+                let exponent = magnitude.log10().floor() as i32;
+                let scale = 10f64.powi(exponent + 1 - significant as i32);
+                format!("{:.0}", (value / scale).round() * scale)
+            }
+            (Some(significant), precision) => {
+                let mut decimals = decimals_for_significant(value, significant);
+                if let Some(precision) = precision { decimals = decimals.min(precision); }
+                format!("{:.*}", decimals, value)
+            }
+            (None, Some(precision)) => format!("{:.*}", precision, value),
+            (None, None) => format!("{:?}", value),
+        }
+    }
+}
+
+/// Calculate the number of decimal places that show `significant` figures of `value`.
+fn decimals_for_significant(value: f64, significant: usize) -> usize {
+    if value == 0.0 { return significant - 1; }
+    let exponent = value.abs().log10().floor() as i64;
+    (significant as i64 - 1 - exponent).max(0) as usize
+}
+
+impl fmt::Display for StatsDisplay {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rows: Vec<(&str, String, String)> = self.fields
+            .iter()
+            .map(|field| {
+                let (number, unit) = self.format_value(field.value, field.kind);
+                (field.label.as_str(), number, unit)
+            })
+            .collect();
+        if !self.align {
+            for (label, number, unit) in rows {
+                if unit.is_empty() {
+                    writeln!(f, "{}: {}", label, number)?;
+                } else {
+                    writeln!(f, "{}: {} {}", label, number, unit)?;
+                }
+            }
+            return Ok(());
+        }
+        let label_width = rows.iter().map(|(label, _, _)| label.chars().count()).max().unwrap_or(0);
+        let number_width = rows.iter().map(|(_, number, _)| number.chars().count()).max().unwrap_or(0);
+        for (label, number, unit) in rows {
+            let label_pad = label_width - label.chars().count();
+            write!(f, "{}:{:label_pad$} {:>number_width$}", label, "", number)?;
+            if !unit.is_empty() {
+                write!(f, " {}", unit)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn one(value: f64, kind: Kind) -> StatsDisplay {
+        StatsDisplay::new(vec![Field::new("x", value, kind)])
+    }
+
+    #[test]
+    fn test_default() {
+        assert_eq!(one(2.3333333333333335, Kind::Value).to_string(), "x: 2.3333333333333335\n");
+    }
+
+    #[test]
+    fn test_precision() {
+        assert_eq!(one(2.3333333333333335, Kind::Value).precision(2).to_string(), "x: 2.33\n");
+        assert_eq!(one(2.675, Kind::Value).precision(1).to_string(), "x: 2.7\n");
+    }
+
+    #[test]
+    fn test_significant() {
+        assert_eq!(one(2.3333333333333335, Kind::Value).significant(4).to_string(), "x: 2.333\n");
+        assert_eq!(one(1234.5678, Kind::Value).significant(3).to_string(), "x: 1230\n");
+        assert_eq!(one(1234.5678, Kind::Value).significant(4).to_string(), "x: 1235\n");
+        assert_eq!(one(0.012345, Kind::Value).significant(2).to_string(), "x: 0.012\n");
+    }
+
+    #[test]
+    fn test_significant_round_up() {
+        assert_eq!(one(9.9996, Kind::Value).significant(4).to_string(), "x: 10.00\n");
+        assert_eq!(one(999.96, Kind::Value).significant(3).to_string(), "x: 1000\n");
+        assert_eq!(one(999999.6, Kind::Value).significant(3).to_string(), "x: 1.00e6\n");
+    }
+
+    #[test]
+    fn test_significant_with_precision() {
+        assert_eq!(one(0.012345, Kind::Value).significant(4).precision(3).to_string(), "x: 0.012\n");
+    }
+
+    #[test]
+    fn test_scientific_auto() {
+        assert_eq!(one(12345678.0, Kind::Value).significant(3).to_string(), "x: 1.23e7\n");
+        assert_eq!(one(0.0000123, Kind::Value).precision(1).to_string(), "x: 1.2e-5\n");
+    }
+
+    #[test]
+    fn test_scientific_fixed() {
+        assert_eq!(one(12345678.0, Kind::Value).precision(0).notation(Notation::Fixed).to_string(), "x: 12345678\n");
+    }
+
+    #[test]
+    fn test_scientific_forced() {
+        assert_eq!(one(2.5, Kind::Value).precision(2).notation(Notation::Scientific).to_string(), "x: 2.50e0\n");
+    }
+
+    #[test]
+    fn test_unit_scale_up() {
+        assert_eq!(one(2500.0, Kind::Value).precision(2).unit("µs").to_string(), "x: 2.50 ms\n");
+        assert_eq!(one(2500.0, Kind::Value).precision(2).unit("ms").to_string(), "x: 2.50 s\n");
+    }
+
+    #[test]
+    fn test_unit_scale_down() {
+        assert_eq!(one(0.5, Kind::Value).precision(0).unit("ms").to_string(), "x: 500 µs\n");
+    }
+
+    #[test]
+    fn test_unit_scale_caps_at_seconds() {
+        assert_eq!(one(120.0, Kind::Value).precision(0).unit("s").to_string(), "x: 120 s\n");
+    }
+
+    #[test]
+    fn test_unit_scale_disabled() {
+        assert_eq!(one(2500.0, Kind::Value).precision(0).unit("ms").auto_scale(false).to_string(), "x: 2500 ms\n");
+    }
+
+    #[test]
+    fn test_unit_squared() {
+        assert_eq!(one(4_000_000.0, Kind::Squared).precision(0).unit("µs").to_string(), "x: 4 ms²\n");
+    }
+
+    #[test]
+    fn test_unit_other() {
+        assert_eq!(one(2500.0, Kind::Value).precision(0).unit("bytes").to_string(), "x: 2500 bytes\n");
+    }
+
+    #[test]
+    fn test_count_and_unitless() {
+        let display = StatsDisplay::new(vec![
+            Field::new("count", 3.0, Kind::Count),
+            Field::new("skew", 0.5, Kind::Unitless),
+        ]);
+        assert_eq!(display.precision(2).unit("ms").to_string(), "count: 3\nskew: 0.50\n");
+    }
+
+    #[test]
+    fn test_nan() {
+        assert_eq!(one(f64::NAN, Kind::Value).precision(2).unit("ms").to_string(), "x: NaN\n");
+    }
+
+    #[test]
+    fn test_align() {
+        let display = StatsDisplay::new(vec![
+            Field::new("min", 1.0, Kind::Value),
+            Field::new("average", 12.5, Kind::Value),
+        ]);
+        assert_eq!(display.precision(1).align(true).to_string(), "min:      1.0\naverage: 12.5\n");
+    }
+
+}
@@ -0,0 +1,1028 @@
+//! Probability distributions, for hypothesis tests and confidence intervals.
+//!
+//! Each distribution implements the `Distribution` trait, which provides
+//! the probability density or mass, the cumulative distribution function
+//! (cdf), the survival function (sf = 1 - cdf, without cancellation in the
+//! upper tail), the quantile function (the inverse of the cdf), the mean,
+//! the variance, and sampling by inverse transform.
+//!
+//! Continuous: `Normal`, `StudentT`, `ChiSquared`, `FisherF`,
+//! `Exponential`, `LogNormal`, `Gamma`, `Beta`, `Weibull`, `Pareto`, and
+//! `Uniform`. Discrete: `Binomial` and `Poisson`.
+//!
+//! The functions return NaN for a NaN argument, and the quantile function
+//! returns NaN for a probability outside 0.0 to 1.0.
+//!
+//! # Example
+//!
+//! ```rust
+//! use numeric_statistics::f64::distribution::*;
+//!
+//! // Two-sided 95% critical value of Student's t with 10 degrees of freedom.
+//! let t = StudentT::new(10.0);
+//! assert!((t.quantile(0.975) - 2.228138851986274).abs() < 1e-13);
+//!
+//! // Probability of at most 2 losses in 100 packets, at 1% loss.
+//! let losses = Binomial::new(100, 0.01);
+//! assert!((losses.cdf(2.0) - 0.9206267977478197).abs() < 1e-13);
+//! ```
+
+use std::f64::consts::{PI, SQRT_2};
+use super::special::*;
+
+/// A probability distribution of a real random variable.
+pub trait Distribution {
+    /// Calculate the probability density function at x, or for a discrete
+    /// distribution, the probability mass function, which is 0.0 at a
+    /// value that is not an integer.
+    fn pdf(&self, x: f64) -> f64;
+
+    /// Calculate the cumulative distribution function P(X <= x).
+    fn cdf(&self, x: f64) -> f64;
+
+    /// Calculate the survival function P(X > x) = 1 - cdf(x), without
+    /// cancellation in the upper tail.
+    fn sf(&self, x: f64) -> f64 {
+        1.0 - self.cdf(x)
+    }
+
+    /// Calculate the quantile function, which is the least x such that
+    /// cdf(x) >= p.
+    ///
+    /// Return NaN if p is not within 0.0 to 1.0.
+    fn quantile(&self, p: f64) -> f64;
+
+    /// Calculate the mean, or infinity or NaN if it does not exist.
+    fn mean(&self) -> f64;
+
+    /// Calculate the variance, or infinity or NaN if it does not exist.
+    fn variance(&self) -> f64;
+
+    /// Calculate the standard deviation, which is the square root of the variance.
+    fn standard_deviation(&self) -> f64 {
+        self.variance().sqrt()
+    }
+
+    /// Sample a value, by the inverse transform of a uniform value from
+    /// `random`, which returns values from 0.0 to 1.0, such as from a
+    /// random number generator.
+    fn sample(&self, random: &mut dyn FnMut() -> f64) -> f64 {
+        self.quantile(random())
+    }
+}
+
+/// Max iterations of a quantile search.
+const MAX_ITERATIONS: usize = 1_000;
+
+/// Solve cdf(x) = p for a continuous distribution with support from lower
+/// to upper, starting near the guess.
+///
+/// Bracket the root, then use Newton's method on the density, with a
+/// fallback to bisection. For p > 0.5, solve sf(x) = 1 - p, so the upper
+/// tail is accurate.
+fn solve_quantile<D: Distribution + ?Sized>(distribution: &D, p: f64, lower: f64, upper: f64, guess: f64) -> f64 {
+    if !(0.0..=1.0).contains(&p) { return f64::NAN; }
+    if p == 0.0 { return lower; }
+    if p == 1.0 { return upper; }
+    let q = 1.0 - p;
+    // An increasing function with its root at the quantile.
+    let f = |x: f64| if p > 0.5 { q - distribution.sf(x) } else { distribution.cdf(x) - p };
+
+    let (mut lo, mut hi) = (guess, guess);
+    let mut step = guess.abs().max(1.0);
+    if f(guess) < 0.0 {
+        while f(hi) < 0.0 {
+            lo = hi;
+            hi = (hi + step).min(upper);
+            step *= 2.0;
+            if hi == upper || !hi.is_finite() { break; }
+        }
+    } else {
+        while f(lo) > 0.0 {
+            hi = lo;
+            lo = (lo - step).max(lower);
+            step *= 2.0;
+            if lo == lower || !lo.is_finite() { break; }
+        }
+    }
+
+    let mut x = (lo + hi) / 2.0;
+    for _ in 0..MAX_ITERATIONS {
+        let fx = f(x);
+        if fx == 0.0 { return x; }
+        if fx < 0.0 { lo = x; } else { hi = x; }
+        let newton = x - fx / distribution.pdf(x);
+        let next = if newton > lo && newton < hi {
+            newton
+        } else if lo > 0.0 && hi > 4.0 * lo {
+            // Bisect in log space, for quantiles spanning many magnitudes.
+            (lo * hi).sqrt()
+        } else if lo == 0.0 && hi > 0.0 {
+            hi / 4.0
+        } else {
+            lo + (hi - lo) / 2.0
+        };
+        if (next - x).abs() <= 2.0 * f64::EPSILON * x.abs() || next == lo || next == hi {
+            return next;
+        }
+        x = next;
+    }
+    x
+}
+
+/// Find the least integer k from lower, to upper inclusive, such that
+/// cdf(k) >= p, by binary search. For p > 0.5, use sf(k) <= 1 - p, so
+/// the upper tail is accurate.
+fn discrete_quantile<D: Distribution + ?Sized>(distribution: &D, p: f64, lower: u64, upper: u64) -> f64 {
+    if !(0.0..=1.0).contains(&p) { return f64::NAN; }
+    let reached = |k: u64| if p > 0.5 { distribution.sf(k as f64) <= 1.0 - p } else { distribution.cdf(k as f64) >= p };
+    // Double the upper bound while it is not reached, for infinite support.
+    let mut hi = lower.max(1);
+    while hi < upper && !reached(hi) { hi = hi.saturating_mul(2).min(upper); }
+    let mut lo = lower;
+    while lo < hi {
+        let middle = lo + (hi - lo) / 2;
+        if reached(middle) { hi = middle; } else { lo = middle + 1; }
+    }
+    lo as f64
+}
+
+/// Normal distribution, also called Gaussian.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Normal {
+    mean: f64,
+    standard_deviation: f64,
+}
+
+impl Normal {
+    /// Create a normal distribution with the mean and the standard deviation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the mean is not finite, or if the standard deviation is not finite and positive.
+    ///
+    pub fn new(mean: f64, standard_deviation: f64) -> Normal {
+        assert!(mean.is_finite(), "mean must be finite");
+        assert!(standard_deviation.is_finite() && standard_deviation > 0.0, "standard deviation must be finite and positive");
+        Normal { mean, standard_deviation }
+    }
+
+    /// Create the standard normal distribution, with mean 0 and standard deviation 1.
+    pub fn standard() -> Normal {
+        Normal::new(0.0, 1.0)
+    }
+}
+
+impl Default for Normal {
+    fn default() -> Self {
+        Normal::standard()
+    }
+}
+
+impl Distribution for Normal {
+    fn pdf(&self, x: f64) -> f64 {
+        let z = (x - self.mean) / self.standard_deviation;
+        (-0.5 * z * z).exp() / (self.standard_deviation * (2.0 * PI).sqrt())
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        0.5 * erfc((self.mean - x) / (self.standard_deviation * SQRT_2))
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        0.5 * erfc((x - self.mean) / (self.standard_deviation * SQRT_2))
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) { return f64::NAN; }
+        self.mean - self.standard_deviation * SQRT_2 * erfc_inv(2.0 * p)
+    }
+
+    fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    fn variance(&self) -> f64 {
+        self.standard_deviation * self.standard_deviation
+    }
+}
+
+/// Student's t distribution, such as for the mean of a small sample.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StudentT {
+    degrees_of_freedom: f64,
+}
+
+impl StudentT {
+    /// Create Student's t distribution with the degrees of freedom, which may be fractional.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the degrees of freedom are not positive.
+    ///
+    pub fn new(degrees_of_freedom: f64) -> StudentT {
+        assert!(degrees_of_freedom > 0.0, "degrees of freedom must be positive");
+        StudentT { degrees_of_freedom }
+    }
+
+    /// P(T > t) for t >= 0.
+    fn upper_tail(&self, t: f64) -> f64 {
+        let v = self.degrees_of_freedom;
+        if t * t < v {
+            // Near the center, avoid I_x(v/2, 1/2) with x near 1.
+            0.5 - 0.5 * incomplete_beta(0.5, v / 2.0, t * t / (v + t * t))
+        } else {
+            0.5 * incomplete_beta(v / 2.0, 0.5, v / (v + t * t))
+        }
+    }
+}
+
+impl Distribution for StudentT {
+    fn pdf(&self, x: f64) -> f64 {
+        let v = self.degrees_of_freedom;
+        (ln_gamma((v + 1.0) / 2.0) - ln_gamma(v / 2.0) - 0.5 * (v * PI).ln()
+            - (v + 1.0) / 2.0 * (x * x / v).ln_1p()).exp()
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        if x.is_nan() { return f64::NAN; }
+        if x > 0.0 { 1.0 - self.upper_tail(x) } else { self.upper_tail(-x) }
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        if x.is_nan() { return f64::NAN; }
+        if x > 0.0 { self.upper_tail(x) } else { 1.0 - self.upper_tail(-x) }
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) { return f64::NAN; }
+        if p == 0.5 { return 0.0; }
+        // Solve in the lower tail, so the quantile is symmetric; 1 - p is exact for p > 0.5.
+        if p > 0.5 { return -self.quantile(1.0 - p); }
+        solve_quantile(self, p, f64::NEG_INFINITY, f64::INFINITY, Normal::standard().quantile(p))
+    }
+
+    fn mean(&self) -> f64 {
+        if self.degrees_of_freedom > 1.0 { 0.0 } else { f64::NAN }
+    }
+
+    fn variance(&self) -> f64 {
+        let v = self.degrees_of_freedom;
+        if v > 2.0 { v / (v - 2.0) } else if v > 1.0 { f64::INFINITY } else { f64::NAN }
+    }
+}
+
+/// Gamma distribution with a shape k and a scale θ.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Gamma {
+    shape: f64,
+    scale: f64,
+}
+
+impl Gamma {
+    /// Create a gamma distribution with the shape k and the scale θ.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the shape or the scale is not finite and positive.
+    ///
+    pub fn new(shape: f64, scale: f64) -> Gamma {
+        assert!(shape.is_finite() && shape > 0.0, "shape must be finite and positive");
+        assert!(scale.is_finite() && scale > 0.0, "scale must be finite and positive");
+        Gamma { shape, scale }
+    }
+}
+
+impl Distribution for Gamma {
+    fn pdf(&self, x: f64) -> f64 {
+        if x.is_nan() { return f64::NAN; }
+        if x < 0.0 { return 0.0; }
+        if x == 0.0 {
+            return if self.shape < 1.0 { f64::INFINITY } else if self.shape == 1.0 { 1.0 / self.scale } else { 0.0 };
+        }
+        let x = x / self.scale;
+        ((self.shape - 1.0) * x.ln() - x - ln_gamma(self.shape)).exp() / self.scale
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        incomplete_gamma_p(self.shape, x / self.scale)
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        incomplete_gamma_q(self.shape, x / self.scale)
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        solve_quantile(self, p, 0.0, f64::INFINITY, self.mean())
+    }
+
+    fn mean(&self) -> f64 {
+        self.shape * self.scale
+    }
+
+    fn variance(&self) -> f64 {
+        self.shape * self.scale * self.scale
+    }
+}
+
+/// Chi-squared distribution, which is the sum of squares of k standard
+/// normal values, such as for goodness-of-fit tests.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChiSquared {
+    gamma: Gamma,
+}
+
+impl ChiSquared {
+    /// Create a chi-squared distribution with the degrees of freedom k, which may be fractional.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the degrees of freedom are not finite and positive.
+    ///
+    pub fn new(degrees_of_freedom: f64) -> ChiSquared {
+        assert!(degrees_of_freedom.is_finite() && degrees_of_freedom > 0.0, "degrees of freedom must be finite and positive");
+        ChiSquared { gamma: Gamma::new(degrees_of_freedom / 2.0, 2.0) }
+    }
+}
+
+impl Distribution for ChiSquared {
+    fn pdf(&self, x: f64) -> f64 {
+        self.gamma.pdf(x)
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        self.gamma.cdf(x)
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        self.gamma.sf(x)
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        self.gamma.quantile(p)
+    }
+
+    fn mean(&self) -> f64 {
+        self.gamma.mean()
+    }
+
+    fn variance(&self) -> f64 {
+        self.gamma.variance()
+    }
+}
+
+/// F distribution, which is the ratio of two chi-squared values divided by
+/// their degrees of freedom, such as for regression and ANOVA.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FisherF {
+    df1: f64,
+    df2: f64,
+}
+
+impl FisherF {
+    /// Create an F distribution with the numerator degrees of freedom and
+    /// the denominator degrees of freedom.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either degrees of freedom is not finite and positive.
+    ///
+    pub fn new(df1: f64, df2: f64) -> FisherF {
+        assert!(df1.is_finite() && df1 > 0.0, "numerator degrees of freedom must be finite and positive");
+        assert!(df2.is_finite() && df2 > 0.0, "denominator degrees of freedom must be finite and positive");
+        FisherF { df1, df2 }
+    }
+}
+
+impl Distribution for FisherF {
+    fn pdf(&self, x: f64) -> f64 {
+        if x.is_nan() { return f64::NAN; }
+        if x < 0.0 { return 0.0; }
+        let (a, b) = (self.df1, self.df2);
+        if x == 0.0 {
+            return if a < 2.0 { f64::INFINITY } else if a == 2.0 { 1.0 } else { 0.0 };
+        }
+        (0.5 * (a * (a * x).ln() + b * b.ln() - (a + b) * (a * x + b).ln()) - x.ln() - ln_beta(a / 2.0, b / 2.0)).exp()
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        if x.is_nan() { return f64::NAN; }
+        if x <= 0.0 { return 0.0; }
+        let (a, b) = (self.df1, self.df2);
+        incomplete_beta(a / 2.0, b / 2.0, a * x / (a * x + b))
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        if x.is_nan() { return f64::NAN; }
+        if x <= 0.0 { return 1.0; }
+        let (a, b) = (self.df1, self.df2);
+        incomplete_beta(b / 2.0, a / 2.0, b / (b + a * x))
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        solve_quantile(self, p, 0.0, f64::INFINITY, 1.0)
+    }
+
+    fn mean(&self) -> f64 {
+        let b = self.df2;
+        if b > 2.0 { b / (b - 2.0) } else { f64::INFINITY }
+    }
+
+    fn variance(&self) -> f64 {
+        let (a, b) = (self.df1, self.df2);
+        if b > 4.0 {
+            2.0 * b * b * (a + b - 2.0) / (a * (b - 2.0) * (b - 2.0) * (b - 4.0))
+        } else if b > 2.0 {
+            f64::INFINITY
+        } else {
+            f64::NAN
+        }
+    }
+}
+
+/// Exponential distribution with a rate λ, such as for the time between
+/// independent events.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Exponential {
+    rate: f64,
+}
+
+impl Exponential {
+    /// Create an exponential distribution with the rate λ, which is 1 / mean.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rate is not finite and positive.
+    ///
+    pub fn new(rate: f64) -> Exponential {
+        assert!(rate.is_finite() && rate > 0.0, "rate must be finite and positive");
+        Exponential { rate }
+    }
+}
+
+impl Distribution for Exponential {
+    fn pdf(&self, x: f64) -> f64 {
+        if x < 0.0 { 0.0 } else { self.rate * (-self.rate * x).exp() }
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        if x < 0.0 { 0.0 } else { -(-self.rate * x).exp_m1() }
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        if x < 0.0 { 1.0 } else { (-self.rate * x).exp() }
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) { return f64::NAN; }
+        -(-p).ln_1p() / self.rate
+    }
+
+    fn mean(&self) -> f64 {
+        1.0 / self.rate
+    }
+
+    fn variance(&self) -> f64 {
+        1.0 / (self.rate * self.rate)
+    }
+}
+
+/// Log-normal distribution, where ln X is normal with a mean μ and a
+/// standard deviation σ, such as for latencies.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LogNormal {
+    normal: Normal,
+}
+
+impl LogNormal {
+    /// Create a log-normal distribution with the mean μ and the standard
+    /// deviation σ of ln X.
+    ///
+    /// # Panics
+    ///
+    /// Panics if μ is not finite, or if σ is not finite and positive.
+    ///
+    pub fn new(mu: f64, sigma: f64) -> LogNormal {
+        LogNormal { normal: Normal::new(mu, sigma) }
+    }
+}
+
+impl Distribution for LogNormal {
+    fn pdf(&self, x: f64) -> f64 {
+        if x <= 0.0 { 0.0 } else { self.normal.pdf(x.ln()) / x }
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        if x <= 0.0 { 0.0 } else { self.normal.cdf(x.ln()) }
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        if x <= 0.0 { 1.0 } else { self.normal.sf(x.ln()) }
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        self.normal.quantile(p).exp()
+    }
+
+    fn mean(&self) -> f64 {
+        (self.normal.mean + self.normal.variance() / 2.0).exp()
+    }
+
+    fn variance(&self) -> f64 {
+        let s2 = self.normal.variance();
+        s2.exp_m1() * (2.0 * self.normal.mean + s2).exp()
+    }
+}
+
+/// Beta distribution on 0.0 to 1.0, with shapes α and β, such as for proportions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Beta {
+    alpha: f64,
+    beta: f64,
+}
+
+impl Beta {
+    /// Create a beta distribution with the shapes α and β.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either shape is not finite and positive.
+    ///
+    pub fn new(alpha: f64, beta: f64) -> Beta {
+        assert!(alpha.is_finite() && alpha > 0.0, "alpha must be finite and positive");
+        assert!(beta.is_finite() && beta > 0.0, "beta must be finite and positive");
+        Beta { alpha, beta }
+    }
+}
+
+impl Distribution for Beta {
+    fn pdf(&self, x: f64) -> f64 {
+        if x.is_nan() { return f64::NAN; }
+        if !(0.0..=1.0).contains(&x) { return 0.0; }
+        let (a, b) = (self.alpha, self.beta);
+        let edge = |shape: f64| if shape < 1.0 { f64::INFINITY } else if shape == 1.0 { (-ln_beta(a, b)).exp() } else { 0.0 };
+        if x == 0.0 { return edge(a); }
+        if x == 1.0 { return edge(b); }
+        ((a - 1.0) * x.ln() + (b - 1.0) * (-x).ln_1p() - ln_beta(a, b)).exp()
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        incomplete_beta(self.alpha, self.beta, x)
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        incomplete_beta(self.beta, self.alpha, 1.0 - x)
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        inverse_incomplete_beta(self.alpha, self.beta, p)
+    }
+
+    fn mean(&self) -> f64 {
+        self.alpha / (self.alpha + self.beta)
+    }
+
+    fn variance(&self) -> f64 {
+        let (a, b) = (self.alpha, self.beta);
+        a * b / ((a + b) * (a + b) * (a + b + 1.0))
+    }
+}
+
+/// Weibull distribution with a shape k and a scale λ, such as for times to failure.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Weibull {
+    shape: f64,
+    scale: f64,
+}
+
+impl Weibull {
+    /// Create a Weibull distribution with the shape k and the scale λ.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the shape or the scale is not finite and positive.
+    ///
+    pub fn new(shape: f64, scale: f64) -> Weibull {
+        assert!(shape.is_finite() && shape > 0.0, "shape must be finite and positive");
+        assert!(scale.is_finite() && scale > 0.0, "scale must be finite and positive");
+        Weibull { shape, scale }
+    }
+}
+
+impl Distribution for Weibull {
+    fn pdf(&self, x: f64) -> f64 {
+        if x.is_nan() { return f64::NAN; }
+        if x < 0.0 { return 0.0; }
+        let (k, z) = (self.shape, x / self.scale);
+        if x == 0.0 {
+            return if k < 1.0 { f64::INFINITY } else if k == 1.0 { 1.0 / self.scale } else { 0.0 };
+        }
+        k / self.scale * z.powf(k - 1.0) * (-z.powf(k)).exp()
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        if x < 0.0 { 0.0 } else { -(-(x / self.scale).powf(self.shape)).exp_m1() }
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        if x < 0.0 { 1.0 } else { (-(x / self.scale).powf(self.shape)).exp() }
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) { return f64::NAN; }
+        self.scale * (-(-p).ln_1p()).powf(1.0 / self.shape)
+    }
+
+    fn mean(&self) -> f64 {
+        self.scale * ln_gamma(1.0 + 1.0 / self.shape).exp()
+    }
+
+    fn variance(&self) -> f64 {
+        let g1 = ln_gamma(1.0 + 1.0 / self.shape).exp();
+        let g2 = ln_gamma(1.0 + 2.0 / self.shape).exp();
+        self.scale * self.scale * (g2 - g1 * g1)
+    }
+}
+
+/// Pareto distribution with a scale, which is the minimum value, and a
+/// shape α, such as for heavy-tailed flow sizes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pareto {
+    scale: f64,
+    shape: f64,
+}
+
+impl Pareto {
+    /// Create a Pareto distribution with the scale, which is the minimum value, and the shape α.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the scale or the shape is not finite and positive.
+    ///
+    pub fn new(scale: f64, shape: f64) -> Pareto {
+        assert!(scale.is_finite() && scale > 0.0, "scale must be finite and positive");
+        assert!(shape.is_finite() && shape > 0.0, "shape must be finite and positive");
+        Pareto { scale, shape }
+    }
+}
+
+impl Distribution for Pareto {
+    fn pdf(&self, x: f64) -> f64 {
+        if x < self.scale { 0.0 } else { self.shape / x * (self.scale / x).powf(self.shape) }
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        if x < self.scale { 0.0 } else { -(self.shape * (self.scale / x).ln()).exp_m1() }
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        if x < self.scale { 1.0 } else { (self.scale / x).powf(self.shape) }
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) { return f64::NAN; }
+        self.scale * (-(-p).ln_1p() / self.shape).exp()
+    }
+
+    fn mean(&self) -> f64 {
+        let a = self.shape;
+        if a > 1.0 { a * self.scale / (a - 1.0) } else { f64::INFINITY }
+    }
+
+    fn variance(&self) -> f64 {
+        let a = self.shape;
+        if a > 2.0 { self.scale * self.scale * a / ((a - 1.0) * (a - 1.0) * (a - 2.0)) } else { f64::INFINITY }
+    }
+}
+
+/// Continuous uniform distribution from a lower bound to an upper bound.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Uniform {
+    lower: f64,
+    upper: f64,
+}
+
+impl Uniform {
+    /// Create a uniform distribution from the lower bound to the upper bound.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either bound is not finite, or if lower is not less than upper.
+    ///
+    pub fn new(lower: f64, upper: f64) -> Uniform {
+        assert!(lower.is_finite() && upper.is_finite(), "bounds must be finite");
+        assert!(lower < upper, "lower must be less than upper");
+        Uniform { lower, upper }
+    }
+}
+
+impl Distribution for Uniform {
+    fn pdf(&self, x: f64) -> f64 {
+        if x.is_nan() { return f64::NAN; }
+        if x < self.lower || x > self.upper { 0.0 } else { 1.0 / (self.upper - self.lower) }
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        ((x - self.lower) / (self.upper - self.lower)).clamp(0.0, 1.0)
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        ((self.upper - x) / (self.upper - self.lower)).clamp(0.0, 1.0)
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) { return f64::NAN; }
+        self.lower + p * (self.upper - self.lower)
+    }
+
+    fn mean(&self) -> f64 {
+        self.lower + (self.upper - self.lower) / 2.0
+    }
+
+    fn variance(&self) -> f64 {
+        (self.upper - self.lower).powi(2) / 12.0
+    }
+}
+
+/// Binomial distribution of the count of successes in n trials, each with
+/// a probability p, such as for packet loss.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Binomial {
+    n: u64,
+    p: f64,
+}
+
+impl Binomial {
+    /// Create a binomial distribution with the count of trials n and the
+    /// probability of success p of each trial.
+    ///
+    /// # Panics
+    ///
+    /// Panics if p is not within 0.0 to 1.0.
+    ///
+    pub fn new(n: u64, p: f64) -> Binomial {
+        assert!((0.0..=1.0).contains(&p), "p must be within 0.0 to 1.0");
+        Binomial { n, p }
+    }
+
+    /// Calculate the probability mass function P(X = k).
+    pub fn pmf(&self, k: u64) -> f64 {
+        let (n, p) = (self.n, self.p);
+        if k > n { return 0.0; }
+        if p == 0.0 || p == 1.0 {
+            let certain = if p == 0.0 { 0 } else { n };
+            return if k == certain { 1.0 } else { 0.0 };
+        }
+        let (n, k) = (n as f64, k as f64);
+        let ln_choose = ln_gamma(n + 1.0) - ln_gamma(k + 1.0) - ln_gamma(n - k + 1.0);
+        (ln_choose + k * p.ln() + (n - k) * (-p).ln_1p()).exp()
+    }
+}
+
+impl Distribution for Binomial {
+    fn pdf(&self, x: f64) -> f64 {
+        if x.is_nan() { return f64::NAN; }
+        if x < 0.0 || x != x.floor() { return 0.0; }
+        self.pmf(x as u64)
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        if x.is_nan() { return f64::NAN; }
+        if x < 0.0 { return 0.0; }
+        let k = x.floor();
+        let n = self.n as f64;
+        if k >= n { return 1.0; }
+        incomplete_beta(n - k, k + 1.0, 1.0 - self.p)
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        if x.is_nan() { return f64::NAN; }
+        if x < 0.0 { return 1.0; }
+        let k = x.floor();
+        let n = self.n as f64;
+        if k >= n { return 0.0; }
+        incomplete_beta(k + 1.0, n - k, self.p)
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        discrete_quantile(self, p, 0, self.n)
+    }
+
+    fn mean(&self) -> f64 {
+        self.n as f64 * self.p
+    }
+
+    fn variance(&self) -> f64 {
+        self.n as f64 * self.p * (1.0 - self.p)
+    }
+}
+
+/// Poisson distribution of the count of independent events in an
+/// interval, with a rate λ, such as for arrivals per second.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Poisson {
+    rate: f64,
+}
+
+impl Poisson {
+    /// Create a Poisson distribution with the rate λ, which is the mean count.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rate is not finite and positive.
+    ///
+    pub fn new(rate: f64) -> Poisson {
+        assert!(rate.is_finite() && rate > 0.0, "rate must be finite and positive");
+        Poisson { rate }
+    }
+
+    /// Calculate the probability mass function P(X = k).
+    pub fn pmf(&self, k: u64) -> f64 {
+        let k = k as f64;
+        (k * self.rate.ln() - self.rate - ln_gamma(k + 1.0)).exp()
+    }
+}
+
+impl Distribution for Poisson {
+    fn pdf(&self, x: f64) -> f64 {
+        if x.is_nan() { return f64::NAN; }
+        if x < 0.0 || x != x.floor() { return 0.0; }
+        self.pmf(x as u64)
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        if x.is_nan() { return f64::NAN; }
+        if x < 0.0 { return 0.0; }
+        incomplete_gamma_q(x.floor() + 1.0, self.rate)
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        if x.is_nan() { return f64::NAN; }
+        if x < 0.0 { return 1.0; }
+        incomplete_gamma_p(x.floor() + 1.0, self.rate)
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        if p == 1.0 { return f64::INFINITY; }
+        discrete_quantile(self, p, 0, u64::MAX)
+    }
+
+    fn mean(&self) -> f64 {
+        self.rate
+    }
+
+    fn variance(&self) -> f64 {
+        self.rate
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::f64::assert_eq_f64::assert_close;
+
+    /// Assert quantile inverts cdf, at probabilities across the support.
+    fn assert_round_trip<D: Distribution>(distribution: &D) {
+        for p in [1e-10, 0.001, 0.1, 0.5, 0.9, 0.999, 1.0 - 1e-10] {
+            let x = distribution.quantile(p);
+            let actual = if p > 0.5 { 1.0 - distribution.sf(x) } else { distribution.cdf(x) };
+            assert_close!(actual, p, relative = 1e-11);
+        }
+    }
+
+    #[test]
+    fn test_nan() {
+        assert!(Normal::standard().cdf(f64::NAN).is_nan());
+        assert!(StudentT::new(3.0).sf(f64::NAN).is_nan());
+        assert!(Gamma::new(2.0, 1.0).quantile(f64::NAN).is_nan());
+        assert!(Poisson::new(2.0).quantile(1.5).is_nan());
+        assert!(Binomial::new(5, 0.5).cdf(f64::NAN).is_nan());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invalid() {
+        Normal::new(0.0, 0.0);
+    }
+
+    #[test]
+    fn test_normal() {
+        let normal = Normal::new(10.0, 2.0);
+        assert_close!(normal.quantile(0.975), 10.0 + 2.0 * 1.959963984540054, relative = 1e-14);
+        assert_close!(Normal::standard().sf(10.0), 7.619853024160526e-24, relative = 1e-13);
+        assert_close!(normal.pdf(10.0), 1.0 / (2.0 * (2.0 * PI).sqrt()), relative = 1e-15);
+        assert_eq!(normal.variance(), 4.0);
+        assert_round_trip(&normal);
+    }
+
+    #[test]
+    fn test_student_t() {
+        let t = StudentT::new(10.0);
+        assert_close!(t.quantile(0.975), 2.228138851986275, relative = 1e-14);
+        assert_close!(t.quantile(0.025), -2.228138851986275, relative = 1e-14);
+        assert_close!(StudentT::new(3.0).quantile(0.999), 10.214531852407386, relative = 1e-13);
+        assert_close!(StudentT::new(5.0).cdf(2.0), 0.9490302605850708, relative = 1e-14);
+        assert_close!(StudentT::new(4.0).pdf(1.0), 0.2146625258399798, relative = 1e-14);
+        assert_eq!(t.quantile(0.5), 0.0);
+        assert!(StudentT::new(1.0).mean().is_nan());
+        assert_round_trip(&StudentT::new(2.5));
+    }
+
+    #[test]
+    fn test_chi_squared() {
+        assert_close!(ChiSquared::new(1.0).quantile(0.95), 3.841458820694126, relative = 1e-13);
+        assert_close!(ChiSquared::new(10.0).quantile(0.99), 23.20925115895436, relative = 1e-13);
+        assert_close!(ChiSquared::new(10.0).sf(100.0), 5.4497019829205295e-17, relative = 1e-12);
+        assert_round_trip(&ChiSquared::new(0.5));
+        assert_round_trip(&ChiSquared::new(50.0));
+        assert_close!(ChiSquared::new(1e8).quantile(0.5), 99999999.33333333, relative = 1e-15);
+        assert_round_trip(&ChiSquared::new(1e8));
+    }
+
+    #[test]
+    fn test_fisher_f() {
+        assert_close!(FisherF::new(5.0, 10.0).quantile(0.95), 3.325834530413012, relative = 1e-13);
+        assert_close!(FisherF::new(3.0, 7.0).cdf(2.0), 0.7973063575133491, relative = 1e-14);
+        assert_eq!(FisherF::new(3.0, 6.0).mean(), 1.5);
+        assert_round_trip(&FisherF::new(2.0, 3.0));
+    }
+
+    #[test]
+    fn test_gamma() {
+        let gamma = Gamma::new(2.5, 1.5);
+        assert_close!(gamma.cdf(3.0), 0.4505840486472198, relative = 1e-14);
+        assert_close!(gamma.quantile(0.9), 6.927267674835839, relative = 1e-13);
+        assert_close!(gamma.mean(), 3.75, relative = 1e-15);
+        assert_round_trip(&gamma);
+    }
+
+    #[test]
+    fn test_beta() {
+        let beta = Beta::new(2.5, 4.0);
+        assert_close!(beta.cdf(0.3), 0.3521975859067672, relative = 1e-14);
+        assert_close!(beta.pdf(0.3), 2.03426725306907, relative = 1e-13);
+        assert_close!(beta.quantile(0.5), 0.3721324311676063, relative = 1e-13);
+        assert_close!(Beta::new(1.0, 1.0).pdf(0.0), 1.0, relative = 1e-14);
+        assert_round_trip(&beta);
+    }
+
+    #[test]
+    fn test_closed_forms() {
+        let exponential = Exponential::new(2.0);
+        assert_close!(exponential.quantile(0.5), std::f64::consts::LN_2 / 2.0, relative = 1e-15);
+        assert_close!(exponential.sf(3.0), (-6.0f64).exp(), relative = 1e-15);
+        let log_normal = LogNormal::new(0.0, 1.0);
+        assert_close!(log_normal.quantile(0.5), 1.0, relative = 1e-15);
+        assert_close!(log_normal.mean(), 0.5f64.exp(), relative = 1e-15);
+        let weibull = Weibull::new(1.5, 2.0);
+        assert_close!(weibull.mean(), 1.8054905859018673, relative = 1e-14);
+        assert_close!(weibull.variance(), 1.502761139255728, relative = 1e-13);
+        assert_round_trip(&weibull);
+        let pareto = Pareto::new(1.0, 3.0);
+        assert_close!(pareto.quantile(0.875), 2.0, relative = 1e-15);
+        assert_close!(pareto.mean(), 1.5, relative = 1e-15);
+        assert_close!(pareto.variance(), 0.75, relative = 1e-15);
+        let uniform = Uniform::new(-1.0, 3.0);
+        assert_eq!(uniform.quantile(0.25), 0.0);
+        assert_eq!(uniform.cdf(5.0), 1.0);
+        assert_close!(uniform.variance(), 16.0 / 12.0, relative = 1e-15);
+    }
+
+    #[test]
+    fn test_binomial() {
+        let binomial = Binomial::new(10, 0.3);
+        assert_close!(binomial.cdf(3.0), 0.6496107184, relative = 1e-13);
+        assert_close!(binomial.cdf(3.5), 0.6496107184, relative = 1e-13);
+        assert_close!(binomial.pmf(3), 0.266827932, relative = 1e-13);
+        assert_eq!(binomial.pdf(2.5), 0.0);
+        assert_eq!(binomial.quantile(0.6496107184), 3.0);
+        assert_eq!(binomial.quantile(0.65), 4.0);
+        assert_eq!(binomial.quantile(0.0), 0.0);
+        assert_eq!(binomial.quantile(1.0), 10.0);
+        assert_eq!(Binomial::new(4, 1.0).pmf(4), 1.0);
+        assert_eq!(Binomial::new(4, 0.0).cdf(0.0), 1.0);
+    }
+
+    #[test]
+    fn test_poisson() {
+        let poisson = Poisson::new(3.5);
+        assert_close!(poisson.cdf(5.0), 0.8576135530957784, relative = 1e-14);
+        assert_close!(poisson.pmf(5), 0.1321685997861737, relative = 1e-14);
+        assert_close!(poisson.sf(20.0), 1.869183620178483e-10, relative = 1e-12);
+        assert_eq!(poisson.quantile(0.85), 5.0);
+        assert_eq!(poisson.quantile(0.86), 6.0);
+        assert_eq!(poisson.quantile(0.0), 0.0);
+        assert_close!(Poisson::new(1e8).cdf(1e8), 0.5000265961519927, relative = 1e-14);
+        assert_eq!(Poisson::new(1e9).quantile(0.5), 1e9);
+    }
+
+    #[test]
+    fn test_sample() {
+        let mut state = 0.0;
+        let mut random = || { state = (state + 0.618_033_988_749_895) % 1.0; state };
+        let exponential = Exponential::new(1.0);
+        let average = (0..10_000).map(|_| exponential.sample(&mut random)).sum::<f64>() / 10_000.0;
+        assert!((average - 1.0).abs() < 0.01);
+    }
+
+}
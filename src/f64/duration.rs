@@ -0,0 +1,327 @@
+//! Statistics over durations, such as for latency measurements, without
+//! converting each `Duration` to f64 seconds by hand.
+//!
+//! Functions that return a `Duration` return None if the values are empty.
+//! Sums and means accumulate nanoseconds in u128, which cannot overflow for
+//! any slice length that fits in memory; only a sum itself can exceed
+//! `Duration::MAX`, so `duration_sum` saturates and `duration_checked_sum`
+//! returns None.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::time::Duration;
+//! use numeric_statistics::f64::duration::*;
+//!
+//! let values = [10, 20, 40].map(Duration::from_millis);
+//! assert_eq!(duration_min(values), Some(Duration::from_millis(10)));
+//! assert_eq!(duration_mean(values), Some(Duration::from_nanos(23_333_333)));
+//! assert_eq!(duration_median(values), Some(Duration::from_millis(20)));
+//! ```
+
+use std::time::Duration;
+use super::all::All;
+
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+/// Convert nanoseconds to a duration, saturating at `Duration::MAX`.
+fn from_nanos(nanos: u128) -> Duration {
+    match u64::try_from(nanos / NANOS_PER_SEC) {
+        Ok(secs) => Duration::new(secs, (nanos % NANOS_PER_SEC) as u32),
+        Err(_) => Duration::MAX,
+    }
+}
+
+/// Sum of nanoseconds, which cannot overflow u128 for any slice in memory.
+fn nanos_sum(values: &[Duration]) -> u128 {
+    values.iter().map(Duration::as_nanos).sum()
+}
+
+/// Calculate min duration for values.
+///
+/// Return None if the values are empty.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+/// use numeric_statistics::f64::duration::*;
+/// let values = [3, 1, 2].map(Duration::from_secs);
+/// assert_eq!(duration_min(values), Some(Duration::from_secs(1)));
+/// ```
+///
+pub fn duration_min<T: AsRef<[Duration]>>(values: T) -> Option<Duration> {
+    values.as_ref().iter().min().copied()
+}
+
+/// Calculate max duration for values.
+///
+/// Return None if the values are empty.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+/// use numeric_statistics::f64::duration::*;
+/// let values = [3, 1, 2].map(Duration::from_secs);
+/// assert_eq!(duration_max(values), Some(Duration::from_secs(3)));
+/// ```
+///
+pub fn duration_max<T: AsRef<[Duration]>>(values: T) -> Option<Duration> {
+    values.as_ref().iter().max().copied()
+}
+
+/// Calculate sum of durations for values, saturating at `Duration::MAX`.
+///
+/// Return zero if the values are empty.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+/// use numeric_statistics::f64::duration::*;
+/// assert_eq!(duration_sum([Duration::MAX, Duration::MAX]), Duration::MAX);
+/// ```
+///
+pub fn duration_sum<T: AsRef<[Duration]>>(values: T) -> Duration {
+    from_nanos(nanos_sum(values.as_ref()))
+}
+
+/// Calculate sum of durations for values.
+///
+/// Return None if the sum exceeds `Duration::MAX`.
+/// Return zero if the values are empty.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+/// use numeric_statistics::f64::duration::*;
+/// assert_eq!(duration_checked_sum([Duration::MAX, Duration::MAX]), None);
+/// ```
+///
+pub fn duration_checked_sum<T: AsRef<[Duration]>>(values: T) -> Option<Duration> {
+    let nanos = nanos_sum(values.as_ref());
+    u64::try_from(nanos / NANOS_PER_SEC).ok().map(|_| from_nanos(nanos))
+}
+
+/// Calculate mean duration for values, truncated to whole nanoseconds.
+///
+/// The mean never overflows, even if the sum exceeds `Duration::MAX`.
+///
+/// Return None if the values are empty.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+/// use numeric_statistics::f64::duration::*;
+/// let values = [Duration::MAX, Duration::MAX];
+/// assert_eq!(duration_mean(values), Some(Duration::MAX));
+/// ```
+///
+pub fn duration_mean<T: AsRef<[Duration]>>(values: T) -> Option<Duration> {
+    let values = values.as_ref();
+    if values.is_empty() { return None; }
+    Some(from_nanos(nanos_sum(values) / values.len() as u128))
+}
+
+/// Calculate sample variance for values, in seconds squared.
+///
+/// Deltas from the mean are exact in nanoseconds before conversion to f64.
+///
+/// Return NaN if the values are empty.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+/// use numeric_statistics::f64::duration::*;
+/// let values = [1, 2, 4].map(Duration::from_secs);
+/// assert!((duration_variance(values) - 7.0 / 3.0).abs() < 1e-12);
+/// ```
+///
+pub fn duration_variance<T: AsRef<[Duration]>>(values: T) -> f64 {
+    let values = values.as_ref();
+    match values.len() {
+        0 => f64::NAN,
+        1 => 0.0,
+        n => {
+            let mean = (nanos_sum(values) / n as u128) as i128;
+            let square_sum: f64 = values
+                .iter()
+                .map(|x| ((x.as_nanos() as i128 - mean) as f64 / 1e9).powi(2))
+                .sum();
+            square_sum / (n - 1) as f64
+        }
+    }
+}
+
+/// Calculate sample standard deviation duration for values.
+///
+/// Return None if the values are empty.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+/// use numeric_statistics::f64::duration::*;
+/// let values = [1, 3].map(Duration::from_secs);
+/// assert_eq!(duration_standard_deviation(values), Some(Duration::from_nanos(1_414_213_562)));
+/// ```
+///
+pub fn duration_standard_deviation<T: AsRef<[Duration]>>(values: T) -> Option<Duration> {
+    let values = values.as_ref();
+    if values.is_empty() { return None; }
+    let deviation = duration_variance(values).sqrt();
+    Some(Duration::try_from_secs_f64(deviation).unwrap_or(Duration::MAX))
+}
+
+/// Calculate quantile duration for values, with the same linear
+/// interpolation as `quantile`, rounded to whole nanoseconds.
+///
+/// Return None if the values are empty, or if p is not within 0.0 to 1.0.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+/// use numeric_statistics::f64::duration::*;
+/// let values = [1, 2, 3, 4].map(Duration::from_millis);
+/// assert_eq!(duration_quantile(values, 0.5), Some(Duration::from_micros(2500)));
+/// ```
+///
+pub fn duration_quantile<T: AsRef<[Duration]>>(values: T, p: f64) -> Option<Duration> {
+    let mut sorted = values.as_ref().to_vec();
+    sorted.sort_unstable();
+    duration_quantile_sorted(&sorted, p)
+}
+
+/// Calculate quantile durations for values, for each p, sorting once.
+///
+/// Return None for each p if the values are empty, or for a p that is not within 0.0 to 1.0.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+/// use numeric_statistics::f64::duration::*;
+/// let values: Vec<Duration> = (1..=100).map(Duration::from_millis).collect();
+/// let ps = duration_quantiles(&values, &[0.0, 1.0]);
+/// assert_eq!(ps, vec![Some(Duration::from_millis(1)), Some(Duration::from_millis(100))]);
+/// ```
+///
+pub fn duration_quantiles<T: AsRef<[Duration]>, P: AsRef<[f64]>>(values: T, ps: P) -> Vec<Option<Duration>> {
+    let mut sorted = values.as_ref().to_vec();
+    sorted.sort_unstable();
+    ps.as_ref().iter().map(|p| duration_quantile_sorted(&sorted, *p)).collect()
+}
+
+/// Calculate quantile duration for values that are sorted in ascending order.
+///
+/// Return None if the values are empty, or if p is not within 0.0 to 1.0.
+pub fn duration_quantile_sorted(sorted: &[Duration], p: f64) -> Option<Duration> {
+    if sorted.is_empty() || !(0.0..=1.0).contains(&p) { return None; }
+    let h = (sorted.len() - 1) as f64 * p;
+    let lo = sorted[h.floor() as usize];
+    let hi = sorted[h.ceil() as usize];
+    let delta = (hi - lo).as_nanos() as f64 * (h - h.floor());
+    Some(from_nanos(lo.as_nanos() + delta.round() as u128))
+}
+
+/// Calculate median duration for values.
+///
+/// Return None if the values are empty.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+/// use numeric_statistics::f64::duration::*;
+/// let values = [5, 1, 3].map(Duration::from_millis);
+/// assert_eq!(duration_median(values), Some(Duration::from_millis(3)));
+/// ```
+///
+pub fn duration_median<T: AsRef<[Duration]>>(values: T) -> Option<Duration> {
+    duration_quantile(values, 0.5)
+}
+
+/// Calculate all statistics for values, in seconds.
+///
+/// Return NaN statistics if the values are empty.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+/// use numeric_statistics::f64::duration::*;
+/// let values = [1, 2, 4].map(Duration::from_millis);
+/// let all = duration_all(values);
+/// assert_eq!(all.min, 0.001);
+/// assert_eq!(all.max, 0.004);
+/// ```
+///
+pub fn duration_all<T: AsRef<[Duration]>>(values: T) -> All {
+    let values = values.as_ref();
+    let seconds = |x: Option<Duration>| x.map_or(f64::NAN, |x| x.as_secs_f64());
+    let variance = duration_variance(values);
+    All {
+        min: seconds(duration_min(values)),
+        max: seconds(duration_max(values)),
+        average: seconds(duration_mean(values)),
+        variance,
+        standard_deviation: variance.sqrt(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_empty() {
+        let x: &[Duration] = &[];
+        assert_eq!(duration_min(x), None);
+        assert_eq!(duration_mean(x), None);
+        assert_eq!(duration_quantile(x, 0.5), None);
+        assert_eq!(duration_sum(x), Duration::ZERO);
+        assert!(duration_variance(x).is_nan());
+        assert!(duration_all(x).average.is_nan());
+    }
+
+    #[test]
+    fn test_overflow() {
+        let x = [Duration::MAX, Duration::MAX, Duration::from_secs(1)];
+        assert_eq!(duration_sum(x), Duration::MAX);
+        assert_eq!(duration_checked_sum(x), None);
+        assert_eq!(duration_checked_sum([Duration::MAX]), Some(Duration::MAX));
+        let mean = duration_mean(x).unwrap();
+        assert!(mean > Duration::from_secs(u64::MAX / 2));
+        assert_eq!(duration_quantile([Duration::MAX; 2], 0.5), Some(Duration::MAX));
+    }
+
+    #[test]
+    fn test_values() {
+        let x = [1, 2, 4].map(Duration::from_millis);
+        assert_eq!(duration_sum(x), Duration::from_millis(7));
+        assert_eq!(duration_mean(x), Some(Duration::from_nanos(2_333_333)));
+        assert_eq!(duration_max(x), Some(Duration::from_millis(4)));
+        let deviation = duration_standard_deviation(x).unwrap().as_secs_f64();
+        assert!((deviation - 0.0015275252316519465).abs() < 1e-9);
+        let all = duration_all(x);
+        assert!((all.variance - 2.3333333333333335e-6).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_quantiles_interpolate() {
+        let x = [10, 20].map(Duration::from_nanos);
+        let ps = duration_quantiles(x, [0.0, 0.25, 1.0, 1.5]);
+        assert_eq!(ps, vec![
+            Some(Duration::from_nanos(10)),
+            Some(Duration::from_nanos(13)),
+            Some(Duration::from_nanos(20)),
+            None,
+        ]);
+    }
+
+}
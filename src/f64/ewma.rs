@@ -0,0 +1,330 @@
+//! Exponentially weighted moving average (EWMA), variance, and standard
+//! deviation, for smooth recency-weighted estimates with no window buffer.
+//!
+//! Each new sample has weight 1, and each older sample's weight decays by
+//! a factor of 1 - α per sample. The mean is bias-corrected, which means
+//! it divides by the sum of the weights, so early estimates are not pulled
+//! toward zero. This matches pandas `ewm(adjust=True)`.
+//!
+//! The variance is the weighted variance with the reliability-weights
+//! correction, which matches pandas `ewm(adjust=True).var()`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use numeric_statistics::f64::ewma::*;
+//!
+//! let mut ewma = Ewma::with_alpha(0.5);
+//! ewma.push(1.0);
+//! ewma.push(3.0);
+//! // Weights 0.5 and 1.0, so (0.5 * 1.0 + 3.0) / 1.5
+//! assert!((ewma.mean() - 7.0 / 3.0).abs() < 1e-12);
+//! ```
+
+/// Decayed weighted moments, shared by the sample-based and time-based EWMA.
+#[derive(Debug, Clone, Default)]
+struct Moments {
+    /// Count of samples.
+    count: u64,
+    /// Sum of weights.
+    weight_sum: f64,
+    /// Sum of squared weights.
+    weight_square_sum: f64,
+    average: f64,
+    /// Weighted sum of squared deltas from the average.
+    delta_square_sum: f64,
+}
+
+impl Moments {
+    /// Decay the existing weights by the factor, then add a sample with weight 1.
+    fn push(&mut self, decay: f64, x: f64) {
+        self.count += 1;
+        self.weight_sum = decay * self.weight_sum + 1.0;
+        self.weight_square_sum = decay * decay * self.weight_square_sum + 1.0;
+        let delta = x - self.average;
+        self.average += delta / self.weight_sum;
+        self.delta_square_sum = decay * self.delta_square_sum + delta * (x - self.average);
+    }
+
+    fn mean(&self) -> f64 {
+        if self.count == 0 { f64::NAN } else { self.average }
+    }
+
+    fn variance(&self) -> f64 {
+        match self.count {
+            0 => f64::NAN,
+            1 => 0.0,
+            _ => {
+                let denominator = self.weight_sum - self.weight_square_sum / self.weight_sum;
+                (self.delta_square_sum / denominator).max(0.0)
+            }
+        }
+    }
+
+    fn variance_biased(&self) -> f64 {
+        if self.count == 0 { f64::NAN } else { (self.delta_square_sum / self.weight_sum).max(0.0) }
+    }
+}
+
+/// Exponentially weighted moving statistics, with decay per sample.
+#[derive(Debug, Clone)]
+pub struct Ewma {
+    alpha: f64,
+    moments: Moments,
+}
+
+impl Ewma {
+    /// Create with the smoothing factor α, within 0.0 exclusive to 1.0 inclusive.
+    ///
+    /// A larger α gives more weight to recent samples.
+    ///
+    /// # Panics
+    ///
+    /// Panic if α is not within 0.0 exclusive to 1.0 inclusive.
+    ///
+    pub fn with_alpha(alpha: f64) -> Ewma {
+        assert!(alpha > 0.0 && alpha <= 1.0, "alpha must be within 0.0 exclusive to 1.0 inclusive, got {}", alpha);
+        Ewma { alpha, moments: Moments::default() }
+    }
+
+    /// Create with the span, which is α = 2 / (span + 1), for span >= 1.
+    ///
+    /// # Panics
+    ///
+    /// Panic if the span is less than 1.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use numeric_statistics::f64::ewma::*;
+    /// assert_eq!(Ewma::with_span(3.0).alpha(), 0.5);
+    /// ```
+    ///
+    pub fn with_span(span: f64) -> Ewma {
+        assert!(span >= 1.0, "span must be at least 1, got {}", span);
+        Ewma::with_alpha(2.0 / (span + 1.0))
+    }
+
+    /// Create with the half-life in samples, which is α = 1 - 0.5^(1 / half-life).
+    ///
+    /// # Panics
+    ///
+    /// Panic if the half-life is not positive.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use numeric_statistics::f64::ewma::*;
+    /// assert_eq!(Ewma::with_half_life(1.0).alpha(), 0.5);
+    /// ```
+    ///
+    pub fn with_half_life(half_life: f64) -> Ewma {
+        assert!(half_life > 0.0, "half-life must be positive, got {}", half_life);
+        Ewma::with_alpha(1.0 - 0.5f64.powf(1.0 / half_life))
+    }
+
+    /// Create with the center of mass, which is α = 1 / (1 + center of mass).
+    ///
+    /// # Panics
+    ///
+    /// Panic if the center of mass is negative.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use numeric_statistics::f64::ewma::*;
+    /// assert_eq!(Ewma::with_center_of_mass(1.0).alpha(), 0.5);
+    /// ```
+    ///
+    pub fn with_center_of_mass(center_of_mass: f64) -> Ewma {
+        assert!(center_of_mass >= 0.0, "center of mass must be non-negative, got {}", center_of_mass);
+        Ewma::with_alpha(1.0 / (1.0 + center_of_mass))
+    }
+
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    /// Count of samples.
+    pub fn count(&self) -> u64 {
+        self.moments.count
+    }
+
+    /// Push a sample.
+    ///
+    /// Filter NaN values in the stream.
+    pub fn push(&mut self, x: f64) {
+        if x.is_nan() { return; }
+        self.moments.push(1.0 - self.alpha, x);
+    }
+
+    /// Bias-corrected weighted mean, or NaN if there are no samples.
+    pub fn mean(&self) -> f64 {
+        self.moments.mean()
+    }
+
+    /// Weighted variance with the reliability-weights correction,
+    /// or NaN if there are no samples.
+    pub fn variance(&self) -> f64 {
+        self.moments.variance()
+    }
+
+    /// Weighted variance without correction, or NaN if there are no samples.
+    pub fn variance_biased(&self) -> f64 {
+        self.moments.variance_biased()
+    }
+
+    /// Square root of `variance`, such as for jitter.
+    pub fn standard_deviation(&self) -> f64 {
+        self.variance().sqrt()
+    }
+}
+
+/// Exponentially weighted moving statistics, with decay by the elapsed
+/// time between samples, for samples at irregular intervals.
+///
+/// The weight of a sample halves every half-life of elapsed time.
+/// Times can be in any unit, such as seconds, if the half-life has the
+/// same unit. A time earlier than the previous time counts as no elapsed time.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::ewma::*;
+///
+/// let mut ewma = TimeEwma::with_half_life(10.0);
+/// ewma.push(0.0, 1.0);
+/// ewma.push(10.0, 3.0);
+/// // Weights 0.5 and 1.0, so (0.5 * 1.0 + 3.0) / 1.5
+/// assert!((ewma.mean() - 7.0 / 3.0).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TimeEwma {
+    half_life: f64,
+    time: f64,
+    moments: Moments,
+}
+
+impl TimeEwma {
+    /// Create with the half-life in units of time.
+    ///
+    /// # Panics
+    ///
+    /// Panic if the half-life is not positive.
+    ///
+    pub fn with_half_life(half_life: f64) -> TimeEwma {
+        assert!(half_life > 0.0, "half-life must be positive, got {}", half_life);
+        TimeEwma { half_life, time: f64::NAN, moments: Moments::default() }
+    }
+
+    pub fn half_life(&self) -> f64 {
+        self.half_life
+    }
+
+    /// Count of samples.
+    pub fn count(&self) -> u64 {
+        self.moments.count
+    }
+
+    /// Push a sample at a time.
+    ///
+    /// Filter NaN values and NaN times in the stream.
+    pub fn push(&mut self, time: f64, x: f64) {
+        if x.is_nan() || time.is_nan() { return; }
+        let elapsed = if self.time.is_nan() { 0.0 } else { (time - self.time).max(0.0) };
+        self.time = self.time.max(time);
+        self.moments.push(0.5f64.powf(elapsed / self.half_life), x);
+    }
+
+    /// Bias-corrected weighted mean, or NaN if there are no samples.
+    pub fn mean(&self) -> f64 {
+        self.moments.mean()
+    }
+
+    /// Weighted variance with the reliability-weights correction,
+    /// or NaN if there are no samples.
+    pub fn variance(&self) -> f64 {
+        self.moments.variance()
+    }
+
+    /// Weighted variance without correction, or NaN if there are no samples.
+    pub fn variance_biased(&self) -> f64 {
+        self.moments.variance_biased()
+    }
+
+    /// Square root of `variance`, such as for jitter.
+    pub fn standard_deviation(&self) -> f64 {
+        self.variance().sqrt()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Weighted mean and unbiased weighted variance, calculated directly.
+    fn weighted(values: &[f64], weights: &[f64]) -> (f64, f64) {
+        let v1: f64 = weights.iter().sum();
+        let v2: f64 = weights.iter().map(|w| w * w).sum();
+        let mean = values.iter().zip(weights).map(|(x, w)| x * w).sum::<f64>() / v1;
+        let s: f64 = values.iter().zip(weights).map(|(x, w)| w * (x - mean) * (x - mean)).sum();
+        (mean, s / (v1 - v2 / v1))
+    }
+
+    #[test]
+    fn test_empty() {
+        let ewma = Ewma::with_alpha(0.5);
+        assert!(ewma.mean().is_nan());
+        assert!(ewma.variance().is_nan());
+    }
+
+    #[test]
+    fn test_nan() {
+        let mut ewma = Ewma::with_alpha(0.5);
+        ewma.push(f64::NAN);
+        ewma.push(2.0);
+        assert_eq!(ewma.count(), 1);
+        assert_eq!(ewma.mean(), 2.0);
+        assert_eq!(ewma.variance(), 0.0);
+    }
+
+    #[test]
+    fn test_matches_direct_weights() {
+        let x = [1.0, 4.0, 2.0, 8.0, 5.0, 7.0];
+        let alpha = 0.3;
+        let mut ewma = Ewma::with_alpha(alpha);
+        x.iter().for_each(|x| ewma.push(*x));
+        let weights: Vec<f64> = (0..x.len()).map(|i| (1.0 - alpha).powi((x.len() - 1 - i) as i32)).collect();
+        let (mean, variance) = weighted(&x, &weights);
+        assert!((ewma.mean() - mean).abs() < 1e-12);
+        assert!((ewma.variance() - variance).abs() < 1e-12);
+        assert!((ewma.standard_deviation() - variance.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_alpha_one_is_last_value() {
+        let mut ewma = Ewma::with_alpha(1.0);
+        [1.0, 5.0, 3.0].iter().for_each(|x| ewma.push(*x));
+        assert_eq!(ewma.mean(), 3.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_alpha_invalid() {
+        Ewma::with_alpha(0.0);
+    }
+
+    #[test]
+    fn test_time_matches_direct_weights() {
+        let samples = [(0.0, 1.0), (0.5, 4.0), (3.0, 2.0), (3.1, 8.0), (10.0, 5.0)];
+        let half_life = 2.0;
+        let mut ewma = TimeEwma::with_half_life(half_life);
+        samples.iter().for_each(|(t, x)| ewma.push(*t, *x));
+        let x: Vec<f64> = samples.iter().map(|(_, x)| *x).collect();
+        let weights: Vec<f64> = samples.iter().map(|(t, _)| 0.5f64.powf((10.0 - t) / half_life)).collect();
+        let (mean, variance) = weighted(&x, &weights);
+        assert!((ewma.mean() - mean).abs() < 1e-12);
+        assert!((ewma.variance() - variance).abs() < 1e-12);
+    }
+
+}
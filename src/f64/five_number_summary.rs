@@ -0,0 +1,145 @@
+use super::quantile::*;
+
+/// Five-number summary: min, first quartile, median, third quartile, max.
+///
+/// Quartiles use the same linear interpolation as `quantile`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FiveNumberSummary {
+    pub min: f64,
+    pub q1: f64,
+    pub median: f64,
+    pub q3: f64,
+    pub max: f64,
+}
+
+impl FiveNumberSummary {
+    /// Create a five-number summary from values that are already sorted
+    /// ascending and have no NaN values.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use numeric_statistics::f64::five_number_summary::*;
+    /// let sorted = &[1.0, 2.0, 3.0, 4.0, 5.0];
+    /// let summary = FiveNumberSummary::from_sorted(sorted);
+    /// assert_eq!(summary.q1, 2.0);
+    /// ```
+    ///
+    pub fn from_sorted<T: AsRef<[f64]>>(sorted: T) -> FiveNumberSummary {
+        let sorted = sorted.as_ref();
+        FiveNumberSummary {
+            min: quantile_sorted(sorted, 0.0),
+            q1: quantile_sorted(sorted, 0.25),
+            median: quantile_sorted(sorted, 0.5),
+            q3: quantile_sorted(sorted, 0.75),
+            max: quantile_sorted(sorted, 1.0),
+        }
+    }
+
+    /// Interquartile range, which is Q3 - Q1.
+    pub fn interquartile_range(&self) -> f64 {
+        self.q3 - self.q1
+    }
+
+    /// Trimean, which is (Q1 + 2 * median + Q3) / 4.
+    pub fn trimean(&self) -> f64 {
+        (self.q1 + 2.0 * self.median + self.q3) / 4.0
+    }
+
+    /// Midhinge, which is (Q1 + Q3) / 2.
+    pub fn midhinge(&self) -> f64 {
+        (self.q1 + self.q3) / 2.0
+    }
+}
+
+/// Calculate the five-number summary for values: min, first quartile,
+/// median, third quartile, and max.
+///
+/// Return NaN fields if the values are empty.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::five_number_summary::*;
+/// let values = &[5.0, 1.0, 4.0, 2.0, 3.0];
+/// let summary = five_number_summary(values);
+/// assert_eq!(summary, FiveNumberSummary { min: 1.0, q1: 2.0, median: 3.0, q3: 4.0, max: 5.0 });
+/// ```
+///
+pub fn five_number_summary<T: AsRef<[f64]>>(values: T) -> FiveNumberSummary {
+    FiveNumberSummary::from_sorted(sorted(values))
+}
+
+/// Calculate statistical trimean for values, which is
+/// (Q1 + 2 * median + Q3) / 4.
+///
+/// Return NaN if the values are empty.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::five_number_summary::*;
+/// let values = &[1.0, 2.0, 3.0, 4.0, 10.0];
+/// let trimean = trimean(values);
+/// assert_eq!(trimean, 3.0);
+/// ```
+///
+pub fn trimean<T: AsRef<[f64]>>(values: T) -> f64 {
+    five_number_summary(values).trimean()
+}
+
+/// Calculate statistical midhinge for values, which is (Q1 + Q3) / 2.
+///
+/// Return NaN if the values are empty.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::five_number_summary::*;
+/// let values = &[1.0, 2.0, 3.0, 6.0, 10.0];
+/// let midhinge = midhinge(values);
+/// assert_eq!(midhinge, 4.0);
+/// ```
+///
+pub fn midhinge<T: AsRef<[f64]>>(values: T) -> f64 {
+    five_number_summary(values).midhinge()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_empty() {
+        let x: &[f64] = &[];
+        let summary = five_number_summary(x);
+        assert!(summary.min.is_nan());
+        assert!(summary.median.is_nan());
+        assert!(summary.max.is_nan());
+        assert!(trimean(x).is_nan());
+    }
+
+    #[test]
+    fn test_value() {
+        let x: &[f64] = &[1.0];
+        let summary = five_number_summary(x);
+        assert_eq!(summary, FiveNumberSummary { min: 1.0, q1: 1.0, median: 1.0, q3: 1.0, max: 1.0 });
+    }
+
+    #[test]
+    fn test_values_and_nans() {
+        let x = &[8.0, f64::NAN, 1.0, 4.0, f64::NAN, 2.0];
+        let summary = five_number_summary(x);
+        assert_eq!(summary, FiveNumberSummary { min: 1.0, q1: 1.75, median: 3.0, q3: 5.0, max: 8.0 });
+        assert_eq!(summary.interquartile_range(), 3.25);
+        assert_eq!(summary.trimean(), 3.1875);
+        assert_eq!(summary.midhinge(), 3.375);
+    }
+
+}
@@ -0,0 +1,766 @@
+//! Goodness-of-fit tests, of whether a sample fits a distribution, and
+//! two-sample and k-sample tests, of whether samples have the same
+//! distribution: Kolmogorov–Smirnov, Anderson–Darling, and Cramér–von Mises.
+//!
+//! The one-sample tests take any `Distribution`, with parameters that are
+//! known in advance. If the parameters are estimated from the same
+//! sample, the p-values are too high.
+//!
+//! # Example
+//!
+//! ```rust
+//! use numeric_statistics::f64::{distribution::*, goodness_of_fit::*, hypothesis::*};
+//!
+//! // Does this week's latency match last week's?
+//! let last_week = [10.2, 11.5, 9.8, 12.1, 10.9, 11.2, 10.4, 11.8, 10.1, 11.0];
+//! let this_week = [14.2, 13.5, 15.8, 12.9, 14.9, 13.2, 14.4, 15.1, 13.8, 14.0];
+//! let result = kolmogorov_smirnov_two_sample(this_week, last_week, Alternative::TwoSided);
+//! assert!(result.p_value < 0.001);
+//!
+//! // Do inter-arrival times fit an exponential distribution with mean 2?
+//! let gaps = [0.4, 3.1, 1.2, 0.8, 2.5, 5.3, 0.2, 1.7, 2.2, 0.9, 4.1, 1.4];
+//! let result = anderson_darling(gaps, &Exponential::new(0.5));
+//! assert!(result.p_value > 0.05);
+//! ```
+
+use super::{
+    distribution::Distribution,
+    hypothesis::*,
+    quantile::sorted,
+    rank::ranks,
+    special::ln_gamma,
+};
+use std::f64::consts::PI;
+
+/// Max sample size for exact one-sample Kolmogorov–Smirnov p-values.
+const KOLMOGOROV_SMIRNOV_EXACT_MAX: usize = 100;
+
+/// Max product of sample sizes for exact two-sample Kolmogorov–Smirnov p-values.
+const SMIRNOV_EXACT_MAX: usize = 10_000;
+
+/// Return true if sorted values have any ties.
+fn has_ties(sorted: &[f64]) -> bool {
+    sorted.windows(2).any(|w| w[0] == w[1])
+}
+
+/// Select D+, D-, or the greater of the two, by the alternative.
+fn select_statistic(d_plus: f64, d_minus: f64, alternative: Alternative) -> f64 {
+    match alternative {
+        Alternative::TwoSided => d_plus.max(d_minus),
+        Alternative::Greater => d_plus,
+        Alternative::Less => d_minus,
+    }
+}
+
+/// Asymptotic P(K > x) of the Kolmogorov distribution, which is the
+/// limit of √n D_n.
+fn kolmogorov_sf(x: f64) -> f64 {
+    if x <= 0.0 { return 1.0; }
+    if x < 1.0 {
+        // P(K <= x) = √(2π) / x Σ exp(-(2k - 1)² π² / (8x²)), which converges fast for small x.
+        let sum: f64 = (1..=20)
+            .map(|k| (-((2 * k - 1) as f64).powi(2) * PI * PI / (8.0 * x * x)).exp())
+            .sum();
+        1.0 - (2.0 * PI).sqrt() / x * sum
+    } else {
+        // P(K > x) = 2 Σ (-1)^(k-1) exp(-2k²x²)
+        let sum: f64 = (1..=100)
+            .map(|k| (if k % 2 == 1 { 1.0 } else { -1.0 }) * (-2.0 * (k * k) as f64 * x * x).exp())
+            .sum();
+        (2.0 * sum).clamp(0.0, 1.0)
+    }
+}
+
+/// Exact P(D_n < d) of the two-sided one-sample statistic, by the method of
+/// Marsaglia, Tsang, and Wang (2003), as a power of an m × m matrix.
+fn kolmogorov_exact_cdf(n: usize, d: f64) -> f64 {
+    let nf = n as f64;
+    let k = (nf * d) as usize + 1;
+    let m = 2 * k - 1;
+    let h = k as f64 - nf * d;
+    let mut matrix = vec![0.0; m * m];
+    for i in 0..m {
+        for j in 0..m {
+            if i + 1 >= j { matrix[i * m + j] = 1.0; }
+        }
+    }
+    for i in 0..m {
+        matrix[i * m] -= h.powi(i as i32 + 1);
+        matrix[(m - 1) * m + i] -= h.powi((m - i) as i32);
+    }
+    if 2.0 * h - 1.0 > 0.0 { matrix[(m - 1) * m] += (2.0 * h - 1.0).powi(m as i32); }
+    for i in 0..m {
+        for j in 0..=(i + 1).min(m - 1) {
+            for g in 1..=(i + 1 - j) { matrix[i * m + j] /= g as f64; }
+        }
+    }
+    let (power, mut exponent) = matrix_power(&matrix, m, n);
+    // Scale by n! / n^n, keeping the value within range with a power of 10.
+    let mut s = power[(k - 1) * m + k - 1];
+    for i in 1..=n {
+        s *= i as f64 / nf;
+        if s < 1e-140 {
+            s *= 1e140;
+            exponent -= 140;
+        }
+    }
+    s * 10f64.powi(exponent)
+}
+
+/// Product of m × m matrices.
+fn matrix_multiply(a: &[f64], b: &[f64], m: usize) -> Vec<f64> {
+    let mut c = vec![0.0; m * m];
+    for i in 0..m {
+        for l in 0..m {
+            let a_il = a[i * m + l];
+            if a_il == 0.0 { continue; }
+            for j in 0..m { c[i * m + j] += a_il * b[l * m + j]; }
+        }
+    }
+    c
+}
+
+/// Power of an m × m matrix, as a matrix and a power of 10, so entries
+/// stay within range.
+fn matrix_power(matrix: &[f64], m: usize, n: usize) -> (Vec<f64>, i32) {
+    if n == 1 { return (matrix.to_vec(), 0); }
+    let (half, half_exponent) = matrix_power(matrix, m, n / 2);
+    let mut power = matrix_multiply(&half, &half, m);
+    let mut exponent = 2 * half_exponent;
+    if n % 2 == 1 { power = matrix_multiply(matrix, &power, m); }
+    if power[(m / 2) * m + m / 2] > 1e140 {
+        power.iter_mut().for_each(|x| *x *= 1e-140);
+        exponent += 140;
+    }
+    (power, exponent)
+}
+
+/// Exact P(D+_n >= d) of the one-sided one-sample statistic, by the
+/// formula of Birnbaum and Tingey (1951).
+fn kolmogorov_one_sided_exact_sf(n: usize, d: f64) -> f64 {
+    if d <= 0.0 { return 1.0; }
+    if d >= 1.0 { return 0.0; }
+    let nf = n as f64;
+    let ln_n_factorial = ln_gamma(nf + 1.0);
+    let sum: f64 = (0..=(nf * (1.0 - d)).floor() as usize)
+        .map(|j| {
+            let jf = j as f64;
+            let a = 1.0 - d - jf / nf;
+            if a <= 0.0 { return 0.0; }
+            let ln_choose = ln_n_factorial - ln_gamma(jf + 1.0) - ln_gamma(nf - jf + 1.0);
+            (ln_choose + (nf - jf) * a.ln() + (jf - 1.0) * (d + jf / nf).ln()).exp()
+        })
+        .sum();
+    (d * sum).clamp(0.0, 1.0)
+}
+
+/// Exact P(D_n >= d) of the two-sided one-sample statistic.
+///
+/// For d >= 1/2, D+ and D- cannot both reach d, so this is exactly twice
+/// the one-sided tail, which keeps its precision far into the upper tail.
+/// Otherwise it is 1 - P(D_n < d), held within the bounds of one and two
+/// times the one-sided tail, as the subtraction cancels when small.
+fn kolmogorov_exact_sf(n: usize, d: f64) -> f64 {
+    let one_sided = kolmogorov_one_sided_exact_sf(n, d);
+    if d >= 0.5 { return (2.0 * one_sided).min(1.0); }
+    (1.0 - kolmogorov_exact_cdf(n, d)).clamp(one_sided, (2.0 * one_sided).min(1.0))
+}
+
+/// Calculate the one-sample Kolmogorov–Smirnov test of whether values fit
+/// the distribution.
+///
+/// The statistic is D, the greatest distance between the empirical cdf
+/// and the distribution's cdf. For `Alternative::Greater` it is D+, the
+/// greatest distance with the empirical cdf above, and for
+/// `Alternative::Less` it is D-, with the empirical cdf below.
+///
+/// The p-value is exact if there are fewer than 100 values and no ties;
+/// otherwise it uses the asymptotic Kolmogorov distribution, or for one
+/// side, exp(-2nD²).
+///
+/// The other fields are NaN.
+///
+/// Return NaN statistics if there are no values.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::{distribution::*, goodness_of_fit::*, hypothesis::*};
+/// let values = [0.61, 0.29, 0.06, 0.59, -1.73, -0.74, 0.51, -0.56, 0.39, 1.64, 0.05, -0.06, 0.64, -0.82, 0.37];
+/// let result = kolmogorov_smirnov(values, &Normal::standard(), Alternative::TwoSided);
+/// assert!(result.statistic < 0.25);
+/// assert!(result.p_value > 0.1);
+/// ```
+///
+pub fn kolmogorov_smirnov<T: AsRef<[f64]>, D: Distribution + ?Sized>(values: T, distribution: &D, alternative: Alternative) -> TestResult {
+    let sorted = sorted(values.as_ref());
+    let n = sorted.len();
+    if n == 0 { return TestResult::nan(); }
+    let nf = n as f64;
+    let (mut d_plus, mut d_minus) = (0.0f64, 0.0f64);
+    for (i, x) in sorted.iter().enumerate() {
+        let f = distribution.cdf(*x);
+        d_plus = d_plus.max((i + 1) as f64 / nf - f);
+        d_minus = d_minus.max(f - i as f64 / nf);
+    }
+    let d = select_statistic(d_plus, d_minus, alternative);
+    let exact = n < KOLMOGOROV_SMIRNOV_EXACT_MAX && !has_ties(&sorted);
+    let p_value = match (alternative, exact) {
+        (Alternative::TwoSided, true) => kolmogorov_exact_sf(n, d),
+        (Alternative::TwoSided, false) => kolmogorov_sf(nf.sqrt() * d),
+        (_, true) => kolmogorov_one_sided_exact_sf(n, d),
+        (_, false) => (-2.0 * nf * d * d).exp(),
+    };
+    TestResult {
+        statistic: d,
+        p_value: p_value.clamp(0.0, 1.0),
+        ..TestResult::nan()
+    }
+}
+
+/// Exact P(D_m,n >= d) of the two-sample statistic, two-sided or for D+
+/// only, by counting lattice paths that reach the bound, as by Hodges
+/// (1957). Counting these paths directly, rather than subtracting those
+/// that stay within the bound from 1, keeps precision in the upper tail.
+fn smirnov_exact_sf(m: usize, n: usize, d: f64, two_sided: bool) -> f64 {
+    let (mi, ni) = (m as i64, n as i64);
+    // D m n is an integer, so compare integers.
+    let threshold = (d * (m * n) as f64 - 1e-7).ceil() as i64;
+    let blocked = |i: i64, j: i64| {
+        let difference = i * ni - j * mi;
+        if two_sided { difference.abs() >= threshold } else { difference >= threshold }
+    };
+    // u[j] and v[j] are the counts of paths to (i, j) that have not and
+    // have reached the bound, divided by C(i + n, n).
+    let (mut u, mut v) = (vec![0.0; n + 1], vec![0.0; n + 1]);
+    for i in 0..=mi {
+        let w = i as f64 / (i + ni) as f64;
+        for j in 0..=n {
+            let (mut within, mut reached) = if i == 0 && j == 0 { (1.0, 0.0) } else { (w * u[j], w * v[j]) };
+            if j > 0 {
+                within += u[j - 1];
+                reached += v[j - 1];
+            }
+            if blocked(i, j as i64) {
+                reached += within;
+                within = 0.0;
+            }
+            u[j] = within;
+            v[j] = reached;
+        }
+    }
+    v[n]
+}
+
+/// Calculate the two-sample Kolmogorov–Smirnov test, also called the
+/// Smirnov test, of whether xs and ys have the same distribution.
+///
+/// The statistic is D, the greatest distance between the empirical cdfs.
+/// For `Alternative::Greater` it is D+, the greatest distance with the cdf
+/// of xs above the cdf of ys, which is when xs tend to be less; and for
+/// `Alternative::Less` it is D-, with the cdf of xs below.
+///
+/// The p-value is exact if n_x n_y < 10,000 and there are no ties;
+/// otherwise it uses the asymptotic Kolmogorov distribution, or for one
+/// side, exp(-2 D² n_x n_y / (n_x + n_y)).
+///
+/// The other fields are NaN.
+///
+/// Return NaN statistics if either side has no values.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::{goodness_of_fit::*, hypothesis::*};
+/// let result = kolmogorov_smirnov_two_sample([1.0, 2.0, 3.0, 4.0], [5.0, 6.0, 7.0, 8.0], Alternative::TwoSided);
+/// assert_eq!(result.statistic, 1.0);
+/// assert!((result.p_value - 2.0 / 70.0).abs() < 1e-15);
+/// ```
+///
+pub fn kolmogorov_smirnov_two_sample<X: AsRef<[f64]>, Y: AsRef<[f64]>>(xs: X, ys: Y, alternative: Alternative) -> TestResult {
+    let (xs, ys) = (sorted(xs.as_ref()), sorted(ys.as_ref()));
+    let (m, n) = (xs.len(), ys.len());
+    if m == 0 || n == 0 { return TestResult::nan(); }
+    let (mf, nf) = (m as f64, n as f64);
+    let (mut i, mut j) = (0, 0);
+    let (mut d_plus, mut d_minus) = (0.0f64, 0.0f64);
+    while i < m || j < n {
+        // Step past every value equal to the next least value.
+        let next = if j == n || (i < m && xs[i] <= ys[j]) { xs[i] } else { ys[j] };
+        while i < m && xs[i] == next { i += 1; }
+        while j < n && ys[j] == next { j += 1; }
+        let difference = i as f64 / mf - j as f64 / nf;
+        d_plus = d_plus.max(difference);
+        d_minus = d_minus.max(-difference);
+    }
+    let d = select_statistic(d_plus, d_minus, alternative);
+    let mut all = xs.clone();
+    all.extend(&ys);
+    all.sort_by(f64::total_cmp);
+    let two_sided = alternative == Alternative::TwoSided;
+    let p_value = if m * n < SMIRNOV_EXACT_MAX && !has_ties(&all) {
+        smirnov_exact_sf(m, n, d, two_sided)
+    } else if two_sided {
+        kolmogorov_sf((mf * nf / (mf + nf)).sqrt() * d)
+    } else {
+        (-2.0 * mf * nf / (mf + nf) * d * d).exp()
+    };
+    TestResult {
+        statistic: d,
+        p_value: p_value.clamp(0.0, 1.0),
+        ..TestResult::nan()
+    }
+}
+
+/// Asymptotic P(A² < z) of the Anderson–Darling statistic, by Marsaglia
+/// and Marsaglia (2004).
+fn anderson_darling_asymptotic_cdf(z: f64) -> f64 {
+    if z <= 0.0 { return 0.0; }
+    if z < 2.0 {
+        (-1.2337141 / z).exp() / z.sqrt()
+            * (2.00012 + (0.247105 - (0.0649821 - (0.0347962 - (0.011672 - 0.00168691 * z) * z) * z) * z) * z)
+    } else {
+        (-(1.0776 - (2.30695 - (0.43424 - (0.082433 - (0.008056 - 0.0003146 * z) * z) * z) * z) * z).exp()).exp()
+    }
+}
+
+/// Correction to the asymptotic cdf x for n values, by Marsaglia and Marsaglia (2004).
+fn anderson_darling_correction(n: f64, x: f64) -> f64 {
+    if x > 0.8 {
+        return (-130.2137 + (745.2337 - (1705.091 - (1950.646 - (1116.360 - 255.7844 * x) * x) * x) * x) * x) / n;
+    }
+    let c = 0.01265 + 0.1757 / n;
+    if x < c {
+        let t = x / c;
+        let t = t.sqrt() * (1.0 - t) * (49.0 * t - 102.0);
+        return t * (0.0037 / (n * n) + 0.00078 / n + 0.00006) / n;
+    }
+    let t = (x - c) / (0.8 - c);
+    let t = -0.00022633 + (6.54034 - (14.6538 - (14.458 - (8.259 - 1.91864 * t) * t) * t) * t) * t;
+    t * (0.04213 + 0.01365 / n) / n
+}
+
+/// Calculate the one-sample Anderson–Darling test of whether values fit the
+/// distribution, which weighs the tails more than Kolmogorov–Smirnov.
+///
+/// The statistic is A². The p-value is by the method of Marsaglia and
+/// Marsaglia (2004), with error about 1e-6.
+///
+/// The other fields are NaN.
+///
+/// Return NaN statistics if there are no values.
+/// Return statistic infinity and p-value 0.0 if any value is outside the
+/// support of the distribution.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::{distribution::*, goodness_of_fit::*};
+/// let values = [0.1, 0.25, 0.4, 0.55, 0.7, 0.85];
+/// let result = anderson_darling(values, &Uniform::new(0.0, 1.0));
+/// assert!(result.p_value > 0.9);
+/// ```
+///
+pub fn anderson_darling<T: AsRef<[f64]>, D: Distribution + ?Sized>(values: T, distribution: &D) -> TestResult {
+    let sorted = sorted(values.as_ref());
+    let n = sorted.len();
+    if n == 0 { return TestResult::nan(); }
+    let nf = n as f64;
+    let sum: f64 = (0..n)
+        .map(|i| {
+            let lower = distribution.cdf(sorted[i]).ln();
+            let upper = distribution.sf(sorted[n - 1 - i]).ln();
+            (2 * i + 1) as f64 * (lower + upper)
+        })
+        .sum();
+    let a2 = -nf - sum / nf;
+    let p_value = if a2.is_infinite() {
+        0.0
+    } else {
+        let x = anderson_darling_asymptotic_cdf(a2);
+        1.0 - (x + anderson_darling_correction(nf, x))
+    };
+    TestResult {
+        statistic: a2,
+        p_value: p_value.clamp(0.0, 1.0),
+        ..TestResult::nan()
+    }
+}
+
+/// Calculate the k-sample Anderson–Darling test of whether the groups have
+/// the same distribution, by Scholz and Stephens (1987), with the midrank
+/// version of the statistic, which allows ties.
+///
+/// The statistic is A²_akN. The p-value is interpolated from the critical
+/// values of the standardized statistic, and is capped from 0.001 to 0.25,
+/// which is the range of the table.
+///
+/// The degrees of freedom are k - 1. The other fields are NaN.
+///
+/// Skip groups without values. Return NaN statistics if there are fewer
+/// than 2 groups with values, if there are fewer than 4 values in all, or
+/// if every value is equal.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::goodness_of_fit::*;
+/// let groups = [
+///     vec![38.7, 41.5, 43.8, 44.5, 45.5, 46.0, 47.7, 58.0],
+///     vec![39.2, 39.3, 39.7, 41.4, 41.8, 42.9, 43.3, 45.8],
+///     vec![34.0, 35.0, 39.0, 40.0, 43.0, 43.0, 44.0, 45.0],
+///     vec![34.0, 34.8, 34.8, 35.4, 37.2, 37.8, 41.2, 42.8],
+/// ];
+/// let result = anderson_darling_k_sample(&groups);
+/// assert!((result.statistic - 8.3926).abs() < 1e-4);
+/// assert!(result.p_value < 0.01);
+/// ```
+///
+pub fn anderson_darling_k_sample<G: AsRef<[f64]>>(groups: &[G]) -> TestResult {
+    let groups: Vec<Vec<f64>> = groups.iter().map(|g| sorted(g.as_ref())).filter(|g| !g.is_empty()).collect();
+    let k = groups.len();
+    let mut all: Vec<f64> = groups.concat();
+    all.sort_by(f64::total_cmp);
+    let n = all.len();
+    if k < 2 || n < 4 || all[0] == all[n - 1] { return TestResult::nan(); }
+    let nf = n as f64;
+    let mut distinct = all.clone();
+    distinct.dedup();
+    // Count of values less than v, and equal to v.
+    let below = |sorted: &[f64], v: f64| sorted.partition_point(|x| *x < v) as f64;
+    let equal = |sorted: &[f64], v: f64| sorted.partition_point(|x| *x <= v) as f64 - below(sorted, v);
+    let mut a2 = 0.0;
+    for group in &groups {
+        let size = group.len() as f64;
+        let sum: f64 = distinct
+            .iter()
+            .map(|v| {
+                let l = equal(&all, *v);
+                let b = below(&all, *v) + l / 2.0;
+                let m = below(group, *v) + equal(group, *v) / 2.0;
+                l / nf * (nf * m - b * size).powi(2) / (b * (nf - b) - nf * l / 4.0)
+            })
+            .sum();
+        a2 += sum / size;
+    }
+    a2 *= (nf - 1.0) / nf;
+
+    // Variance of the statistic, by Scholz and Stephens (1987), equation 4.
+    let kf = k as f64;
+    let h_sum: f64 = groups.iter().map(|g| 1.0 / g.len() as f64).sum();
+    let h: f64 = (1..n).map(|i| 1.0 / i as f64).sum();
+    let mut g = 0.0;
+    let mut inner = 0.0;
+    for j in 2..n {
+        // Σ over i < j of 1 / (N - i)
+        inner += 1.0 / (nf - (j - 1) as f64);
+        g += inner / j as f64;
+    }
+    let a = (4.0 * g - 6.0) * (kf - 1.0) + (10.0 - 6.0 * g) * h_sum;
+    let b = (2.0 * g - 4.0) * kf * kf + 8.0 * h * kf + (2.0 * g - 14.0 * h - 4.0) * h_sum - 8.0 * h + 4.0 * g - 6.0;
+    let c = (6.0 * h + 2.0 * g - 2.0) * kf * kf + (4.0 * h - 4.0 * g + 6.0) * kf + (2.0 * h - 6.0) * h_sum + 4.0 * h;
+    let d = (2.0 * h + 6.0) * kf * kf - 4.0 * h * kf;
+    let variance = (a * nf.powi(3) + b * nf * nf + c * nf + d) / ((nf - 1.0) * (nf - 2.0) * (nf - 3.0));
+    let standardized = (a2 - (kf - 1.0)) / variance.sqrt();
+
+    TestResult {
+        statistic: a2,
+        degrees_of_freedom: kf - 1.0,
+        p_value: anderson_darling_k_sample_p_value(standardized, kf - 1.0),
+        ..TestResult::nan()
+    }
+}
+
+/// P-value of the standardized k-sample statistic with m = k - 1, by
+/// fitting ln p as a quadratic of the critical values of Scholz and
+/// Stephens (1987), table 1.
+fn anderson_darling_k_sample_p_value(t: f64, m: f64) -> f64 {
+    const LEVELS: [f64; 7] = [0.25, 0.1, 0.05, 0.025, 0.01, 0.005, 0.001];
+    const B0: [f64; 7] = [0.675, 1.281, 1.645, 1.96, 2.326, 2.573, 3.085];
+    const B1: [f64; 7] = [-0.245, 0.25, 0.678, 1.149, 1.822, 2.364, 3.615];
+    const B2: [f64; 7] = [-0.105, -0.305, -0.362, -0.391, -0.396, -0.345, -0.154];
+    let critical: Vec<f64> = (0..7).map(|i| B0[i] + B1[i] / m.sqrt() + B2[i] / m).collect();
+    if t.is_nan() { return f64::NAN; }
+    if t <= critical[0] { return LEVELS[0]; }
+    if t >= critical[6] { return LEVELS[6]; }
+    // Least squares fit of ln p = c0 + c1 t + c2 t², by the normal equations.
+    let mut normal = [[0.0; 4]; 3];
+    for (x, p) in critical.iter().zip(LEVELS) {
+        let powers = [1.0, *x, x * x];
+        for r in 0..3 {
+            for c in 0..3 { normal[r][c] += powers[r] * powers[c]; }
+            normal[r][3] += powers[r] * p.ln();
+        }
+    }
+    // Gaussian elimination; the 3 × 3 system is well conditioned here.
+    for col in 0..3 {
+        for row in col + 1..3 {
+            let pivot = normal[col];
+            let factor = normal[row][col] / pivot[col];
+            for (value, p) in normal[row].iter_mut().zip(pivot).skip(col) { *value -= factor * p; }
+        }
+    }
+    let mut coefficients = [0.0; 3];
+    for row in (0..3).rev() {
+        let sum: f64 = (row + 1..3).map(|c| normal[row][c] * coefficients[c]).sum();
+        coefficients[row] = (normal[row][3] - sum) / normal[row][row];
+    }
+    (coefficients[0] + coefficients[1] * t + coefficients[2] * t * t).exp().clamp(LEVELS[6], LEVELS[0])
+}
+
+/// Modified Bessel function of the second kind K_ν(z) for z > 0, by the
+/// trapezoid rule on K_ν(z) = ∫ exp(-z cosh t) cosh(νt) dt from 0 to ∞,
+/// which converges fast since the integrand decays double exponentially.
+fn bessel_k(nu: f64, z: f64) -> f64 {
+    let step = 0.05_f64;
+    let mut sum = 0.5 * (-z).exp();
+    let mut t = step;
+    loop {
+        let term = (-z * t.cosh()).exp() * (nu * t).cosh();
+        sum += term;
+        if term < sum * 1e-17 { break; }
+        t += step;
+    }
+    sum * step
+}
+
+/// Asymptotic P(W² < x) of the Cramér–von Mises statistic, by Anderson
+/// and Darling (1952).
+fn cramer_von_mises_asymptotic_cdf(x: f64) -> f64 {
+    if x <= 0.0 { return 0.0; }
+    let mut sum = 0.0;
+    for k in 0..100 {
+        let kf = k as f64;
+        let u = (ln_gamma(kf + 0.5) - ln_gamma(kf + 1.0)).exp() / (PI.powf(1.5) * x.sqrt());
+        let y = 4.0 * kf + 1.0;
+        let q = y * y / (16.0 * x);
+        if q > 700.0 { break; }
+        let term = u * y.sqrt() * (-q).exp() * bessel_k(0.25, q);
+        sum += term;
+        if term.abs() < 1e-16 { break; }
+    }
+    sum.clamp(0.0, 1.0)
+}
+
+/// Calculate the one-sample Cramér–von Mises test of whether values fit
+/// the distribution.
+///
+/// The statistic is W². The p-value uses the asymptotic distribution of
+/// Stephens' modified statistic (W² - 0.4/n + 0.6/n²)(1 + 1/n), which is
+/// accurate for n >= 5.
+///
+/// The other fields are NaN.
+///
+/// Return NaN statistics if there are no values.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::{distribution::*, goodness_of_fit::*};
+/// let values = [0.1, 0.25, 0.4, 0.55, 0.7, 0.85];
+/// let result = cramer_von_mises(values, &Uniform::new(0.0, 1.0));
+/// assert!(result.p_value > 0.9);
+/// ```
+///
+pub fn cramer_von_mises<T: AsRef<[f64]>, D: Distribution + ?Sized>(values: T, distribution: &D) -> TestResult {
+    let sorted = sorted(values.as_ref());
+    let n = sorted.len();
+    if n == 0 { return TestResult::nan(); }
+    let nf = n as f64;
+    let w2 = 1.0 / (12.0 * nf)
+        + sorted
+            .iter()
+            .enumerate()
+            .map(|(i, x)| ((2 * i + 1) as f64 / (2.0 * nf) - distribution.cdf(*x)).powi(2))
+            .sum::<f64>();
+    let modified = (w2 - 0.4 / nf + 0.6 / (nf * nf)) * (1.0 + 1.0 / nf);
+    TestResult {
+        statistic: w2,
+        p_value: 1.0 - cramer_von_mises_asymptotic_cdf(modified),
+        ..TestResult::nan()
+    }
+}
+
+/// Calculate the two-sample Cramér–von Mises test of whether xs and ys have
+/// the same distribution, by Anderson (1962).
+///
+/// The statistic is T. The p-value uses the asymptotic distribution of T,
+/// adjusted to the exact mean and variance for the sample sizes.
+///
+/// The other fields are NaN.
+///
+/// Return NaN statistics if either side has fewer than 2 values.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::goodness_of_fit::*;
+/// let xs = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0];
+/// let ys = [8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0];
+/// let result = cramer_von_mises_two_sample(xs, ys);
+/// assert!(result.p_value < 0.001);
+/// ```
+///
+pub fn cramer_von_mises_two_sample<X: AsRef<[f64]>, Y: AsRef<[f64]>>(xs: X, ys: Y) -> TestResult {
+    let (xs, ys) = (sorted(xs.as_ref()), sorted(ys.as_ref()));
+    let (m, n) = (xs.len(), ys.len());
+    if m < 2 || n < 2 { return TestResult::nan(); }
+    let (mf, nf) = (m as f64, n as f64);
+    let total = mf + nf;
+    let all: Vec<f64> = xs.iter().chain(&ys).copied().collect();
+    let ranks = ranks(&all);
+    // Ranks are sorted within each side, since each side is sorted.
+    let squares = |ranks: &[f64]| ranks.iter().enumerate().map(|(i, r)| (r - (i + 1) as f64).powi(2)).sum::<f64>();
+    let u = mf * squares(&ranks[..m]) + nf * squares(&ranks[m..]);
+    let t = u / (mf * nf * total) - (4.0 * mf * nf - 1.0) / (6.0 * total);
+    // Exact mean and variance of T, to standardize to the asymptotic distribution.
+    let mean = (1.0 + 1.0 / total) / 6.0;
+    let k = mf * nf;
+    let variance = (total + 1.0) * (4.0 * k * total - 3.0 * (mf * mf + nf * nf) - 2.0 * k) / (45.0 * total * total * 4.0 * k);
+    let standardized = 1.0 / 6.0 + (t - mean) / (45.0 * variance).sqrt();
+    TestResult {
+        statistic: t,
+        p_value: (1.0 - cramer_von_mises_asymptotic_cdf(standardized)).clamp(0.0, 1.0),
+        ..TestResult::nan()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::f64::assert_eq_f64::assert_close;
+    use crate::f64::distribution::*;
+
+    #[test]
+    fn test_empty() {
+        let x: &[f64] = &[];
+        assert!(kolmogorov_smirnov(x, &Normal::standard(), Alternative::TwoSided).p_value.is_nan());
+        assert!(kolmogorov_smirnov_two_sample(x, [1.0], Alternative::TwoSided).p_value.is_nan());
+        assert!(anderson_darling(x, &Normal::standard()).p_value.is_nan());
+        assert!(anderson_darling_k_sample(&[vec![1.0, 2.0, 3.0]]).p_value.is_nan());
+        assert!(cramer_von_mises(x, &Normal::standard()).p_value.is_nan());
+        assert!(cramer_von_mises_two_sample([1.0], [1.0, 2.0]).p_value.is_nan());
+    }
+
+    #[test]
+    fn test_kolmogorov_exact() {
+        // Example of Marsaglia, Tsang, and Wang (2003).
+        assert_close!(kolmogorov_exact_cdf(10, 0.274), 0.6284796154565043, 1e-14);
+        // P(D_1 < d) = 2d - 1 for d >= 1/2.
+        assert_close!(kolmogorov_exact_cdf(1, 0.75), 0.5, 1e-15);
+        // Exact and asymptotic agree for large n.
+        assert_close!(1.0 - kolmogorov_exact_cdf(99, 0.12), kolmogorov_sf(99f64.sqrt() * 0.12), 0.01);
+        // Twice the one-sided tail for d >= 1/2, with no cancellation.
+        assert_close!(kolmogorov_exact_sf(5, 0.9999), 2e-20, relative = 1e-9);
+        assert_close!(kolmogorov_exact_sf(10, 0.5), 1.0 - kolmogorov_exact_cdf(10, 0.5), 1e-14);
+        assert_close!(kolmogorov_exact_sf(10, 0.274), 1.0 - 0.6284796154565043, 1e-14);
+    }
+
+    #[test]
+    fn test_kolmogorov_smirnov() {
+        let uniform = Uniform::new(0.0, 1.0);
+        let values = [0.05, 0.1, 0.15, 0.2, 0.7];
+        let result = kolmogorov_smirnov(values, &uniform, Alternative::TwoSided);
+        assert_close!(result.statistic, 0.6, 1e-15);
+        let greater = kolmogorov_smirnov(values, &uniform, Alternative::Greater);
+        assert_close!(greater.statistic, 0.6, 1e-15);
+        assert!(greater.p_value < result.p_value);
+        // Monte Carlo gives about 0.0303 and 0.0151.
+        assert_close!(result.p_value, 0.0303, 0.001);
+        assert_close!(greater.p_value, 0.0151, 0.001);
+        let less = kolmogorov_smirnov(values, &uniform, Alternative::Less);
+        assert_close!(less.statistic, 0.05, 1e-15);
+        // P(D+_1 >= d) = 1 - d
+        let one = kolmogorov_smirnov([0.3], &uniform, Alternative::Greater);
+        assert_close!(one.p_value, 0.3, 1e-15);
+        // Far in the upper tail, P(D- >= d) = (1 - d)^n.
+        let values = [0.9999, 0.99992, 0.99994, 0.99996, 0.99998];
+        let result = kolmogorov_smirnov(values, &uniform, Alternative::TwoSided);
+        assert_close!(result.statistic, 0.9999, 1e-15);
+        assert_close!(result.p_value, 2e-20, relative = 1e-9);
+        let less = kolmogorov_smirnov(values, &uniform, Alternative::Less);
+        assert_close!(less.p_value, 1e-20, relative = 1e-9);
+    }
+
+    #[test]
+    fn test_kolmogorov_smirnov_two_sample() {
+        let xs = [0.61, 0.29, 0.06, 0.59, -1.73, -0.74, 0.51, -0.56, 0.39, 1.64, 0.05, -0.06, 0.64, -0.82, 0.37];
+        let ys = [1.77, 1.09, 0.96, 1.91, 2.54, 0.63, 1.48, 2.33, 1.57, 0.92, 1.22, 1.15, 0.39, 2.12, 1.33, 0.79];
+        let result = kolmogorov_smirnov_two_sample(xs, ys, Alternative::TwoSided);
+        assert_close!(result.statistic, 97.0 / 120.0, 1e-15);
+        assert!(result.p_value < 1e-4);
+        // xs tend to be less, so the cdf of xs is above.
+        let greater = kolmogorov_smirnov_two_sample(xs, ys, Alternative::Greater);
+        assert_close!(greater.statistic, 97.0 / 120.0, 1e-15);
+        let less = kolmogorov_smirnov_two_sample(xs, ys, Alternative::Less);
+        assert!(less.p_value > 0.5);
+        // Exact p-values by enumerating the 126 orders.
+        let xs = [0.3, 1.2, 2.5, 3.1];
+        let ys = [0.8, 1.9, 2.2, 4.0, 5.5];
+        let result = kolmogorov_smirnov_two_sample(xs, ys, Alternative::TwoSided);
+        assert_close!(result.statistic, 0.4, 1e-15);
+        assert_close!(result.p_value, 94.0 / 126.0, 1e-14);
+        let greater = kolmogorov_smirnov_two_sample(xs, ys, Alternative::Greater);
+        assert_close!(greater.p_value, 50.0 / 126.0, 1e-14);
+        // With ties, use the asymptotic distribution.
+        let tied = kolmogorov_smirnov_two_sample([1.0, 2.0, 2.0, 3.0], [2.0, 3.0, 4.0, 5.0], Alternative::TwoSided);
+        assert_close!(tied.statistic, 0.5, 1e-15);
+        assert_close!(tied.p_value, kolmogorov_sf(2f64.sqrt() * 0.5), 1e-15);
+        // With the samples apart, P(D >= 1) = 2 / C(100, 50), far past 1 - cdf.
+        let xs: Vec<f64> = (0..50).map(|i| i as f64).collect();
+        let ys: Vec<f64> = (50..100).map(|i| i as f64).collect();
+        let result = kolmogorov_smirnov_two_sample(&xs, &ys, Alternative::TwoSided);
+        assert_close!(result.p_value, 1.9823306042836678e-29, relative = 1e-12);
+        let greater = kolmogorov_smirnov_two_sample(&xs, &ys, Alternative::Greater);
+        assert_close!(greater.p_value, 9.911653021418339e-30, relative = 1e-12);
+    }
+
+    #[test]
+    fn test_anderson_darling() {
+        // Asymptotic 5% critical value 2.492.
+        assert_close!(1.0 - anderson_darling_asymptotic_cdf(2.492), 0.05, 1e-4);
+        let outside = anderson_darling([0.5, 1.5], &Uniform::new(0.0, 1.0));
+        assert_eq!(outside.statistic, f64::INFINITY);
+        assert_eq!(outside.p_value, 0.0);
+    }
+
+    #[test]
+    fn test_anderson_darling_k_sample() {
+        let same = anderson_darling_k_sample(&[vec![1.0, 3.0, 5.0, 7.0, 9.0], vec![2.0, 4.0, 6.0, 8.0, 10.0]]);
+        assert_eq!(same.p_value, 0.25);
+        let different = anderson_darling_k_sample(&[vec![1.0, 2.0, 3.0, 4.0, 5.0], vec![6.0, 7.0, 8.0, 9.0, 10.0]]);
+        assert!(different.p_value < 0.01);
+    }
+
+    #[test]
+    fn test_cramer_von_mises() {
+        // Asymptotic 5% critical value 0.46136.
+        assert_close!(1.0 - cramer_von_mises_asymptotic_cdf(0.46136), 0.05, 1e-4);
+        // Asymptotic 1% critical value 0.74346.
+        assert_close!(1.0 - cramer_von_mises_asymptotic_cdf(0.74346), 0.01, 1e-4);
+        let result = cramer_von_mises([0.05, 0.1, 0.15, 0.2, 0.25], &Uniform::new(0.0, 1.0));
+        assert!(result.p_value < 0.05);
+    }
+
+    #[test]
+    fn test_bessel_k() {
+        // K_1/2(z) = √(π / 2z) e^-z
+        for z in [0.1, 1.0, 10.0] {
+            let expected = (PI / (2.0 * z)).sqrt() * (-z).exp();
+            assert_close!(bessel_k(0.5, z) / expected, 1.0, 1e-14);
+        }
+    }
+
+}
@@ -0,0 +1,312 @@
+//! Histogram with fixed-width bins, or with explicit bin edges.
+//!
+//! The bin count or bin width can come from a rule:
+//!
+//! * Sturges: k = ⌈log2(n)⌉ + 1
+//!
+//! * Scott: h = 3.49 σ n^(-1/3)
+//!
+//! * Freedman-Diaconis: h = 2 IQR n^(-1/3)
+//!
+//! * Square root: k = ⌈√n⌉
+//!
+//! * Rice: k = ⌈2 n^(1/3)⌉
+//!
+//! * Doane: k = 1 + ⌈log2(n) + log2(1 + |g1| / σg1)⌉
+//!
+//! Bins are half-open [left, right), except the last bin, which also
+//! includes its right edge, so the max value is counted.
+//!
+//! # Example
+//!
+//! ```rust
+//! use numeric_statistics::f64::histogram::*;
+//!
+//! let values = &[1.0, 2.0, 2.0, 3.0, 3.0, 3.0, 4.0, 4.0, 5.0];
+//! let histogram = Histogram::new(values, &BinRule::Count(4));
+//! assert_eq!(histogram.edges, vec![1.0, 2.0, 3.0, 4.0, 5.0]);
+//! assert_eq!(histogram.counts, vec![1, 2, 3, 3]);
+//! ```
+
+use super::{
+    quantile::*,
+    skewness::*,
+    standard_deviation::*,
+};
+
+/// The max bin count that a rule can choose, to guard against huge allocations.
+pub const MAX_BINS: usize = 1 << 20;
+
+/// Rule for choosing histogram bins.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BinRule {
+    Sturges,
+    Scott,
+    FreedmanDiaconis,
+    SquareRoot,
+    Rice,
+    Doane,
+    /// Explicit bin count.
+    Count(usize),
+    /// Explicit bin width.
+    Width(f64),
+    /// Explicit bin edges, sorted ascending.
+    Edges(Vec<f64>),
+}
+
+/// Histogram bins with edges and counts.
+///
+/// There is one more edge than count. Bin i covers edges[i] to edges[i + 1].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    /// Bin edges, ascending.
+    pub edges: Vec<f64>,
+    /// Count of values per bin.
+    pub counts: Vec<usize>,
+    /// Count of values outside the edges, including infinite values.
+    pub outside: usize,
+}
+
+impl Histogram {
+    /// Create a histogram for values, with bins chosen by the rule.
+    ///
+    /// Return a histogram with no bins if the values have no finite values.
+    ///
+    /// Filter NaN values in the stream.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use numeric_statistics::f64::histogram::*;
+    /// let values = &[1.0, 2.0, 4.0, 8.0];
+    /// let histogram = Histogram::new(values, &BinRule::Sturges);
+    /// assert_eq!(histogram.counts, vec![2, 1, 1]);
+    /// ```
+    ///
+    pub fn new<T: AsRef<[f64]>>(values: T, rule: &BinRule) -> Histogram {
+        let values = values.as_ref();
+        let edges = bin_edges(values, rule);
+        let mut counts = vec![0; edges.len().saturating_sub(1)];
+        let mut outside = 0;
+        let uniform = !matches!(rule, BinRule::Edges(_));
+        values.iter().filter(|x| !x.is_nan()).for_each(|x|
+            match bin_index(&edges, *x, uniform) {
+                Some(i) => counts[i] += 1,
+                None => outside += 1,
+            }
+        );
+        Histogram { edges, counts, outside }
+    }
+
+    /// Count of values within the bins.
+    pub fn total(&self) -> usize {
+        self.counts.iter().sum()
+    }
+
+    /// Width of each bin.
+    pub fn widths(&self) -> Vec<f64> {
+        self.edges.windows(2).map(|w| w[1] - w[0]).collect()
+    }
+
+    /// Center of each bin.
+    pub fn centers(&self) -> Vec<f64> {
+        self.edges.windows(2).map(|w| w[0] + (w[1] - w[0]) / 2.0).collect()
+    }
+
+    /// Probability density of each bin, which is count / (total * width),
+    /// so the densities integrate to 1 over the range.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use numeric_statistics::f64::histogram::*;
+    /// let values = &[1.0, 2.0, 2.0, 3.0];
+    /// let histogram = Histogram::new(values, &BinRule::Count(2));
+    /// assert_eq!(histogram.densities(), vec![0.25, 0.75]);
+    /// ```
+    ///
+    pub fn densities(&self) -> Vec<f64> {
+        let total = self.total() as f64;
+        self.counts
+            .iter()
+            .zip(self.widths())
+            .map(|(count, width)| *count as f64 / (total * width))
+            .collect()
+    }
+}
+
+/// Calculate bin edges for values, with bins chosen by the rule.
+///
+/// For rules, the edges span the min to the max of the finite values.
+/// For an explicit width, the edges step by the width from the min until
+/// they reach the max, unless that would take more than `MAX_BINS` bins.
+/// If all values are equal, the single bin spans value - 0.5 to value + 0.5.
+///
+/// Return empty edges if the values have no finite values, or if the
+/// rule is explicit edges with fewer than 2 edges.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::histogram::*;
+/// let values = &[0.0, 10.0];
+/// let edges = bin_edges(values, &BinRule::Width(2.5));
+/// assert_eq!(edges, vec![0.0, 2.5, 5.0, 7.5, 10.0]);
+/// ```
+///
+pub fn bin_edges<T: AsRef<[f64]>>(values: T, rule: &BinRule) -> Vec<f64> {
+    let values = values.as_ref();
+    if let BinRule::Edges(edges) = rule {
+        let mut edges: Vec<f64> = edges.iter().copied().filter(|x| !x.is_nan()).collect();
+        edges.sort_by(f64::total_cmp);
+        edges.dedup();
+        return if edges.len() < 2 { Vec::new() } else { edges };
+    }
+    let finite: Vec<f64> = values.iter().copied().filter(|x| x.is_finite()).collect();
+    if finite.is_empty() { return Vec::new(); }
+    let lo = finite.iter().fold(f64::INFINITY, |a, x| a.min(*x));
+    let hi = finite.iter().fold(f64::NEG_INFINITY, |a, x| a.max(*x));
+    if lo == hi { return vec![lo - 0.5, hi + 0.5]; }
+    if let BinRule::Width(h) = *rule && h > 0.0 && (hi - lo) / h < MAX_BINS as f64 {
+        let mut edges = vec![lo];
+        for i in 1..=MAX_BINS {
+            edges.push(lo + i as f64 * h);
+            if edges[i] >= hi { break; }
+        }
+        return edges;
+    }
+    let k = bin_count(&finite, rule, hi - lo);
+    (0..=k)
+        .map(|i| if i == k { hi } else { lo + (hi - lo) * (i as f64 / k as f64) })
+        .collect()
+}
+
+/// Calculate the bin count for finite values with a nonzero range.
+fn bin_count(finite: &[f64], rule: &BinRule, range: f64) -> usize {
+    let n = finite.len() as f64;
+    let count = |k: f64| k.ceil().clamp(1.0, MAX_BINS as f64) as usize;
+    let width = |h: f64| if h > 0.0 && h.is_finite() { count(range / h) } else { 1 };
+    match rule {
+        BinRule::Sturges => count(n.log2() + 1.0),
+        BinRule::Scott => width(3.49 * standard_deviation(finite) * n.cbrt().recip()),
+        BinRule::FreedmanDiaconis => width(2.0 * interquartile_range(finite) * n.cbrt().recip()),
+        BinRule::SquareRoot => count(n.sqrt()),
+        BinRule::Rice => count(2.0 * n.cbrt()),
+        BinRule::Doane => {
+            if n < 3.0 { return count(n.log2() + 1.0); }
+            let g1 = skewness_population(finite);
+            let g1 = if g1.is_nan() { 0.0 } else { g1 };
+            let sigma_g1 = (6.0 * (n - 2.0) / ((n + 1.0) * (n + 3.0))).sqrt();
+            count(1.0 + n.log2() + (1.0 + g1.abs() / sigma_g1).log2())
+        }
+        BinRule::Count(k) => (*k).clamp(1, MAX_BINS),
+        BinRule::Width(h) => width(*h),
+        BinRule::Edges(_) => unreachable!("explicit edges have no bin count"),
+    }
+}
+
+/// Find the bin index for a value, or None if the value is outside the edges.
+fn bin_index(edges: &[f64], x: f64, uniform: bool) -> Option<usize> {
+    let k = edges.len().checked_sub(1)?;
+    if k == 0 || x < edges[0] || x > edges[k] { return None; }
+    if x == edges[k] { return Some(k - 1); }
+    let i = if uniform {
+        let i = ((x - edges[0]) / (edges[k] - edges[0]) * k as f64) as usize;
+        // Adjust for rounding at the edges.
+        let i = i.min(k - 1);
+        if x < edges[i] { i - 1 } else if x >= edges[i + 1] { i + 1 } else { i }
+    } else {
+        edges.partition_point(|e| *e <= x) - 1
+    };
+    Some(i)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_empty() {
+        let x: &[f64] = &[];
+        let histogram = Histogram::new(x, &BinRule::Sturges);
+        assert!(histogram.edges.is_empty());
+        assert!(histogram.counts.is_empty());
+    }
+
+    #[test]
+    fn test_nan() {
+        let x: &[f64] = &[f64::NAN];
+        let histogram = Histogram::new(x, &BinRule::Sturges);
+        assert!(histogram.counts.is_empty());
+        assert_eq!(histogram.outside, 0);
+    }
+
+    #[test]
+    fn test_value() {
+        let x: &[f64] = &[1.0];
+        let histogram = Histogram::new(x, &BinRule::Sturges);
+        assert_eq!(histogram.edges, vec![0.5, 1.5]);
+        assert_eq!(histogram.counts, vec![1]);
+    }
+
+    #[test]
+    fn test_values_and_nans_and_infinities() {
+        let x = &[1.0, f64::NAN, 2.0, f64::INFINITY, 3.0, 4.0];
+        let histogram = Histogram::new(x, &BinRule::Count(3));
+        assert_eq!(histogram.counts, vec![1, 1, 2]);
+        assert_eq!(histogram.outside, 1);
+    }
+
+    #[test]
+    fn test_rules() {
+        let x: Vec<f64> = (0..100).map(|i| (i * i) as f64).collect();
+        let bins = |rule| Histogram::new(&x, &rule).counts.len();
+        assert_eq!(bins(BinRule::Sturges), 8);
+        assert_eq!(bins(BinRule::SquareRoot), 10);
+        assert_eq!(bins(BinRule::Rice), 10);
+        assert_eq!(bins(BinRule::Scott), 5);
+        assert_eq!(bins(BinRule::FreedmanDiaconis), 5);
+        assert_eq!(bins(BinRule::Doane), 10);
+    }
+
+    #[test]
+    fn test_rule_width() {
+        let x = &[0.0, 3.3, 10.0];
+        let histogram = Histogram::new(x, &BinRule::Width(3.0));
+        assert_eq!(histogram.edges, vec![0.0, 3.0, 6.0, 9.0, 12.0]);
+        assert_eq!(histogram.counts, vec![1, 1, 0, 1]);
+        let x: Vec<f64> = (0..50).map(|i| 0.1 + i as f64 * 0.037).collect();
+        let histogram = Histogram::new(&x, &BinRule::Width(0.3));
+        assert_eq!(histogram.counts.len(), 7);
+        assert!(histogram.widths().iter().all(|w| (w - 0.3).abs() < 1e-12));
+        assert_eq!(histogram.total(), 50);
+    }
+
+    #[test]
+    fn test_rule_width_zero() {
+        let x = &[1.0, 2.0, 3.0];
+        assert_eq!(Histogram::new(x, &BinRule::Width(0.0)).counts, vec![3]);
+    }
+
+    #[test]
+    fn test_edges() {
+        let x = &[0.0, 1.0, 1.5, 2.0, 5.0, 10.0, 11.0];
+        let histogram = Histogram::new(x, &BinRule::Edges(vec![0.0, 1.0, 2.0, 10.0]));
+        assert_eq!(histogram.counts, vec![1, 2, 3]);
+        assert_eq!(histogram.outside, 1);
+        assert_eq!(histogram.widths(), vec![1.0, 1.0, 8.0]);
+        assert_eq!(histogram.centers(), vec![0.5, 1.5, 6.0]);
+    }
+
+    #[test]
+    fn test_densities_integrate_to_one() {
+        let x: Vec<f64> = (0..1000).map(|i| ((i * 7919) % 1000) as f64 / 10.0).collect();
+        let histogram = Histogram::new(&x, &BinRule::FreedmanDiaconis);
+        let area: f64 = histogram.densities().iter().zip(histogram.widths()).map(|(d, w)| d * w).sum();
+        assert!((area - 1.0).abs() < 1e-12);
+        assert_eq!(histogram.total(), 1000);
+    }
+
+}
@@ -0,0 +1,106 @@
+//! Shared types for hypothesis tests: the alternative hypothesis, and the
+//! test result with the statistic, degrees of freedom, p-value, confidence
+//! interval, and effect size.
+//!
+//! # Example
+//!
+//! ```rust
+//! use numeric_statistics::f64::{hypothesis::*, t_test::*};
+//!
+//! let before = [10.1, 10.4, 9.8, 10.0, 10.3, 9.9];
+//! let after = [11.0, 11.3, 10.9, 11.4, 10.8, 11.1];
+//! let result = welch_t_test(after, before, Alternative::Greater, 0.95);
+//! assert!(result.rejects(0.05));
+//! ```
+
+use super::distribution::Distribution;
+
+/// Alternative hypothesis of a test, relative to the null hypothesis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Alternative {
+    /// The parameter differs from the null value, in either direction.
+    #[default]
+    TwoSided,
+    /// The parameter is less than the null value.
+    Less,
+    /// The parameter is greater than the null value.
+    Greater,
+}
+
+/// Result of a hypothesis test.
+///
+/// Fields that do not apply to a test are NaN, such as the degrees of
+/// freedom of a test with a normal statistic.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TestResult {
+    /// Test statistic, such as t.
+    pub statistic: f64,
+    pub degrees_of_freedom: f64,
+    pub p_value: f64,
+    /// Estimate of the parameter, such as the difference of averages.
+    pub estimate: f64,
+    /// Confidence bounds of the estimate, which are infinite on the open
+    /// side of a one-sided alternative.
+    pub confidence_lower: f64,
+    pub confidence_upper: f64,
+    /// Standardized effect size, such as Cohen's d.
+    pub effect_size: f64,
+}
+
+impl TestResult {
+    /// Create a result with every field NaN, such as for too few values.
+    pub(crate) fn nan() -> TestResult {
+        TestResult {
+            statistic: f64::NAN,
+            degrees_of_freedom: f64::NAN,
+            p_value: f64::NAN,
+            estimate: f64::NAN,
+            confidence_lower: f64::NAN,
+            confidence_upper: f64::NAN,
+            effect_size: f64::NAN,
+        }
+    }
+
+    /// Return true if the p-value is less than the significance level alpha,
+    /// such as 0.05, so the test rejects the null hypothesis.
+    pub fn rejects(&self, alpha: f64) -> bool {
+        self.p_value < alpha
+    }
+}
+
+/// Copy values without NaN values, which the tests filter from the stream.
+pub(crate) fn without_nan(values: &[f64]) -> Vec<f64> {
+    values.iter().copied().filter(|x| !x.is_nan()).collect()
+}
+
+/// P-value of a statistic with a distribution that is symmetric about 0,
+/// such as t or standard normal.
+pub(crate) fn symmetric_p_value<D: Distribution>(distribution: &D, statistic: f64, alternative: Alternative) -> f64 {
+    match alternative {
+        Alternative::TwoSided => (2.0 * distribution.sf(statistic.abs())).min(1.0),
+        Alternative::Less => distribution.cdf(statistic),
+        Alternative::Greater => distribution.sf(statistic),
+    }
+}
+
+/// Confidence bounds of an estimate with a standard error, where the
+/// distribution of the standardized estimate is symmetric about 0.
+///
+/// Return NaN bounds if the confidence is not within 0.0 to 1.0 exclusive.
+pub(crate) fn symmetric_confidence_interval<D: Distribution>(
+    distribution: &D,
+    estimate: f64,
+    standard_error: f64,
+    alternative: Alternative,
+    confidence: f64,
+) -> (f64, f64) {
+    if !(confidence > 0.0 && confidence < 1.0) { return (f64::NAN, f64::NAN); }
+    match alternative {
+        Alternative::TwoSided => {
+            let margin = distribution.quantile(0.5 + confidence / 2.0) * standard_error;
+            (estimate - margin, estimate + margin)
+        }
+        Alternative::Less => (f64::NEG_INFINITY, estimate + distribution.quantile(confidence) * standard_error),
+        Alternative::Greater => (estimate - distribution.quantile(confidence) * standard_error, f64::INFINITY),
+    }
+}
@@ -0,0 +1,91 @@
+use super::{average, skewness::central_moments};
+
+/// Calculate statistical excess kurtosis for values, as the adjusted
+/// estimator G2 that is unbiased for normal samples.
+///
+/// This is the sample kurtosis used by spreadsheets and pandas.
+///
+/// Return NaN if there are fewer than 4 values, or if the values are all equal.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::kurtosis::*;
+/// let values = &[1.0, 2.0, 3.0, 4.0, 10.0];
+/// let kurtosis = kurtosis(values);
+/// assert!((kurtosis - 3.152).abs() < 1e-12);
+/// ```
+///
+pub fn kurtosis<T: AsRef<[f64]>>(values: T) -> f64 {
+    let values = values.as_ref();
+    let n = values.iter().filter(|x| !x.is_nan()).count() as f64;
+    if n < 4.0 { return f64::NAN; }
+    let g2 = kurtosis_population(values);
+    ((n + 1.0) * g2 + 6.0) * (n - 1.0) / ((n - 2.0) * (n - 3.0))
+}
+
+/// Calculate statistical excess kurtosis for values, as the biased
+/// estimator g2 = m4 / m2^2 - 3.
+///
+/// This is the population kurtosis used by SciPy by default,
+/// and by tests such as Jarque-Bera.
+///
+/// Return NaN if the values are empty, or if the values are all equal.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::kurtosis::*;
+/// let values = &[1.0, 2.0, 3.0, 4.0, 10.0];
+/// let kurtosis = kurtosis_population(values);
+/// assert!((kurtosis + 0.212).abs() < 1e-12);
+/// ```
+///
+pub fn kurtosis_population<T: AsRef<[f64]>>(values: T) -> f64 {
+    let values = values.as_ref();
+    let (m2, _, m4) = central_moments(values, average(values));
+    if m2 == 0.0 { return f64::NAN; }
+    m4 / (m2 * m2) - 3.0
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_empty() {
+        let x: &[f64] = &[];
+        assert!(kurtosis(x).is_nan());
+        assert!(kurtosis_population(x).is_nan());
+    }
+
+    #[test]
+    fn test_nan() {
+        let x: &[f64] = &[f64::NAN];
+        assert!(kurtosis(x).is_nan());
+    }
+
+    #[test]
+    fn test_values_equal() {
+        let x: &[f64] = &[2.0, 2.0, 2.0, 2.0];
+        assert!(kurtosis(x).is_nan());
+    }
+
+    #[test]
+    fn test_values_uniform() {
+        let x = &[1.0, 2.0, 3.0, 4.0, 5.0];
+        assert!((kurtosis_population(x) + 1.3).abs() < 1e-12);
+        assert!((kurtosis(x) + 1.2).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_values_and_nans() {
+        let x = &[1.0, f64::NAN, 2.0, 3.0, f64::NAN, 4.0, 10.0];
+        assert!((kurtosis(x) - 3.152).abs() < 1e-12);
+    }
+
+}
@@ -0,0 +1,492 @@
+//! Log-bucketed histogram with bounded relative error, in the style of
+//! DDSketch and HdrHistogram, for streaming quantiles such as p99.9.
+//!
+//! Each bucket i covers the values (γ^(i-1), γ^i], where
+//! γ = (1 + α) / (1 - α) for relative accuracy α. Any quantile is then
+//! within relative error α of an actual value, and the bucket count grows
+//! with the log of the value range rather than with the sample count.
+//! For example, 1 µs to 100 s at α = 0.01 needs about 900 buckets.
+//!
+//! # Example
+//!
+//! ```rust
+//! use numeric_statistics::f64::log_histogram::*;
+//!
+//! let mut histogram = LogHistogram::new(0.01);
+//! for i in 1..=1000 {
+//!     histogram.record(i as f64);
+//! }
+//! let p99 = histogram.value_at_quantile(0.99);
+//! assert!((p99 - 990.0).abs() <= 990.0 * 0.01);
+//! ```
+
+use std::collections::BTreeMap;
+use super::{all::All, summary::Summary};
+
+/// Default relative accuracy, which is 1%.
+pub const DEFAULT_RELATIVE_ACCURACY: f64 = 0.01;
+
+/// One bucket with its value range and count.
+///
+/// For positive values the range is (lower, upper]; for negative values
+/// the range is [lower, upper); the zero bucket has lower = upper = 0.
+/// After buckets collapse, the bucket nearest zero of each sign extends
+/// to zero.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bucket {
+    pub lower: f64,
+    pub upper: f64,
+    pub count: u64,
+}
+
+/// Log-bucketed histogram with bounded relative error.
+#[derive(Debug, Clone)]
+pub struct LogHistogram {
+    relative_accuracy: f64,
+    gamma: f64,
+    ln_gamma: f64,
+    max_buckets: Option<usize>,
+    positive: BTreeMap<i32, u64>,
+    negative: BTreeMap<i32, u64>,
+    /// True if buckets of the sign have collapsed into the bucket nearest zero.
+    positive_collapsed: bool,
+    negative_collapsed: bool,
+    zero: u64,
+    summary: Summary,
+}
+
+impl Default for LogHistogram {
+    fn default() -> Self {
+        LogHistogram::new(DEFAULT_RELATIVE_ACCURACY)
+    }
+}
+
+impl LogHistogram {
+    /// Create a histogram with the relative accuracy, such as 0.01 for 1%.
+    ///
+    /// # Panics
+    ///
+    /// Panic if the relative accuracy is not within 0.0 to 1.0 exclusive.
+    ///
+    pub fn new(relative_accuracy: f64) -> LogHistogram {
+        assert!(
+            relative_accuracy > 0.0 && relative_accuracy < 1.0,
+            "relative accuracy must be within 0.0 to 1.0 exclusive, got {}", relative_accuracy
+        );
+        let gamma = (1.0 + relative_accuracy) / (1.0 - relative_accuracy);
+        LogHistogram {
+            relative_accuracy,
+            gamma,
+            ln_gamma: gamma.ln(),
+            max_buckets: None,
+            positive: BTreeMap::new(),
+            negative: BTreeMap::new(),
+            positive_collapsed: false,
+            negative_collapsed: false,
+            zero: 0,
+            summary: Summary::new(),
+        }
+    }
+
+    /// Create a histogram with the relative accuracy and a max bucket count
+    /// for each sign, so memory stays constant for any value range.
+    ///
+    /// When there are too many buckets, the buckets nearest zero collapse
+    /// together, so the high quantiles keep their accuracy, and the
+    /// bucket nearest zero then extends to zero.
+    ///
+    /// # Panics
+    ///
+    /// Panic if the relative accuracy is not within 0.0 to 1.0 exclusive,
+    /// or if the max bucket count is zero.
+    ///
+    pub fn with_max_buckets(relative_accuracy: f64, max_buckets: usize) -> LogHistogram {
+        assert!(max_buckets > 0, "max buckets must be positive");
+        let mut histogram = LogHistogram::new(relative_accuracy);
+        histogram.max_buckets = Some(max_buckets);
+        histogram
+    }
+
+    pub fn relative_accuracy(&self) -> f64 {
+        self.relative_accuracy
+    }
+
+    /// Count of recorded values.
+    pub fn count(&self) -> u64 {
+        self.summary.count()
+    }
+
+    /// Min recorded value, or NaN if empty.
+    pub fn min(&self) -> f64 {
+        self.summary.min()
+    }
+
+    /// Max recorded value, or NaN if empty.
+    pub fn max(&self) -> f64 {
+        self.summary.max()
+    }
+
+    /// Average of recorded values, or NaN if empty.
+    pub fn average(&self) -> f64 {
+        self.summary.average()
+    }
+
+    /// Sample variance of recorded values, or NaN if empty.
+    pub fn variance(&self) -> f64 {
+        self.summary.variance()
+    }
+
+    /// Count of buckets in use.
+    pub fn bucket_count(&self) -> usize {
+        self.positive.len() + self.negative.len() + usize::from(self.zero > 0)
+    }
+
+    /// Record one value.
+    ///
+    /// Filter NaN values and infinite values in the stream.
+    pub fn record(&mut self, value: f64) {
+        self.record_n(value, 1);
+    }
+
+    /// Record one value n times.
+    ///
+    /// Filter NaN values and infinite values in the stream.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use numeric_statistics::f64::log_histogram::*;
+    /// let mut histogram = LogHistogram::new(0.01);
+    /// histogram.record_n(5.0, 3);
+    /// assert_eq!(histogram.count(), 3);
+    /// ```
+    ///
+    pub fn record_n(&mut self, value: f64, n: u64) {
+        if !value.is_finite() || n == 0 { return; }
+        if value.abs() < f64::MIN_POSITIVE {
+            self.zero += n;
+        } else if value > 0.0 {
+            *self.positive.entry(self.index(value)).or_insert(0) += n;
+        } else {
+            *self.negative.entry(self.index(-value)).or_insert(0) += n;
+        }
+        self.summary.push_n(value, n);
+        self.collapse();
+    }
+
+    /// Merge another histogram into this one.
+    ///
+    /// # Panics
+    ///
+    /// Panic if the histograms have different relative accuracy.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use numeric_statistics::f64::log_histogram::*;
+    /// let mut a = LogHistogram::new(0.01);
+    /// let mut b = LogHistogram::new(0.01);
+    /// a.record(1.0);
+    /// b.record(3.0);
+    /// a.merge(&b);
+    /// assert_eq!(a.count(), 2);
+    /// assert_eq!(a.average(), 2.0);
+    /// ```
+    ///
+    pub fn merge(&mut self, other: &LogHistogram) {
+        assert!(
+            self.relative_accuracy == other.relative_accuracy,
+            "cannot merge histograms with relative accuracy {} and {}",
+            self.relative_accuracy, other.relative_accuracy
+        );
+        if other.count() == 0 { return; }
+        // A collapsed bucket extends to zero, so it absorbs the buckets
+        // below it from the other histogram.
+        let floor = |store: &BTreeMap<i32, u64>, collapsed: bool| if collapsed { store.keys().next().copied() } else { None };
+        let positive_floor = floor(&self.positive, self.positive_collapsed).max(floor(&other.positive, other.positive_collapsed));
+        let negative_floor = floor(&self.negative, self.negative_collapsed).max(floor(&other.negative, other.negative_collapsed));
+        other.positive.iter().for_each(|(i, n)| *self.positive.entry(*i).or_insert(0) += n);
+        other.negative.iter().for_each(|(i, n)| *self.negative.entry(*i).or_insert(0) += n);
+        if let Some(index) = positive_floor {
+            fold_below(&mut self.positive, index);
+            self.positive_collapsed = true;
+        }
+        if let Some(index) = negative_floor {
+            fold_below(&mut self.negative, index);
+            self.negative_collapsed = true;
+        }
+        self.zero += other.zero;
+        self.summary.merge(&other.summary);
+        self.collapse();
+    }
+
+    /// Calculate the value at the quantile, such as 0.999 for p99.9.
+    ///
+    /// The result is within the relative accuracy of an actual value,
+    /// and is clamped to the min and max recorded values.
+    ///
+    /// Return NaN if empty, or if the quantile is not within 0.0 to 1.0.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use numeric_statistics::f64::log_histogram::*;
+    /// let mut histogram = LogHistogram::new(0.01);
+    /// [1.0, 2.0, 4.0].iter().for_each(|x| histogram.record(*x));
+    /// let median = histogram.value_at_quantile(0.5);
+    /// assert!((median - 2.0).abs() <= 2.0 * 0.01);
+    /// ```
+    ///
+    pub fn value_at_quantile(&self, quantile: f64) -> f64 {
+        if self.count() == 0 || !(0.0..=1.0).contains(&quantile) { return f64::NAN; }
+        let rank = (quantile * (self.count() - 1) as f64).floor() as u64;
+        let mut seen: u64 = 0;
+        for (i, n) in self.negative.iter().rev() {
+            seen += n;
+            if seen > rank { return (-self.representative(*i)).clamp(self.min(), self.max()); }
+        }
+        seen += self.zero;
+        if seen > rank { return 0.0; }
+        for (i, n) in self.positive.iter() {
+            seen += n;
+            if seen > rank { return self.representative(*i).clamp(self.min(), self.max()); }
+        }
+        self.max()
+    }
+
+    /// Count the values within lower to upper inclusive.
+    ///
+    /// Each bucket counts when its representative value is within the range,
+    /// so the range boundaries are accurate to the relative accuracy.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use numeric_statistics::f64::log_histogram::*;
+    /// let mut histogram = LogHistogram::new(0.01);
+    /// [1.0, 2.0, 4.0, 8.0].iter().for_each(|x| histogram.record(*x));
+    /// assert_eq!(histogram.count_between(1.5, 5.0), 2);
+    /// ```
+    ///
+    pub fn count_between(&self, lower: f64, upper: f64) -> u64 {
+        let within = |x: f64| lower <= x && x <= upper;
+        let negative: u64 = self.negative.iter().filter(|(i, _)| within(-self.representative(**i))).map(|(_, n)| n).sum();
+        let positive: u64 = self.positive.iter().filter(|(i, _)| within(self.representative(**i))).map(|(_, n)| n).sum();
+        let zero = if within(0.0) { self.zero } else { 0 };
+        negative + zero + positive
+    }
+
+    /// Iterate the nonempty buckets in ascending value order.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use numeric_statistics::f64::log_histogram::*;
+    /// let mut histogram = LogHistogram::new(0.01);
+    /// histogram.record_n(10.0, 2);
+    /// let buckets: Vec<Bucket> = histogram.iter_buckets().collect();
+    /// assert_eq!(buckets.len(), 1);
+    /// assert_eq!(buckets[0].count, 2);
+    /// assert!(buckets[0].lower < 10.0 && 10.0 <= buckets[0].upper);
+    /// ```
+    ///
+    pub fn iter_buckets(&self) -> impl Iterator<Item = Bucket> + '_ {
+        // Bound nearest zero of the bucket, which is zero for a collapsed bucket.
+        let inner = |store: &BTreeMap<i32, u64>, collapsed: bool, i: i32| {
+            if collapsed && store.keys().next() == Some(&i) { 0.0 } else { self.upper(i - 1) }
+        };
+        let negative = self.negative.iter().rev().map(move |(i, n)| Bucket {
+            lower: -self.upper(*i),
+            upper: -inner(&self.negative, self.negative_collapsed, *i),
+            count: *n,
+        });
+        let zero = (self.zero > 0).then_some(Bucket { lower: 0.0, upper: 0.0, count: self.zero });
+        let positive = self.positive.iter().map(move |(i, n)| Bucket {
+            lower: inner(&self.positive, self.positive_collapsed, *i),
+            upper: self.upper(*i),
+            count: *n,
+        });
+        negative.chain(zero).chain(positive)
+    }
+
+    /// Bucket index for a positive value.
+    fn index(&self, value: f64) -> i32 {
+        (value.ln() / self.ln_gamma).ceil() as i32
+    }
+
+    /// Upper bound of the bucket index, which is γ^i.
+    fn upper(&self, index: i32) -> f64 {
+        (index as f64 * self.ln_gamma).exp()
+    }
+
+    /// Representative value of the bucket index, with relative error at most α.
+    fn representative(&self, index: i32) -> f64 {
+        2.0 * self.upper(index) / (self.gamma + 1.0)
+    }
+
+    /// Collapse the buckets nearest zero when there are too many buckets.
+    fn collapse(&mut self) {
+        let Some(max_buckets) = self.max_buckets else { return; };
+        for (store, collapsed) in [(&mut self.positive, &mut self.positive_collapsed), (&mut self.negative, &mut self.negative_collapsed)] {
+            while store.len() > max_buckets {
+                let (_, n) = store.pop_first().unwrap();
+                *store.first_entry().unwrap().get_mut() += n;
+                *collapsed = true;
+            }
+        }
+    }
+}
+
+/// Fold the buckets below the index into the bucket at the index.
+fn fold_below(store: &mut BTreeMap<i32, u64>, index: i32) {
+    let below: u64 = store.range(..index).map(|(_, n)| n).sum();
+    if below == 0 { return; }
+    store.retain(|i, _| *i >= index);
+    *store.entry(index).or_insert(0) += below;
+}
+
+impl From<&LogHistogram> for All {
+    fn from(histogram: &LogHistogram) -> All {
+        All::from(&histogram.summary)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::f64::quantile::quantile;
+
+    #[test]
+    fn test_empty() {
+        let histogram = LogHistogram::new(0.01);
+        assert_eq!(histogram.count(), 0);
+        assert!(histogram.value_at_quantile(0.5).is_nan());
+        assert!(histogram.average().is_nan());
+        assert_eq!(histogram.iter_buckets().count(), 0);
+    }
+
+    #[test]
+    fn test_nan() {
+        let mut histogram = LogHistogram::new(0.01);
+        histogram.record(f64::NAN);
+        histogram.record(f64::INFINITY);
+        assert_eq!(histogram.count(), 0);
+    }
+
+    #[test]
+    fn test_relative_error() {
+        // Latency from 1 µs to 10 s, in seconds.
+        let values: Vec<f64> = (0..10000).map(|i| 1e-6 * 10f64.powf(7.0 * i as f64 / 9999.0)).collect();
+        let mut histogram = LogHistogram::new(0.01);
+        values.iter().for_each(|x| histogram.record(*x));
+        for q in [0.0, 0.1, 0.5, 0.9, 0.99, 0.999, 1.0] {
+            let expect = quantile(&values, q);
+            let actual = histogram.value_at_quantile(q);
+            assert!((actual - expect).abs() <= expect * 0.01 * 1.0001, "q: {} expect: {} actual: {}", q, expect, actual);
+        }
+        assert!(histogram.bucket_count() < 1000);
+    }
+
+    #[test]
+    fn test_negative_and_zero() {
+        let mut histogram = LogHistogram::new(0.01);
+        [-4.0, -2.0, 0.0, 2.0, 4.0].iter().for_each(|x| histogram.record(*x));
+        assert_eq!(histogram.value_at_quantile(0.0), -4.0);
+        assert_eq!(histogram.value_at_quantile(0.5), 0.0);
+        assert_eq!(histogram.value_at_quantile(1.0), 4.0);
+        assert!((histogram.value_at_quantile(0.25) + 2.0).abs() <= 0.02);
+        assert_eq!(histogram.count_between(-3.0, 3.0), 3);
+        let buckets: Vec<Bucket> = histogram.iter_buckets().collect();
+        assert_eq!(buckets.len(), 5);
+        assert!(buckets.windows(2).all(|w| w[0].upper <= w[1].lower));
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut a = LogHistogram::new(0.02);
+        let mut b = LogHistogram::new(0.02);
+        let mut c = LogHistogram::new(0.02);
+        (1..=100).for_each(|i| { a.record(i as f64); c.record(i as f64); });
+        (101..=300).for_each(|i| { b.record(i as f64); c.record(i as f64); });
+        a.merge(&b);
+        assert_eq!(a.count(), c.count());
+        assert_eq!(a.value_at_quantile(0.9), c.value_at_quantile(0.9));
+        assert!((a.average() - c.average()).abs() < 1e-9);
+        assert!((a.variance() - c.variance()).abs() < 1e-6);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_merge_mismatch() {
+        let mut a = LogHistogram::new(0.01);
+        a.merge(&LogHistogram::new(0.02));
+    }
+
+    #[test]
+    fn test_max_buckets() {
+        let mut histogram = LogHistogram::with_max_buckets(0.01, 100);
+        (1..=100000).for_each(|i| histogram.record(i as f64));
+        assert!(histogram.bucket_count() <= 100);
+        let p99 = histogram.value_at_quantile(0.99);
+        assert!((p99 - 99000.0).abs() <= 99000.0 * 0.01);
+        assert_eq!(histogram.count(), 100000);
+    }
+
+    /// Assert each bucket counts exactly the values within its bounds.
+    fn assert_buckets_cover(histogram: &LogHistogram, values: &[f64]) {
+        for bucket in histogram.iter_buckets() {
+            let within = values
+                .iter()
+                .filter(|x| {
+                    let x = **x;
+                    if x > 0.0 {
+                        bucket.lower < x && x <= bucket.upper
+                    } else if x < 0.0 {
+                        bucket.lower <= x && x < bucket.upper
+                    } else {
+                        bucket.lower == 0.0 && bucket.upper == 0.0
+                    }
+                })
+                .count() as u64;
+            assert_eq!(within, bucket.count, "{:?}", bucket);
+        }
+    }
+
+    #[test]
+    fn test_max_buckets_bounds() {
+        // Values between the bucket bounds, from 1e-3 to 1e3 of each sign.
+        let values: Vec<f64> = (0..600)
+            .map(|i| 10f64.powf(-3.0 + i as f64 / 100.0) * 1.003)
+            .flat_map(|x| [x, -x])
+            .chain([0.0])
+            .collect();
+        let mut histogram = LogHistogram::with_max_buckets(0.01, 50);
+        values.iter().for_each(|x| histogram.record(*x));
+        assert_eq!(histogram.bucket_count(), 101);
+        assert_buckets_cover(&histogram, &values);
+        // A collapsed histogram merged into one with lesser values.
+        let lesser: Vec<f64> = values.iter().map(|x| x * 1e-4).collect();
+        let mut merged = LogHistogram::new(0.01);
+        lesser.iter().for_each(|x| merged.record(*x));
+        merged.merge(&histogram);
+        let all: Vec<f64> = lesser.iter().chain(&values).copied().collect();
+        assert_buckets_cover(&merged, &all);
+        assert_eq!(merged.count(), all.len() as u64);
+    }
+
+    #[test]
+    fn test_all() {
+        let mut histogram = LogHistogram::new(0.01);
+        histogram.record(1.0);
+        histogram.record_n(2.0, 1);
+        histogram.record(4.0);
+        let all = All::from(&histogram);
+        let expect = All::new([1.0, 2.0, 4.0]);
+        assert_eq!(all.min, expect.min);
+        assert_eq!(all.max, expect.max);
+        assert!((all.average - expect.average).abs() < 1e-12);
+        assert!((all.variance - expect.variance).abs() < 1e-12);
+        assert!((all.standard_deviation - expect.standard_deviation).abs() < 1e-12);
+    }
+
+}
@@ -1,5 +1,7 @@
 pub mod all; 
 
+pub mod summary;
+
 pub mod assert_eq_f64;
 
 pub mod min; 
@@ -21,3 +23,77 @@ use variance::*;
 pub mod standard_deviation;
 #[allow(unused_imports)] 
 use standard_deviation::*;
+
+pub mod display;
+
+pub mod quantile;
+#[allow(unused_imports)] 
+use quantile::*;
+
+pub mod skewness;
+#[allow(unused_imports)] 
+use skewness::*;
+
+pub mod histogram;
+
+pub mod log_histogram;
+
+pub mod plot;
+
+pub mod svg;
+
+pub mod five_number_summary;
+#[allow(unused_imports)] 
+use five_number_summary::*;
+
+pub mod box_plot;
+
+pub mod kurtosis;
+#[allow(unused_imports)] 
+use kurtosis::*;
+
+pub mod describe;
+
+pub mod rolling;
+
+pub mod rolling_quantile;
+
+pub mod ewma;
+
+pub mod time_buckets;
+
+pub mod duration;
+
+pub mod network;
+
+pub mod ping;
+
+pub mod rank;
+#[allow(unused_imports)]
+use rank::*;
+
+pub mod correlation;
+#[allow(unused_imports)]
+use correlation::*;
+
+pub mod special;
+
+pub mod distribution;
+
+pub mod regression;
+
+pub mod robust_regression;
+
+pub mod hypothesis;
+
+pub mod t_test;
+
+pub mod nonparametric;
+
+pub mod goodness_of_fit;
+
+pub mod normality;
+
+pub mod contingency;
+
+pub mod anova;
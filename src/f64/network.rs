@@ -0,0 +1,398 @@
+//! Network statistics: interarrival jitter, mean absolute successive
+//! difference, packet loss, loss bursts, and reordering.
+//!
+//! Jitter follows RFC 3550 section 6.4.1: for each packet i, the
+//! difference D between its transit time and the transit time of the
+//! previous packet updates the smoothed estimate J += (|D| - J) / 16.
+//! A transit time is the arrival time minus the send time; a round trip
+//! time (RTT), such as from ping, works the same way, because only the
+//! differences matter.
+//!
+//! Loss, bursts, and reordering come from packet sequence numbers, in
+//! arrival order. Expected packets are every sequence number from the
+//! lowest to the highest that arrived.
+//!
+//! Each statistic is available in batch, such as `jitter`, and streaming,
+//! such as `Jitter`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use numeric_statistics::f64::network::*;
+//!
+//! let rtts = [10.0, 12.0, 11.0, 15.0];
+//! assert_eq!(mean_absolute_successive_difference(rtts), 7.0 / 3.0);
+//!
+//! let stats = sequence_stats([1, 2, 5, 4, 6, 6]);
+//! assert_eq!(stats.expected, 6);
+//! assert_eq!(stats.lost, 1);
+//! assert_eq!(stats.reordered, 1);
+//! assert_eq!(stats.duplicates, 1);
+//! ```
+
+use std::collections::BTreeMap;
+
+/// Gain of the RFC 3550 jitter estimator.
+const JITTER_GAIN: f64 = 1.0 / 16.0;
+
+/// Streaming RFC 3550 jitter and mean absolute successive difference
+/// of transit times.
+#[derive(Debug, Clone)]
+pub struct Jitter {
+    count: u64,
+    last: f64,
+    jitter: f64,
+    absolute_difference_sum: f64,
+}
+
+impl Default for Jitter {
+    fn default() -> Self {
+        Jitter::new()
+    }
+}
+
+impl Jitter {
+    pub fn new() -> Jitter {
+        Jitter { count: 0, last: f64::NAN, jitter: 0.0, absolute_difference_sum: 0.0 }
+    }
+
+    /// Count of transit times.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Push the transit time of the next packet in arrival order.
+    ///
+    /// Filter NaN values in the stream, such as for lost packets.
+    pub fn push(&mut self, transit: f64) {
+        if transit.is_nan() { return; }
+        if self.count > 0 {
+            let d = (transit - self.last).abs();
+            self.jitter += (d - self.jitter) * JITTER_GAIN;
+            self.absolute_difference_sum += d;
+        }
+        self.last = transit;
+        self.count += 1;
+    }
+
+    /// Push the send time and the arrival time of the next packet in arrival order.
+    pub fn push_times(&mut self, send: f64, arrival: f64) {
+        self.push(arrival - send);
+    }
+
+    /// RFC 3550 interarrival jitter, or NaN if there are fewer than 2 transit times.
+    pub fn jitter(&self) -> f64 {
+        if self.count < 2 { f64::NAN } else { self.jitter }
+    }
+
+    /// Mean absolute successive difference, or NaN if there are fewer than 2 transit times.
+    pub fn mean_absolute_successive_difference(&self) -> f64 {
+        if self.count < 2 { f64::NAN } else { self.absolute_difference_sum / (self.count - 1) as f64 }
+    }
+}
+
+/// Calculate RFC 3550 interarrival jitter for transit times in arrival order.
+///
+/// Return NaN if there are fewer than 2 values.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::network::*;
+/// let jitter = jitter([10.0, 26.0]);
+/// assert_eq!(jitter, 1.0);
+/// ```
+///
+pub fn jitter<T: AsRef<[f64]>>(values: T) -> f64 {
+    let mut jitter = Jitter::new();
+    values.as_ref().iter().for_each(|x| jitter.push(*x));
+    jitter.jitter()
+}
+
+/// Calculate mean absolute successive difference for values,
+/// which is the mean of |x[i] - x[i - 1]|, such as for ping jitter.
+///
+/// Return NaN if there are fewer than 2 values.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::network::*;
+/// let masd = mean_absolute_successive_difference([1.0, 3.0, 2.0]);
+/// assert_eq!(masd, 1.5);
+/// ```
+///
+pub fn mean_absolute_successive_difference<T: AsRef<[f64]>>(values: T) -> f64 {
+    let mut jitter = Jitter::new();
+    values.as_ref().iter().for_each(|x| jitter.push(*x));
+    jitter.mean_absolute_successive_difference()
+}
+
+/// Packet loss, loss bursts, and reordering of sequence numbers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SequenceStats {
+    /// Count of sequence numbers from the lowest to the highest received,
+    /// or u64::MAX if there are more, from 0 to u64::MAX.
+    pub expected: u64,
+    /// Count of distinct sequence numbers received.
+    pub received: u64,
+    /// Count of expected sequence numbers not received.
+    pub lost: u64,
+    /// Count of packets with a sequence number already received.
+    pub duplicates: u64,
+    /// Count of packets that arrived after a packet with a higher sequence number.
+    pub reordered: u64,
+    /// Lost divided by expected, or NaN if nothing is expected.
+    pub loss_ratio: f64,
+    /// Count of loss bursts by burst length, where a burst is a run of
+    /// consecutive lost sequence numbers.
+    pub burst_lengths: BTreeMap<u64, u64>,
+}
+
+impl SequenceStats {
+    /// Count of loss bursts.
+    pub fn burst_count(&self) -> u64 {
+        self.burst_lengths.values().sum()
+    }
+
+    /// Longest loss burst, or 0 if nothing is lost.
+    pub fn max_burst_length(&self) -> u64 {
+        self.burst_lengths.keys().next_back().copied().unwrap_or(0)
+    }
+
+    /// Mean loss burst length, or NaN if nothing is lost.
+    pub fn mean_burst_length(&self) -> f64 {
+        self.lost as f64 / self.burst_count() as f64
+    }
+}
+
+/// Streaming tracker of sequence numbers for loss, loss bursts, and reordering.
+///
+/// Memory is proportional to the count of runs of sequence numbers still
+/// missing, so a far-off sequence number costs one run, not its whole gap.
+#[derive(Debug, Clone, Default)]
+pub struct SequenceTracker {
+    /// Lowest and highest sequence numbers received.
+    range: Option<(u64, u64)>,
+    received: u64,
+    duplicates: u64,
+    reordered: u64,
+    /// Runs of missing sequence numbers, from first to last inclusive.
+    missing: BTreeMap<u64, u64>,
+    /// Count of missing sequence numbers in the runs.
+    lost: u64,
+}
+
+impl SequenceTracker {
+    pub fn new() -> SequenceTracker {
+        SequenceTracker::default()
+    }
+
+    /// Push the sequence number of the next packet in arrival order.
+    pub fn push(&mut self, seq: u64) {
+        let Some((lowest, highest)) = self.range else {
+            self.range = Some((seq, seq));
+            self.received = 1;
+            return;
+        };
+        if seq > highest {
+            self.insert_missing(highest + 1, seq - 1);
+            self.range = Some((lowest, seq));
+        } else if seq < lowest {
+            self.insert_missing(seq + 1, lowest - 1);
+            self.range = Some((seq, highest));
+            self.reordered += 1;
+        } else if self.remove_missing(seq) {
+            self.reordered += 1;
+        } else {
+            self.duplicates += 1;
+            return;
+        }
+        self.received += 1;
+    }
+
+    /// Insert a run of missing sequence numbers, if not empty.
+    fn insert_missing(&mut self, first: u64, last: u64) {
+        if first > last { return; }
+        self.missing.insert(first, last);
+        self.lost += last - first + 1;
+    }
+
+    /// Remove a sequence number from its run of missing sequence numbers,
+    /// splitting the run. Return false if it is not missing.
+    fn remove_missing(&mut self, seq: u64) -> bool {
+        let Some((&first, &last)) = self.missing.range(..=seq).next_back() else { return false; };
+        if seq > last { return false; }
+        self.missing.remove(&first);
+        self.lost -= last - first + 1;
+        if first < seq { self.insert_missing(first, seq - 1); }
+        if seq < last { self.insert_missing(seq + 1, last); }
+        true
+    }
+
+    /// Push a 16-bit sequence number that wraps around, such as an ICMP or
+    /// RTP sequence number, as the nearest sequence number to the highest.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use numeric_statistics::f64::network::*;
+    /// let mut tracker = SequenceTracker::new();
+    /// for seq in [65534, 65535, 0, 1] {
+    ///     tracker.push_u16(seq);
+    /// }
+    /// assert_eq!(tracker.stats().lost, 0);
+    /// ```
+    ///
+    pub fn push_u16(&mut self, seq: u16) {
+        let highest = self.range.map_or(1 << 16, |(_, highest)| highest);
+        self.push(unwrap_u16(highest, seq));
+    }
+
+    /// Calculate the statistics of the sequence numbers so far.
+    pub fn stats(&self) -> SequenceStats {
+        let expected = self.range.map_or(0, |(lowest, highest)| (highest - lowest).saturating_add(1));
+        let lost = self.lost;
+        // Runs are separated by received sequence numbers, so each is a burst.
+        let mut burst_lengths = BTreeMap::new();
+        for (first, last) in &self.missing {
+            *burst_lengths.entry(last - first + 1).or_insert(0) += 1;
+        }
+        SequenceStats {
+            expected,
+            received: self.received,
+            lost,
+            duplicates: self.duplicates,
+            reordered: self.reordered,
+            loss_ratio: lost as f64 / expected as f64,
+            burst_lengths,
+        }
+    }
+}
+
+/// Unwrap a 16-bit sequence number as the nearest sequence number to the
+/// reference, which starts at 2^16 so early sequence numbers can be lower.
+pub(crate) fn unwrap_u16(reference: u64, seq: u16) -> u64 {
+    let delta = seq.wrapping_sub(reference as u16) as i16 as i64;
+    (reference as i64 + delta).max(0) as u64
+}
+
+/// Calculate packet loss, loss bursts, and reordering for sequence numbers in arrival order.
+///
+/// Return a loss ratio of NaN if the sequence numbers are empty.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::network::*;
+/// let stats = sequence_stats([1, 4, 5, 9]);
+/// assert_eq!(stats.lost, 5);
+/// assert_eq!(stats.loss_ratio, 5.0 / 9.0);
+/// assert_eq!(stats.burst_lengths.get(&2), Some(&1));
+/// assert_eq!(stats.burst_lengths.get(&3), Some(&1));
+/// ```
+///
+pub fn sequence_stats<T: AsRef<[u64]>>(seqs: T) -> SequenceStats {
+    let mut tracker = SequenceTracker::new();
+    seqs.as_ref().iter().for_each(|seq| tracker.push(*seq));
+    tracker.stats()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_empty() {
+        let x: &[f64] = &[];
+        assert!(jitter(x).is_nan());
+        assert!(mean_absolute_successive_difference(x).is_nan());
+        let stats = sequence_stats([]);
+        assert_eq!(stats.expected, 0);
+        assert!(stats.loss_ratio.is_nan());
+        assert!(stats.mean_burst_length().is_nan());
+    }
+
+    #[test]
+    fn test_nan() {
+        let x = [1.0, f64::NAN, 3.0];
+        assert_eq!(mean_absolute_successive_difference(x), 2.0);
+        assert_eq!(jitter(x), 2.0 / 16.0);
+    }
+
+    #[test]
+    fn test_jitter_smoothing() {
+        let x = [0.0, 16.0, 16.0];
+        // J1 = 16 / 16 = 1; J2 = 1 + (0 - 1) / 16
+        assert_eq!(jitter(x), 1.0 - 1.0 / 16.0);
+        let mut streaming = Jitter::new();
+        streaming.push_times(100.0, 100.0);
+        streaming.push_times(200.0, 216.0);
+        assert_eq!(streaming.jitter(), 1.0);
+    }
+
+    #[test]
+    fn test_reordering_fills_loss() {
+        let stats = sequence_stats([1, 2, 6, 4, 3, 7]);
+        assert_eq!(stats.expected, 7);
+        assert_eq!(stats.received, 6);
+        assert_eq!(stats.lost, 1);
+        assert_eq!(stats.reordered, 2);
+        assert_eq!(stats.burst_lengths, BTreeMap::from([(1, 1)]));
+    }
+
+    #[test]
+    fn test_bursts() {
+        let stats = sequence_stats([0, 3, 4, 5, 10, 12]);
+        assert_eq!(stats.lost, 7);
+        assert_eq!(stats.burst_lengths, BTreeMap::from([(1, 1), (2, 1), (4, 1)]));
+        assert_eq!(stats.burst_count(), 3);
+        assert_eq!(stats.max_burst_length(), 4);
+        assert_eq!(stats.mean_burst_length(), 7.0 / 3.0);
+    }
+
+    #[test]
+    fn test_huge_gap() {
+        // A far-off sequence number is one run, not 2^40 entries.
+        let stats = sequence_stats([0, 1 << 40, 5, 6]);
+        assert_eq!(stats.expected, (1 << 40) + 1);
+        assert_eq!(stats.lost, (1 << 40) - 3);
+        assert_eq!(stats.reordered, 2);
+        assert_eq!(stats.burst_lengths, BTreeMap::from([(4, 1), ((1 << 40) - 7, 1)]));
+    }
+
+    #[test]
+    fn test_extreme_sequence_numbers() {
+        for order in [[0, u64::MAX], [u64::MAX, 0]] {
+            let stats = sequence_stats(order);
+            assert_eq!(stats.expected, u64::MAX);
+            assert_eq!(stats.lost, u64::MAX - 1);
+            assert_eq!(stats.burst_lengths, BTreeMap::from([(u64::MAX - 1, 1)]));
+        }
+    }
+
+    #[test]
+    fn test_before_first() {
+        let stats = sequence_stats([5, 6, 2]);
+        assert_eq!(stats.expected, 5);
+        assert_eq!(stats.lost, 2);
+        assert_eq!(stats.reordered, 1);
+    }
+
+    #[test]
+    fn test_u16_wraps() {
+        let mut tracker = SequenceTracker::new();
+        for seq in [65533, 65535, 1, 0] {
+            tracker.push_u16(seq);
+        }
+        let stats = tracker.stats();
+        assert_eq!(stats.expected, 5);
+        assert_eq!(stats.lost, 1);
+        assert_eq!(stats.reordered, 1);
+    }
+
+}
@@ -0,0 +1,666 @@
+//! Nonparametric rank tests, which suit skewed data such as latency:
+//! Mann–Whitney U, Wilcoxon signed-rank, and Kruskal–Wallis H, with the
+//! Hodges–Lehmann estimators of shift and location.
+//!
+//! The p-values are exact for small samples without ties, and otherwise
+//! use the normal or chi-squared approximation, corrected for ties.
+//!
+//! # Example
+//!
+//! ```rust
+//! use numeric_statistics::f64::{hypothesis::*, nonparametric::*};
+//!
+//! // Latencies with outliers, before and after a deploy.
+//! let before = [12.0, 11.0, 13.0, 12.5, 11.5, 250.0, 12.2];
+//! let after = [14.0, 15.5, 13.8, 14.9, 15.1, 14.2, 900.0];
+//! let result = mann_whitney_u(after, before, Alternative::Greater, 0.95);
+//! assert!(result.p_value < 0.01);
+//! assert!(result.confidence_lower > 0.0);
+//! ```
+
+use super::{
+    distribution::{ChiSquared, Distribution, Normal},
+    hypothesis::*,
+    rank::*,
+};
+
+/// Max sample size, on each side, for exact p-values.
+const EXACT_MAX: usize = 50;
+
+/// Max count of arrangements to enumerate for exact Kruskal–Wallis p-values.
+const KRUSKAL_WALLIS_EXACT_MAX: f64 = 100_000.0;
+
+/// Tie correction sum Σ (t³ - t) over groups of tied values.
+fn tie_correction(values: &[f64]) -> f64 {
+    tie_sizes(values).iter().map(|t| (*t as f64).powi(3) - *t as f64).sum()
+}
+
+/// Exact distribution of a statistic, as counts of equally likely
+/// arrangements for each value 0, 1, 2, ...
+struct Counts(Vec<f64>);
+
+impl Counts {
+    /// Counts of the Mann–Whitney U statistic for sample sizes m and n,
+    /// by the recurrence on which sample has the greatest value.
+    fn mann_whitney(m: usize, n: usize) -> Counts {
+        // columns[i] holds the counts for sizes i and j, for the current j.
+        let mut columns: Vec<Vec<f64>> = vec![vec![1.0]; m + 1];
+        for j in 1..=n {
+            for i in 1..=m {
+                let mut counts = vec![0.0; i * j + 1];
+                // The greatest value is from the first sample, so it exceeds all j values.
+                columns[i - 1].iter().enumerate().for_each(|(u, c)| counts[u + j] += c);
+                // The greatest value is from the second sample.
+                columns[i].iter().enumerate().for_each(|(u, c)| counts[u] += c);
+                columns[i] = counts;
+            }
+        }
+        Counts(columns.swap_remove(m))
+    }
+
+    /// Counts of the Wilcoxon signed-rank statistic for n values, as the
+    /// count of subsets of ranks 1 to n with each sum.
+    fn signed_rank(n: usize) -> Counts {
+        let mut counts = vec![1.0];
+        for rank in 1..=n {
+            let mut next = counts.clone();
+            next.resize(counts.len() + rank, 0.0);
+            counts.iter().enumerate().for_each(|(s, c)| next[s + rank] += c);
+            counts = next;
+        }
+        Counts(counts)
+    }
+
+    /// P(S <= s).
+    fn cdf(&self, s: f64) -> f64 {
+        if s < 0.0 { return 0.0; }
+        let total: f64 = self.0.iter().sum();
+        let end = (s.floor() as usize + 1).min(self.0.len());
+        self.0[..end].iter().sum::<f64>() / total
+    }
+
+    /// P(S >= s), summing the counts from s up, as 1 - P(S < s) cancels
+    /// in the upper tail.
+    fn sf_inclusive(&self, s: f64) -> f64 {
+        if s <= 0.0 { return 1.0; }
+        let total: f64 = self.0.iter().sum();
+        let start = (s.ceil() as usize).min(self.0.len());
+        self.0[start..].iter().sum::<f64>() / total
+    }
+
+    /// Least s such that P(S <= s) >= p.
+    fn quantile(&self, p: f64) -> usize {
+        let total: f64 = self.0.iter().sum();
+        let mut sum = 0.0;
+        for (s, c) in self.0.iter().enumerate() {
+            sum += c;
+            if sum / total >= p { return s; }
+        }
+        self.0.len() - 1
+    }
+
+    /// P-value of s.
+    fn p_value(&self, s: f64, alternative: Alternative) -> f64 {
+        match alternative {
+            Alternative::TwoSided => (2.0 * self.cdf(s).min(self.sf_inclusive(s))).min(1.0),
+            Alternative::Less => self.cdf(s),
+            Alternative::Greater => self.sf_inclusive(s),
+        }
+    }
+}
+
+/// P-value of a statistic s with a mean and a standard deviation, by the
+/// normal approximation with continuity correction, which is 0 for a
+/// two-sided test of a statistic equal to its mean, as in R.
+fn normal_p_value(s: f64, mean: f64, standard_deviation: f64, alternative: Alternative) -> f64 {
+    let correction = match alternative {
+        Alternative::TwoSided if s == mean => 0.0,
+        Alternative::TwoSided => 0.5 * (s - mean).signum(),
+        Alternative::Less => -0.5,
+        Alternative::Greater => 0.5,
+    };
+    let z = (s - mean - correction) / standard_deviation;
+    symmetric_p_value(&Normal::standard(), z, alternative)
+}
+
+/// Rank, from 1, of the lower confidence bound in the sorted estimates,
+/// such as pairwise differences, where the upper bound has the same rank
+/// from the end.
+fn lower_bound_rank(exact: Option<&Counts>, count: usize, mean: f64, standard_deviation: f64, alpha: f64) -> usize {
+    let rank = match exact {
+        Some(counts) => counts.quantile(alpha),
+        None => (mean - Normal::standard().quantile(1.0 - alpha) * standard_deviation).round().max(0.0) as usize,
+    };
+    rank.clamp(1, count)
+}
+
+/// Confidence bounds from the estimates, such as pairwise differences.
+///
+/// Return NaN bounds if the confidence is not within 0.0 to 1.0 exclusive.
+fn confidence_interval<F: Fn(usize, usize) -> f64>(
+    estimates: &SortedRows<F>,
+    exact: Option<&Counts>,
+    mean: f64,
+    standard_deviation: f64,
+    alternative: Alternative,
+    confidence: f64,
+) -> (f64, f64) {
+    let count = estimates.count();
+    if !(confidence > 0.0 && confidence < 1.0) || count == 0 { return (f64::NAN, f64::NAN); }
+    let alpha = 1.0 - confidence;
+    let alpha = if alternative == Alternative::TwoSided { alpha / 2.0 } else { alpha };
+    let k = lower_bound_rank(exact, count, mean, standard_deviation, alpha);
+    match alternative {
+        Alternative::TwoSided => (estimates.select(k), estimates.select(count + 1 - k)),
+        Alternative::Less => (f64::NEG_INFINITY, estimates.select(count + 1 - k)),
+        Alternative::Greater => (estimates.select(k), f64::INFINITY),
+    }
+}
+
+/// Values in rows that are each ascending, where row i has `lens[i]`
+/// values and `value(i, j)` is its jth value, such as the pairwise
+/// differences of two samples, so a value of a given rank can be selected
+/// without building and sorting every value.
+struct SortedRows<F: Fn(usize, usize) -> f64> {
+    lens: Vec<usize>,
+    value: F,
+}
+
+impl<F: Fn(usize, usize) -> f64> SortedRows<F> {
+    /// Count of values in all rows.
+    fn count(&self) -> usize {
+        self.lens.iter().sum()
+    }
+
+    /// Count of values at the start of row i for which `before` is true.
+    fn partition_point(&self, i: usize, before: impl Fn(f64) -> bool) -> usize {
+        let (mut low, mut high) = (0, self.lens[i]);
+        while low < high {
+            let middle = (low + high) / 2;
+            if before((self.value)(i, middle)) { low = middle + 1; } else { high = middle; }
+        }
+        low
+    }
+
+    /// Value of rank k, from 1, by Monahan's selection: split the candidates
+    /// at the weighted median of the row medians, which discards at least a
+    /// quarter of them, until few enough remain to sort.
+    ///
+    /// Use O(r log c) time per split, for r rows of at most c values.
+    fn select(&self, k: usize) -> f64 {
+        let rows = self.lens.len();
+        // Candidates in row i are at lower[i]..upper[i].
+        let mut lower = vec![0; rows];
+        let mut upper = self.lens.clone();
+        let mut remaining = self.count();
+        while remaining > rows.max(16) {
+            let mut medians: Vec<(f64, usize)> = (0..rows)
+                .filter(|i| upper[*i] > lower[*i])
+                .map(|i| ((self.value)(i, (lower[i] + upper[i] - 1) / 2), upper[i] - lower[i]))
+                .collect();
+            medians.sort_by(|a, b| a.0.total_cmp(&b.0));
+            let mut weight = 0;
+            let pivot = medians.iter().find(|(_, w)| { weight += w; 2 * weight >= remaining }).unwrap().0;
+            let less: Vec<usize> = (0..rows).map(|i| self.partition_point(i, |v| v < pivot)).collect();
+            let less_or_equal: Vec<usize> = (0..rows).map(|i| self.partition_point(i, |v| v <= pivot)).collect();
+            if k <= less.iter().sum() {
+                upper.iter_mut().zip(&less).for_each(|(u, l)| *u = (*u).min(*l));
+            } else if k <= less_or_equal.iter().sum() {
+                return pivot;
+            } else {
+                lower.iter_mut().zip(&less_or_equal).for_each(|(l, e)| *l = (*l).max(*e));
+            }
+            let next = lower.iter().zip(&upper).map(|(l, u)| u.saturating_sub(*l)).sum();
+            // Stop if a split discards nothing, which only happens for rows with NaN.
+            if next == remaining { break; }
+            remaining = next;
+        }
+        let mut candidates: Vec<f64> = (0..rows)
+            .flat_map(|i| (lower[i]..upper[i].max(lower[i])).map(move |j| (i, j)))
+            .map(|(i, j)| (self.value)(i, j))
+            .collect();
+        candidates.sort_by(f64::total_cmp);
+        candidates[k - 1 - lower.iter().sum::<usize>()]
+    }
+
+    /// Median of the values, or NaN if there are no values.
+    fn median(&self) -> f64 {
+        let count = self.count();
+        if count == 0 { return f64::NAN; }
+        let (low_rank, high_rank) = (count.div_ceil(2), count / 2 + 1);
+        let low = self.select(low_rank);
+        let high = if high_rank == low_rank { low } else { self.select(high_rank) };
+        low + 0.5 * (high - low)
+    }
+}
+
+/// Differences x - y of every pair, in rows of ascending x by descending y.
+fn pairwise_differences(xs: &[f64], ys: &[f64]) -> SortedRows<impl Fn(usize, usize) -> f64> {
+    let mut xs = xs.to_vec();
+    xs.sort_by(f64::total_cmp);
+    let mut ys = ys.to_vec();
+    ys.sort_by(|a, b| b.total_cmp(a));
+    SortedRows { lens: vec![ys.len(); xs.len()], value: move |i, j| xs[i] - ys[j] }
+}
+
+/// Walsh averages (d_i + d_j) / 2 for i <= j, in rows of ascending d_i by ascending d_j.
+fn walsh_averages(values: &[f64]) -> SortedRows<impl Fn(usize, usize) -> f64> {
+    let mut values = values.to_vec();
+    values.sort_by(f64::total_cmp);
+    SortedRows { lens: (0..values.len()).map(|i| values.len() - i).collect(), value: move |i, j| (values[i] + values[i + j]) / 2.0 }
+}
+
+/// Calculate the Mann–Whitney U test, also called the Wilcoxon rank-sum
+/// test, of whether values of xs tend to be greater or less than values of ys.
+///
+/// The statistic is U, the count of pairs with x > y, plus half the count
+/// of pairs with x = y. The p-value is exact if both sides have fewer than
+/// 50 values and there are no ties; otherwise it uses the normal
+/// approximation, with continuity correction and tie correction.
+///
+/// The estimate is the Hodges–Lehmann shift, which is the median of the
+/// pairwise differences x - y, with its confidence interval. The effect
+/// size is the rank-biserial correlation, 2U / (n_x n_y) - 1, from -1.0 to 1.0.
+///
+/// Select the estimate and the bounds without building the n_x n_y
+/// pairwise differences, in O(N log² N) time for N values.
+///
+/// Return NaN statistics if either side has no values.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::{hypothesis::*, nonparametric::*};
+/// let result = mann_whitney_u([1.1, 2.2, 3.3, 4.4], [0.5, 0.9, 1.0, 1.2], Alternative::TwoSided, 0.95);
+/// assert_eq!(result.statistic, 15.0);
+/// assert!((result.p_value - 4.0 / 70.0).abs() < 1e-15);
+/// ```
+///
+pub fn mann_whitney_u<X: AsRef<[f64]>, Y: AsRef<[f64]>>(xs: X, ys: Y, alternative: Alternative, confidence: f64) -> TestResult {
+    let (xs, ys) = (without_nan(xs.as_ref()), without_nan(ys.as_ref()));
+    let (m, n) = (xs.len(), ys.len());
+    if m == 0 || n == 0 { return TestResult::nan(); }
+    let all: Vec<f64> = xs.iter().chain(&ys).copied().collect();
+    let ranks = ranks(&all);
+    let (mf, nf) = (m as f64, n as f64);
+    let u = ranks[..m].iter().sum::<f64>() - mf * (mf + 1.0) / 2.0;
+    let ties = tie_correction(&all);
+    let mean = mf * nf / 2.0;
+    let total = mf + nf;
+    let standard_deviation = (mf * nf / 12.0 * ((total + 1.0) - ties / (total * (total - 1.0)))).sqrt();
+    let exact = (m < EXACT_MAX && n < EXACT_MAX && ties == 0.0).then(|| Counts::mann_whitney(m, n));
+    let p_value = match &exact {
+        Some(counts) => counts.p_value(u, alternative),
+        None => normal_p_value(u, mean, standard_deviation, alternative),
+    };
+    let differences = pairwise_differences(&xs, &ys);
+    let (confidence_lower, confidence_upper) =
+        confidence_interval(&differences, exact.as_ref(), mean, standard_deviation, alternative, confidence);
+    TestResult {
+        statistic: u,
+        degrees_of_freedom: f64::NAN,
+        p_value,
+        estimate: differences.median(),
+        confidence_lower,
+        confidence_upper,
+        effect_size: 2.0 * u / (mf * nf) - 1.0,
+    }
+}
+
+/// Calculate the Hodges–Lehmann estimate of the shift between xs and ys,
+/// which is the median of the pairwise differences x - y, with its two-sided
+/// confidence interval, which is the same as for `mann_whitney_u`.
+///
+/// The result has the estimate and the confidence bounds, and its other
+/// fields are NaN.
+///
+/// Return NaN statistics if either side has no values.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::nonparametric::*;
+/// let result = hodges_lehmann([10.0, 11.0, 12.0, 13.0, 100.0], [1.0, 2.0, 3.0, 4.0], 0.9);
+/// assert_eq!(result.estimate, 9.5);
+/// assert!(result.confidence_lower < 9.5 && 9.5 < result.confidence_upper);
+/// ```
+///
+pub fn hodges_lehmann<X: AsRef<[f64]>, Y: AsRef<[f64]>>(xs: X, ys: Y, confidence: f64) -> TestResult {
+    let result = mann_whitney_u(xs, ys, Alternative::TwoSided, confidence);
+    TestResult {
+        estimate: result.estimate,
+        confidence_lower: result.confidence_lower,
+        confidence_upper: result.confidence_upper,
+        ..TestResult::nan()
+    }
+}
+
+/// Calculate the Wilcoxon signed-rank test of whether values are
+/// symmetric about mu, such as whether their median is mu.
+///
+/// Drop values equal to mu, then rank the absolute differences from mu.
+/// The statistic is V, the sum of the ranks of the positive differences.
+/// The p-value is exact if there are fewer than 50 values and there are no
+/// ties and no values equal to mu; otherwise it uses the normal
+/// approximation, with continuity correction and tie correction.
+///
+/// The estimate is the Hodges–Lehmann pseudo-median, which is the median
+/// of the Walsh averages of the nonzero differences, with its confidence
+/// interval. The effect size is the matched-pairs rank-biserial
+/// correlation, from -1.0 to 1.0.
+///
+/// Select the estimate and the bounds without building the n² Walsh
+/// averages, in O(n log² n) time.
+///
+/// Return NaN statistics if no values differ from mu.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::{hypothesis::*, nonparametric::*};
+/// let result = wilcoxon_signed_rank([1.5, 2.5, 3.5, 4.5, 5.5], 0.0, Alternative::Greater, 0.95);
+/// assert_eq!(result.statistic, 15.0);
+/// assert!((result.p_value - 1.0 / 32.0).abs() < 1e-15);
+/// ```
+///
+pub fn wilcoxon_signed_rank<T: AsRef<[f64]>>(values: T, mu: f64, alternative: Alternative, confidence: f64) -> TestResult {
+    let values = without_nan(values.as_ref());
+    let differences: Vec<f64> = values.iter().map(|v| v - mu).collect();
+    let nonzero: Vec<f64> = differences.iter().copied().filter(|d| *d != 0.0).collect();
+    let n = nonzero.len();
+    if n == 0 { return TestResult::nan(); }
+    let magnitudes: Vec<f64> = nonzero.iter().map(|d| d.abs()).collect();
+    let ranks = ranks(&magnitudes);
+    let v: f64 = nonzero.iter().zip(&ranks).filter(|(d, _)| **d > 0.0).map(|(_, r)| r).sum();
+    let nf = n as f64;
+    let rank_sum = nf * (nf + 1.0) / 2.0;
+    let ties = tie_correction(&magnitudes);
+    let mean = rank_sum / 2.0;
+    let standard_deviation = (nf * (nf + 1.0) * (2.0 * nf + 1.0) / 24.0 - ties / 48.0).sqrt();
+    let exact = (n < EXACT_MAX && ties == 0.0 && n == differences.len()).then(|| Counts::signed_rank(n));
+    let p_value = match &exact {
+        Some(counts) => counts.p_value(v, alternative),
+        None => normal_p_value(v, mean, standard_deviation, alternative),
+    };
+    // The Walsh averages drop zero differences, like the ranks.
+    let averages = walsh_averages(&nonzero);
+    let (lower, upper) = confidence_interval(&averages, exact.as_ref(), mean, standard_deviation, alternative, confidence);
+    TestResult {
+        statistic: v,
+        degrees_of_freedom: f64::NAN,
+        p_value,
+        estimate: averages.median() + mu,
+        confidence_lower: lower + mu,
+        confidence_upper: upper + mu,
+        effect_size: (2.0 * v - rank_sum) / rank_sum,
+    }
+}
+
+/// Calculate the paired Wilcoxon signed-rank test of whether the
+/// differences xs - ys are symmetric about 0, such as for the same hosts
+/// before and after a change.
+///
+/// This is `wilcoxon_signed_rank` of the differences, with mu = 0.
+///
+/// Return NaN statistics if xs and ys have different lengths, or if no pairs differ.
+///
+/// Filter pairs with a NaN value in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::{hypothesis::*, nonparametric::*};
+/// let xs = [1.83, 0.50, 1.62, 2.48, 1.68, 1.88, 1.55, 3.06, 1.30];
+/// let ys = [0.878, 0.647, 0.598, 2.05, 1.06, 1.29, 1.06, 3.14, 1.29];
+/// let result = paired_wilcoxon_signed_rank(xs, ys, Alternative::Greater, 0.95);
+/// assert_eq!(result.statistic, 40.0);
+/// assert!((result.p_value - 10.0 / 512.0).abs() < 1e-15);
+/// ```
+///
+pub fn paired_wilcoxon_signed_rank<X: AsRef<[f64]>, Y: AsRef<[f64]>>(xs: X, ys: Y, alternative: Alternative, confidence: f64) -> TestResult {
+    let (xs, ys) = (xs.as_ref(), ys.as_ref());
+    if xs.len() != ys.len() { return TestResult::nan(); }
+    let differences: Vec<f64> = xs.iter().zip(ys).map(|(x, y)| x - y).collect();
+    wilcoxon_signed_rank(differences, 0.0, alternative, confidence)
+}
+
+/// Sum over groups of (rank sum)² / size, from an assignment of ranks to groups.
+fn kruskal_wallis_sum(rank_sums: &[f64], sizes: &[usize]) -> f64 {
+    rank_sums.iter().zip(sizes).map(|(r, n)| r * r / *n as f64).sum()
+}
+
+/// Count the assignments of ranks to groups of the sizes, with the
+/// count that have a Kruskal–Wallis sum at least the observed sum.
+fn kruskal_wallis_enumerate(
+    ranks: &[f64],
+    sizes: &[usize],
+    remaining: &mut [usize],
+    rank_sums: &mut [f64],
+    observed: f64,
+    counts: &mut (f64, f64),
+) {
+    let Some((rank, rest)) = ranks.split_first() else {
+        counts.0 += 1.0;
+        // Tolerate rounding, since equal sums may be calculated in a different order.
+        if kruskal_wallis_sum(rank_sums, sizes) >= observed * (1.0 - 1e-12) { counts.1 += 1.0; }
+        return;
+    };
+    for group in 0..sizes.len() {
+        if remaining[group] == 0 { continue; }
+        remaining[group] -= 1;
+        rank_sums[group] += rank;
+        kruskal_wallis_enumerate(rest, sizes, remaining, rank_sums, observed, counts);
+        rank_sums[group] -= rank;
+        remaining[group] += 1;
+    }
+}
+
+/// Calculate the Kruskal–Wallis H test of whether the groups come from the
+/// same distribution, which is the rank analog of one-way ANOVA.
+///
+/// The statistic is H, corrected for ties, with k - 1 degrees of freedom
+/// for k groups. The p-value is exact, by enumerating every assignment of
+/// the ranks to the groups, if there are at most 100,000 assignments;
+/// otherwise it uses the chi-squared approximation.
+///
+/// The effect size is epsilon squared, H / (N - 1), for N values in all.
+/// The estimate and the confidence bounds are NaN.
+///
+/// Skip groups without values. Return NaN statistics if there are fewer
+/// than 2 groups with values, or if every value is equal.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::nonparametric::*;
+/// let groups = [
+///     vec![2.9, 3.0, 2.5, 2.6, 3.2],
+///     vec![3.8, 2.7, 4.0, 2.4],
+///     vec![2.8, 3.4, 3.7, 2.2, 2.0],
+/// ];
+/// let result = kruskal_wallis(&groups);
+/// assert!((result.statistic - 0.7714285714285714).abs() < 1e-12);
+/// assert_eq!(result.degrees_of_freedom, 2.0);
+/// ```
+///
+pub fn kruskal_wallis<G: AsRef<[f64]>>(groups: &[G]) -> TestResult {
+    let groups: Vec<Vec<f64>> = groups.iter().map(|g| without_nan(g.as_ref())).filter(|g| !g.is_empty()).collect();
+    let k = groups.len();
+    if k < 2 { return TestResult::nan(); }
+    let sizes: Vec<usize> = groups.iter().map(Vec::len).collect();
+    let all: Vec<f64> = groups.concat();
+    let ranks = ranks(&all);
+    let nf = all.len() as f64;
+    let ties = tie_correction(&all);
+    let correction = 1.0 - ties / (nf * nf * nf - nf);
+    if correction == 0.0 { return TestResult::nan(); }
+    let mut rank_sums = Vec::with_capacity(k);
+    let mut start = 0;
+    for size in &sizes {
+        rank_sums.push(ranks[start..start + size].iter().sum::<f64>());
+        start += size;
+    }
+    let observed = kruskal_wallis_sum(&rank_sums, &sizes);
+    let h = (12.0 / (nf * (nf + 1.0)) * observed - 3.0 * (nf + 1.0)) / correction;
+    let df = (k - 1) as f64;
+    // Count of assignments is the multinomial coefficient N! / Π n_i!.
+    let mut arrangements = 1.0;
+    let mut placed = 0.0;
+    for size in &sizes {
+        for i in 1..=*size {
+            placed += 1.0;
+            arrangements *= placed / i as f64;
+        }
+    }
+    let p_value = if arrangements <= KRUSKAL_WALLIS_EXACT_MAX {
+        let mut counts = (0.0, 0.0);
+        let mut remaining = sizes.clone();
+        let mut sums = vec![0.0; k];
+        kruskal_wallis_enumerate(&ranks, &sizes, &mut remaining, &mut sums, observed, &mut counts);
+        counts.1 / counts.0
+    } else {
+        ChiSquared::new(df).sf(h)
+    };
+    TestResult {
+        statistic: h,
+        degrees_of_freedom: df,
+        p_value,
+        effect_size: h / (nf - 1.0),
+        ..TestResult::nan()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::f64::assert_eq_f64::assert_close;
+
+    #[test]
+    fn test_empty() {
+        let x: &[f64] = &[];
+        assert!(mann_whitney_u(x, [1.0], Alternative::TwoSided, 0.95).p_value.is_nan());
+        assert!(wilcoxon_signed_rank([2.0, 2.0], 2.0, Alternative::TwoSided, 0.95).p_value.is_nan());
+        assert!(kruskal_wallis(&[vec![1.0, 2.0], vec![]]).p_value.is_nan());
+    }
+
+    #[test]
+    fn test_counts() {
+        // U for sizes 2 and 2 has counts 1, 1, 2, 1, 1.
+        assert_eq!(Counts::mann_whitney(2, 2).0, vec![1.0, 1.0, 2.0, 1.0, 1.0]);
+        // V for 3 values has counts 1, 1, 1, 2, 1, 1, 1.
+        assert_eq!(Counts::signed_rank(3).0, vec![1.0, 1.0, 1.0, 2.0, 1.0, 1.0, 1.0]);
+        let total: f64 = Counts::mann_whitney(10, 7).0.iter().sum();
+        assert_eq!(total, 19448.0);
+    }
+
+    #[test]
+    fn test_mann_whitney_exact() {
+        let xs = [0.80, 0.83, 1.89, 1.04, 1.45, 1.38, 1.91, 1.64, 0.73, 1.46];
+        let ys = [1.15, 0.88, 0.90, 0.74, 1.21];
+        let result = mann_whitney_u(xs, ys, Alternative::Greater, 0.95);
+        assert_eq!(result.statistic, 35.0);
+        assert_close!(result.p_value, 0.1272061272061272, 1e-12);
+        assert_close!(result.effect_size, 0.4, 1e-12);
+        assert_close!(result.estimate, 0.305, 1e-12);
+        assert_close!(result.confidence_lower, -0.08, 1e-12);
+        assert_eq!(result.confidence_upper, f64::INFINITY);
+    }
+
+    #[test]
+    fn test_mann_whitney_separated() {
+        // Every x is less: 1 of the C(60, 30) arrangements, in either tail.
+        let xs: Vec<f64> = (0..30).map(|i| i as f64).collect();
+        let ys: Vec<f64> = (30..60).map(|i| i as f64).collect();
+        let less = mann_whitney_u(&xs, &ys, Alternative::Less, 0.95);
+        assert_close!(less.p_value, 8.455616946072368e-18, relative = 1e-12);
+        let greater = mann_whitney_u(&ys, &xs, Alternative::Greater, 0.95);
+        assert_close!(greater.p_value, less.p_value, relative = 1e-12);
+        let two_sided = mann_whitney_u(&ys, &xs, Alternative::TwoSided, 0.95);
+        assert_close!(two_sided.p_value, 2.0 * less.p_value, relative = 1e-12);
+    }
+
+    #[test]
+    fn test_mann_whitney_ties() {
+        let xs = [1.0, 2.0, 2.0, 3.0, 4.0, 4.0, 5.0];
+        let ys = [2.0, 3.0, 3.0, 5.0, 6.0, 7.0, 7.0, 8.0];
+        let result = mann_whitney_u(xs, ys, Alternative::TwoSided, 0.95);
+        assert_eq!(result.statistic, 12.5);
+        assert_close!(result.p_value, 0.07956473459319045, 1e-12);
+        assert_eq!(result.estimate, -2.0);
+        assert_eq!(result.confidence_lower, -5.0);
+        assert_eq!(result.confidence_upper, 0.0);
+    }
+
+    #[test]
+    fn test_select() {
+        // Values with ties, so many pairwise differences are equal.
+        let xs: Vec<f64> = (0..37).map(|i| ((i * 13) % 11) as f64 * 0.5).collect();
+        let ys: Vec<f64> = (0..29).map(|i| ((i * 7) % 9) as f64).collect();
+        let mut naive = Vec::new();
+        xs.iter().for_each(|x| naive.extend(ys.iter().map(|y| x - y)));
+        naive.sort_by(f64::total_cmp);
+        let differences = pairwise_differences(&xs, &ys);
+        for (k, expected) in naive.iter().enumerate() {
+            assert_eq!(differences.select(k + 1), *expected);
+        }
+        let mut naive = Vec::new();
+        (0..xs.len()).for_each(|i| naive.extend(xs[i..].iter().map(|x| (xs[i] + x) / 2.0)));
+        naive.sort_by(f64::total_cmp);
+        let averages = walsh_averages(&xs);
+        for (k, expected) in naive.iter().enumerate() {
+            assert_eq!(averages.select(k + 1), *expected);
+        }
+        // 10⁸ pairwise differences, without building them.
+        let xs: Vec<f64> = (0..10_000).map(|i| (i as f64).sqrt()).collect();
+        let result = mann_whitney_u(&xs, &xs, Alternative::TwoSided, 0.95);
+        assert_eq!(result.estimate, 0.0);
+        assert!(result.confidence_lower < 0.0 && result.confidence_upper > 0.0);
+    }
+
+    #[test]
+    fn test_statistic_at_mean() {
+        // No continuity correction when the statistic equals its mean.
+        let xs = [1.0, 2.0, 2.0, 3.0];
+        assert_eq!(mann_whitney_u(xs, xs, Alternative::TwoSided, 0.95).p_value, 1.0);
+        assert_eq!(wilcoxon_signed_rank([-1.0, 1.0, -2.0, 2.0, 0.0], 0.0, Alternative::TwoSided, 0.95).p_value, 1.0);
+    }
+
+    #[test]
+    fn test_wilcoxon() {
+        let result = wilcoxon_signed_rank([1.0, -2.0, 3.0, 4.0, 5.0, 6.0, -0.5], 0.0, Alternative::TwoSided, 0.9);
+        assert_eq!(result.statistic, 24.0);
+        assert_close!(result.p_value, 0.109375, 1e-12);
+        assert_close!(result.effect_size, 5.0 / 7.0, 1e-12);
+        assert_eq!(result.estimate, 2.375);
+        assert_eq!(result.confidence_lower, -0.5);
+        assert_eq!(result.confidence_upper, 5.0);
+        // With a zero, use the normal approximation.
+        let zero = wilcoxon_signed_rank([1.0, -2.0, 3.0, 4.0, 5.0, 6.0, -0.5, 0.0], 0.0, Alternative::TwoSided, 0.9);
+        assert_close!(zero.p_value, 0.10831938073000424, 1e-12);
+        assert_eq!(zero.estimate, 2.375);
+    }
+
+    #[test]
+    fn test_kruskal_wallis() {
+        let groups = [
+            vec![2.9, 3.0, 2.5, 2.6, 3.2],
+            vec![3.8, 2.7, 4.0, 2.4],
+            vec![2.8, 3.4, 3.7, 2.2, 2.0],
+        ];
+        let result = kruskal_wallis(&groups);
+        assert_close!(result.statistic, 0.7714285714285714, 1e-12);
+        assert_close!(result.p_value, 0.6799647735788936, 1e-12);
+        let small = kruskal_wallis(&[vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
+        // The most extreme of 20 assignments, on either side.
+        assert_close!(small.p_value, 0.1, 1e-12);
+    }
+
+}
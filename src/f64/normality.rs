@@ -0,0 +1,322 @@
+//! Normality tests, of whether values fit a normal distribution with any
+//! mean and standard deviation: Shapiro–Wilk, D'Agostino–Pearson K², and
+//! Jarque–Bera.
+//!
+//! Shapiro–Wilk has the most power for small samples. D'Agostino–Pearson
+//! and Jarque–Bera combine skewness and kurtosis, so the statistic shows
+//! the shape of any departure; Jarque–Bera needs large samples, since its
+//! p-value is asymptotic.
+//!
+//! # Example
+//!
+//! ```rust
+//! use numeric_statistics::f64::normality::*;
+//!
+//! // Latency is skewed, so not normal.
+//! let latency = [12.0, 11.0, 13.0, 12.5, 11.5, 250.0, 12.2, 14.0, 15.5, 13.8, 14.9, 900.0];
+//! let result = shapiro_wilk(latency);
+//! assert!(result.rejects(0.05));
+//! ```
+
+use super::{
+    distribution::{ChiSquared, Distribution, Normal},
+    hypothesis::*,
+    kurtosis::kurtosis_population,
+    quantile::sorted,
+    skewness::skewness_population,
+};
+
+/// Max sample size for the Shapiro–Wilk test.
+const SHAPIRO_WILK_MAX: usize = 5000;
+
+/// Evaluate the polynomial with coefficients from the constant term up.
+fn polynomial(coefficients: &[f64], x: f64) -> f64 {
+    coefficients.iter().rev().fold(0.0, |sum, c| sum * x + c)
+}
+
+/// Count of values that are not NaN.
+fn count(values: &[f64]) -> usize {
+    values.iter().filter(|x| !x.is_nan()).count()
+}
+
+/// Calculate the Shapiro–Wilk test of normality, by Royston's algorithm
+/// AS R94 (1995), which approximates the coefficients and the p-value for
+/// 3 to 5000 values, like R's `shapiro.test`.
+///
+/// The statistic is W, which is near 1.0 for normal values. The other
+/// fields are NaN.
+///
+/// Return NaN statistics if there are fewer than 3 values or more than
+/// 5000, or if the values are all equal.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::normality::*;
+/// let weights = [148.0, 154.0, 158.0, 160.0, 161.0, 162.0, 166.0, 170.0, 182.0, 195.0, 236.0];
+/// let result = shapiro_wilk(weights);
+/// assert!((result.statistic - 0.78881).abs() < 1e-5);
+/// assert!((result.p_value - 0.006704).abs() < 1e-6);
+/// ```
+///
+pub fn shapiro_wilk<T: AsRef<[f64]>>(values: T) -> TestResult {
+    const C1: [f64; 6] = [0.0, 0.221157, -0.147981, -2.07119, 4.434685, -2.706056];
+    const C2: [f64; 6] = [0.0, 0.042981, -0.293762, -1.752461, 5.682633, -3.582633];
+    const C3: [f64; 4] = [0.544, -0.39978, 0.025054, -6.714e-4];
+    const C4: [f64; 4] = [1.3822, -0.77857, 0.062767, -0.0020322];
+    const C5: [f64; 4] = [-1.5861, -0.31082, -0.083751, 0.0038915];
+    const C6: [f64; 3] = [-0.4803, -0.082676, 0.0030302];
+    const G: [f64; 2] = [-2.273, 0.459];
+
+    let sorted = sorted(values);
+    let n = sorted.len();
+    if !(3..=SHAPIRO_WILK_MAX).contains(&n) || sorted[0] == sorted[n - 1] { return TestResult::nan(); }
+    let nf = n as f64;
+    let half = n / 2;
+    let normal = Normal::standard();
+
+    // Coefficients a for the lower half, which are antisymmetric, so the
+    // upper half is -a reversed.
+    let mut a = vec![0.0; half];
+    if n == 3 {
+        a[0] = 0.5f64.sqrt();
+    } else {
+        let m: Vec<f64> = (1..=half).map(|i| normal.quantile((i as f64 - 0.375) / (nf + 0.25))).collect();
+        let sum_m2 = 2.0 * m.iter().map(|x| x * x).sum::<f64>();
+        let root_sum_m2 = sum_m2.sqrt();
+        let rsn = 1.0 / nf.sqrt();
+        let a1 = polynomial(&C1, rsn) - m[0] / root_sum_m2;
+        let (first, scale) = if n > 5 {
+            let a2 = polynomial(&C2, rsn) - m[1] / root_sum_m2;
+            a[1] = a2;
+            let scale = ((sum_m2 - 2.0 * m[0] * m[0] - 2.0 * m[1] * m[1]) / (1.0 - 2.0 * a1 * a1 - 2.0 * a2 * a2)).sqrt();
+            (2, scale)
+        } else {
+            (1, ((sum_m2 - 2.0 * m[0] * m[0]) / (1.0 - 2.0 * a1 * a1)).sqrt())
+        };
+        a[0] = a1;
+        for i in first..half { a[i] = -m[i] / scale; }
+    }
+
+    // W is the squared correlation of the values with the coefficients,
+    // which have Σ a² = 1 and Σ a = 0.
+    let average = sorted.iter().sum::<f64>() / nf;
+    let sum_squares: f64 = sorted.iter().map(|x| (x - average).powi(2)).sum();
+    let numerator: f64 = (0..half).map(|i| a[i] * (sorted[n - 1 - i] - sorted[i])).sum();
+    let w = (numerator * numerator / sum_squares).min(1.0);
+
+    let p_value = if n == 3 {
+        // Exact: p = 6/π (asin √W - π/3)
+        (6.0 / std::f64::consts::PI * (w.sqrt().asin() - std::f64::consts::PI / 3.0)).max(0.0)
+    } else {
+        // Normalize ln(1 - W) to a standard normal, for which small W is the upper tail.
+        let mut y = (1.0 - w).ln();
+        let (mean, sd) = if n <= 11 {
+            let gamma = polynomial(&G, nf);
+            if y >= gamma { return TestResult { statistic: w, p_value: 0.0, ..TestResult::nan() }; }
+            y = -(gamma - y).ln();
+            (polynomial(&C3, nf), polynomial(&C4, nf).exp())
+        } else {
+            let ln_n = nf.ln();
+            (polynomial(&C5, ln_n), polynomial(&C6, ln_n).exp())
+        };
+        normal.sf((y - mean) / sd)
+    };
+    TestResult {
+        statistic: w,
+        p_value,
+        ..TestResult::nan()
+    }
+}
+
+/// Calculate the D'Agostino–Pearson K² test of normality, which combines
+/// the skewness and kurtosis, each transformed to a standard normal Z, like
+/// SciPy's `normaltest`.
+///
+/// The statistic is K² = Z_skewness² + Z_kurtosis², with a chi-squared
+/// distribution with 2 degrees of freedom. The kurtosis transform is
+/// accurate for 20 or more values.
+///
+/// The other fields are NaN.
+///
+/// Return NaN statistics if there are fewer than 8 values, or if the values
+/// are all equal.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::normality::*;
+/// let values = [
+///     0.61, 0.29, 0.06, 0.59, -1.73, -0.74, 0.51, -0.56, 0.39, 1.64,
+///     0.05, -0.06, 0.64, -0.82, 0.37, 1.77, 1.09, -1.28, 0.33, -0.51,
+/// ];
+/// let result = dagostino_pearson(values);
+/// assert!(!result.rejects(0.05));
+/// ```
+///
+pub fn dagostino_pearson<T: AsRef<[f64]>>(values: T) -> TestResult {
+    let values = values.as_ref();
+    let n = count(values) as f64;
+    if n < 8.0 { return TestResult::nan(); }
+    let skewness = skewness_population(values);
+    let kurtosis = kurtosis_population(values) + 3.0;
+    if skewness.is_nan() || kurtosis.is_nan() { return TestResult::nan(); }
+
+    // Skewness transform, by D'Agostino (1970).
+    let y = skewness * ((n + 1.0) * (n + 3.0) / (6.0 * (n - 2.0))).sqrt();
+    let beta2 = 3.0 * (n * n + 27.0 * n - 70.0) * (n + 1.0) * (n + 3.0)
+        / ((n - 2.0) * (n + 5.0) * (n + 7.0) * (n + 9.0));
+    let w2 = -1.0 + (2.0 * (beta2 - 1.0)).sqrt();
+    let delta = 1.0 / (0.5 * w2.ln()).sqrt();
+    let alpha = (2.0 / (w2 - 1.0)).sqrt();
+    let z_skewness = delta * (y / alpha).asinh();
+
+    // Kurtosis transform, by Anscombe and Glynn (1983).
+    let expected = 3.0 * (n - 1.0) / (n + 1.0);
+    let variance = 24.0 * n * (n - 2.0) * (n - 3.0) / ((n + 1.0) * (n + 1.0) * (n + 3.0) * (n + 5.0));
+    let x = (kurtosis - expected) / variance.sqrt();
+    let root_beta1 = 6.0 * (n * n - 5.0 * n + 2.0) / ((n + 7.0) * (n + 9.0))
+        * (6.0 * (n + 3.0) * (n + 5.0) / (n * (n - 2.0) * (n - 3.0))).sqrt();
+    let a = 6.0 + 8.0 / root_beta1 * (2.0 / root_beta1 + (1.0 + 4.0 / (root_beta1 * root_beta1)).sqrt());
+    let denominator = 1.0 + x * (2.0 / (a - 4.0)).sqrt();
+    let cube = ((1.0 - 2.0 / a) / denominator.abs()).cbrt().copysign(denominator);
+    let z_kurtosis = (1.0 - 2.0 / (9.0 * a) - cube) / (2.0 / (9.0 * a)).sqrt();
+
+    let k2 = z_skewness * z_skewness + z_kurtosis * z_kurtosis;
+    TestResult {
+        statistic: k2,
+        degrees_of_freedom: 2.0,
+        p_value: ChiSquared::new(2.0).sf(k2),
+        ..TestResult::nan()
+    }
+}
+
+/// Calculate the Jarque–Bera test of normality, from the population
+/// skewness S and excess kurtosis K.
+///
+/// The statistic is JB = n/6 (S² + K²/4), with an asymptotic chi-squared
+/// distribution with 2 degrees of freedom, which is accurate for large
+/// samples, such as 2000 or more values; for small samples the p-value is
+/// too low.
+///
+/// The other fields are NaN.
+///
+/// Return NaN statistics if there are fewer than 2 values, or if the values
+/// are all equal.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::normality::*;
+/// let values = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0];
+/// let result = jarque_bera(values);
+/// // S = 0, K = -1.2242...
+/// assert!((result.statistic - 0.6244872972145701).abs() < 1e-12);
+/// ```
+///
+pub fn jarque_bera<T: AsRef<[f64]>>(values: T) -> TestResult {
+    let values = values.as_ref();
+    let n = count(values) as f64;
+    if n < 2.0 { return TestResult::nan(); }
+    let skewness = skewness_population(values);
+    let kurtosis = kurtosis_population(values);
+    if skewness.is_nan() || kurtosis.is_nan() { return TestResult::nan(); }
+    let jb = n / 6.0 * (skewness * skewness + kurtosis * kurtosis / 4.0);
+    TestResult {
+        statistic: jb,
+        degrees_of_freedom: 2.0,
+        p_value: ChiSquared::new(2.0).sf(jb),
+        ..TestResult::nan()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::f64::assert_eq_f64::assert_close;
+
+    #[test]
+    fn test_empty() {
+        let x: &[f64] = &[];
+        assert!(shapiro_wilk(x).p_value.is_nan());
+        assert!(dagostino_pearson(x).p_value.is_nan());
+        assert!(jarque_bera(x).p_value.is_nan());
+    }
+
+    #[test]
+    fn test_values_equal() {
+        let x = [2.0; 10];
+        assert!(shapiro_wilk(x).p_value.is_nan());
+        assert!(dagostino_pearson(x).p_value.is_nan());
+        assert!(jarque_bera(x).p_value.is_nan());
+    }
+
+    #[test]
+    fn test_shapiro_wilk() {
+        // Exact for 3 values: evenly spaced values have W = 1.
+        let result = shapiro_wilk([1.0, 2.0, f64::NAN, 3.0]);
+        assert_close!(result.statistic, 1.0, 1e-15);
+        assert_close!(result.p_value, 1.0, 1e-15);
+        // The least W for 3 values is 3/4, with p = 0.
+        let result = shapiro_wilk([1.0, 1.0, 2.0]);
+        assert_close!(result.statistic, 0.75, 1e-15);
+        assert_close!(result.p_value, 0.0, 1e-7);
+        // R's shapiro.test, which prints W to 5 and p to 4 significant figures,
+        // on its datasets women$weight, mtcars$mpg, cars$dist, and iris$Sepal.Length.
+        let women = [115.0, 117.0, 120.0, 123.0, 126.0, 129.0, 132.0, 135.0, 139.0, 142.0, 146.0, 150.0, 154.0, 159.0, 164.0];
+        let result = shapiro_wilk(women);
+        assert_close!(result.statistic, 0.96036, 5e-6);
+        assert_close!(result.p_value, 0.6986, relative = 1e-4);
+        let mpg = [
+            21.0, 21.0, 22.8, 21.4, 18.7, 18.1, 14.3, 24.4, 22.8, 19.2, 17.8, 16.4, 17.3, 15.2, 10.4, 10.4,
+            14.7, 32.4, 30.4, 33.9, 21.5, 15.5, 15.2, 13.3, 19.2, 27.3, 26.0, 30.4, 15.8, 19.7, 15.0, 21.4,
+        ];
+        let result = shapiro_wilk(mpg);
+        assert_close!(result.statistic, 0.94756, 5e-6);
+        assert_close!(result.p_value, 0.1229, relative = 1e-3);
+        let dist = [
+            2.0, 10.0, 4.0, 22.0, 16.0, 10.0, 18.0, 26.0, 34.0, 17.0, 28.0, 14.0, 20.0, 24.0, 28.0, 26.0, 34.0,
+            34.0, 46.0, 26.0, 36.0, 60.0, 80.0, 20.0, 26.0, 54.0, 32.0, 40.0, 32.0, 40.0, 50.0, 42.0, 56.0, 76.0,
+            84.0, 36.0, 46.0, 68.0, 32.0, 48.0, 52.0, 56.0, 64.0, 66.0, 54.0, 70.0, 92.0, 93.0, 120.0, 85.0,
+        ];
+        let result = shapiro_wilk(dist);
+        assert_close!(result.statistic, 0.95144, 5e-6);
+        assert_close!(result.p_value, 0.0391, relative = 1e-3);
+        let sepal_length = [
+            5.1, 4.9, 4.7, 4.6, 5.0, 5.4, 4.6, 5.0, 4.4, 4.9, 5.4, 4.8, 4.8, 4.3, 5.8, 5.7, 5.4, 5.1, 5.7, 5.1, 5.4, 5.1, 4.6, 5.1, 4.8,
+            5.0, 5.0, 5.2, 5.2, 4.7, 4.8, 5.4, 5.2, 5.5, 4.9, 5.0, 5.5, 4.9, 4.4, 5.1, 5.0, 4.5, 4.4, 5.0, 5.1, 4.8, 5.1, 4.6, 5.3, 5.0,
+            7.0, 6.4, 6.9, 5.5, 6.5, 5.7, 6.3, 4.9, 6.6, 5.2, 5.0, 5.9, 6.0, 6.1, 5.6, 6.7, 5.6, 5.8, 6.2, 5.6, 5.9, 6.1, 6.3, 6.1, 6.4,
+            6.6, 6.8, 6.7, 6.0, 5.7, 5.5, 5.5, 5.8, 6.0, 5.4, 6.0, 6.7, 6.3, 5.6, 5.5, 5.5, 6.1, 5.8, 5.0, 5.6, 5.7, 5.7, 6.2, 5.1, 5.7,
+            6.3, 5.8, 7.1, 6.3, 6.5, 7.6, 4.9, 7.3, 6.7, 7.2, 6.5, 6.4, 6.8, 5.7, 5.8, 6.4, 6.5, 7.7, 7.7, 6.0, 6.9, 5.6, 7.7, 6.3, 6.7,
+            7.2, 6.2, 6.1, 6.4, 7.2, 7.4, 7.9, 6.4, 6.3, 6.1, 7.7, 6.3, 6.4, 6.0, 6.9, 6.7, 6.9, 5.8, 6.8, 6.7, 6.7, 6.3, 6.5, 6.2, 5.9,
+        ];
+        let result = shapiro_wilk(sepal_length);
+        assert_close!(result.statistic, 0.97609, 5e-6);
+        assert_close!(result.p_value, 0.01018, relative = 1e-3);
+        let too_many = vec![1.0; SHAPIRO_WILK_MAX + 1];
+        assert!(shapiro_wilk(too_many).p_value.is_nan());
+    }
+
+    #[test]
+    fn test_dagostino_pearson() {
+        let skewed = [1.0, 1.1, 1.2, 1.3, 1.4, 1.5, 1.6, 1.7, 1.8, 2.0, 2.5, 3.0, 4.0, 6.0, 10.0, 20.0, 50.0, 100.0, 200.0, 500.0];
+        let result = dagostino_pearson(skewed);
+        assert_eq!(result.degrees_of_freedom, 2.0);
+        assert!(result.p_value < 1e-4);
+    }
+
+    #[test]
+    fn test_jarque_bera() {
+        let values = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0];
+        let result = jarque_bera(values);
+        // The chi-squared with 2 degrees of freedom has sf e^(-x/2).
+        assert_close!(result.p_value, (-result.statistic / 2.0).exp(), 1e-15);
+    }
+
+}
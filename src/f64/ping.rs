@@ -0,0 +1,468 @@
+//! Parse `ping` and `traceroute` output into latency samples, such as to
+//! get statistics for round trip times (RTT), jitter, and packet loss.
+//!
+//! The parsers read the text of Linux iputils `ping`, BSD and macOS
+//! `ping`, and `traceroute`, line by line, and skip lines they do not
+//! recognize. Round trip times are in milliseconds.
+//!
+//! # Example
+//!
+//! ```rust
+//! use numeric_statistics::f64::ping::*;
+//!
+//! let text = "\
+//! PING 1.1.1.1 (1.1.1.1) 56(84) bytes of data.
+//! 64 bytes from 1.1.1.1: icmp_seq=1 ttl=57 time=10.0 ms
+//! 64 bytes from 1.1.1.1: icmp_seq=3 ttl=57 time=14.0 ms
+//!
+//! --- 1.1.1.1 ping statistics ---
+//! 3 packets transmitted, 2 received, 33.3333% packet loss, time 2003ms
+//! ";
+//! let ping = parse_ping(text);
+//! assert_eq!(ping.rtts(), vec![10.0, 14.0]);
+//! assert_eq!(ping.all().average, 12.0);
+//! assert_eq!(ping.loss_ratio(), 1.0 / 3.0);
+//! ```
+
+use super::{
+    all::All,
+    display::*,
+    network::*,
+    quantile::*,
+};
+
+/// One reply from `ping`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PingReply {
+    /// ICMP sequence number, if shown, which wraps after 65535.
+    pub seq: Option<u16>,
+    /// Time to live, or IPv6 hop limit, if shown.
+    pub ttl: Option<u32>,
+    /// Round trip time in milliseconds. For Windows `time<1ms`, this is
+    /// the bound 1.0, so is an overestimate.
+    pub rtt: f64,
+    /// True if `ping` marks the reply as a duplicate.
+    pub duplicate: bool,
+}
+
+/// One recognized line of `ping` output.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PingLine {
+    Reply(PingReply),
+    /// A request with no reply, or with an ICMP error reply such as
+    /// "Destination Host Unreachable", with its sequence number if shown.
+    Timeout(Option<u16>),
+    /// The totals of the summary, such as "5 packets transmitted, 4 received".
+    Totals { transmitted: u64, received: u64 },
+}
+
+/// Find the text after a key such as "ttl=", up to whitespace or a comma.
+fn field<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    let start = line.find(key)? + key.len();
+    let rest = &line[start..];
+    let end = rest.find(|c: char| c.is_whitespace() || c == ',').unwrap_or(rest.len());
+    Some(&rest[..end])
+}
+
+/// Parse the leading integer of a segment, such as 5 in " 5 packets transmitted".
+fn leading_integer(segment: &str) -> Option<u64> {
+    segment.split_whitespace().next()?.parse().ok()
+}
+
+/// Parse one line of `ping` output.
+///
+/// Return None if the line is not a reply, a lost request, or the totals.
+/// A lost request is a timeout, such as "Request timed out", or an ICMP
+/// error reply, such as "From 10.0.0.1 icmp_seq=4 Destination Host Unreachable".
+/// Skip a sequence number above 65535, since ICMP sequence numbers are 16 bits.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::ping::*;
+/// let line = "64 bytes from 1.1.1.1: icmp_seq=0 ttl=57 time=12.345 ms";
+/// let reply = PingReply { seq: Some(0), ttl: Some(57), rtt: 12.345, duplicate: false };
+/// assert_eq!(parse_ping_line(line), Some(PingLine::Reply(reply)));
+/// assert_eq!(parse_ping_line("Request timeout for icmp_seq 3"), Some(PingLine::Timeout(Some(3))));
+/// let line = "From 10.0.0.1 icmp_seq=4 Destination Host Unreachable";
+/// assert_eq!(parse_ping_line(line), Some(PingLine::Timeout(Some(4))));
+/// ```
+///
+pub fn parse_ping_line(line: &str) -> Option<PingLine> {
+    let line = line.trim().to_lowercase();
+    let time = field(&line, "time=").or_else(|| field(&line, "time<"));
+    if let Some(rtt) = time.and_then(|t| t.trim_end_matches("ms").parse::<f64>().ok()) {
+        return Some(PingLine::Reply(PingReply {
+            seq: field(&line, "seq=").and_then(|x| x.parse().ok()),
+            ttl: field(&line, "ttl=").or_else(|| field(&line, "hlim=")).and_then(|x| x.parse().ok()),
+            rtt,
+            duplicate: line.contains("dup!"),
+        }));
+    }
+    // Errors of ping itself, such as "ping: connect: Network is unreachable", are not requests.
+    let lost = ["timeout", "timed out", "no answer", "unreachable", "exceeded"].iter().any(|k| line.contains(k))
+        || (line.starts_with("from ") && line.contains("seq="));
+    if lost && !line.starts_with("ping:") {
+        let seq = field(&line, "seq=")
+            .or_else(|| line.split_whitespace().last())
+            .and_then(|x| x.parse().ok());
+        return Some(PingLine::Timeout(seq));
+    }
+    if line.contains("packets transmitted") {
+        let mut segments = line.split(',');
+        let transmitted = leading_integer(segments.next()?)?;
+        let received = leading_integer(segments.next()?)?;
+        return Some(PingLine::Totals { transmitted, received });
+    }
+    None
+}
+
+/// Parsed `ping` output.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PingOutput {
+    pub replies: Vec<PingReply>,
+    /// Sequence numbers of requests with no reply or an ICMP error reply, if shown.
+    pub timeouts: Vec<Option<u16>>,
+    /// Totals of the summary, if shown.
+    pub transmitted: Option<u64>,
+    pub received: Option<u64>,
+}
+
+/// Parse `ping` output.
+///
+/// Skip lines that are not a reply, a lost request, or the totals.
+pub fn parse_ping(text: &str) -> PingOutput {
+    let mut output = PingOutput::default();
+    for line in text.lines() {
+        match parse_ping_line(line) {
+            Some(PingLine::Reply(reply)) => output.replies.push(reply),
+            Some(PingLine::Timeout(seq)) => output.timeouts.push(seq),
+            Some(PingLine::Totals { transmitted, received }) => {
+                output.transmitted = Some(transmitted);
+                output.received = Some(received);
+            }
+            None => {}
+        }
+    }
+    output
+}
+
+impl PingOutput {
+    /// Round trip times of the replies, without duplicates, in milliseconds.
+    pub fn rtts(&self) -> Vec<f64> {
+        self.replies.iter().filter(|reply| !reply.duplicate).map(|reply| reply.rtt).collect()
+    }
+
+    /// Calculate all statistics of the round trip times.
+    pub fn all(&self) -> All {
+        All::new(self.rtts())
+    }
+
+    /// Calculate jitter of the round trip times.
+    pub fn jitter(&self) -> Jitter {
+        let mut jitter = Jitter::new();
+        self.rtts().iter().for_each(|x| jitter.push(*x));
+        jitter
+    }
+
+    /// Calculate loss, loss bursts, and reordering of the reply sequence
+    /// numbers, unwrapped after 65535.
+    pub fn sequence_stats(&self) -> SequenceStats {
+        let mut tracker = SequenceTracker::new();
+        self.replies.iter().filter_map(|reply| reply.seq).for_each(|seq| tracker.push_u16(seq));
+        tracker.stats()
+    }
+
+    /// Calculate packet loss ratio.
+    ///
+    /// Use the totals if shown. Otherwise use the reply sequence numbers
+    /// and the timeouts.
+    ///
+    /// Return NaN if there are no requests.
+    pub fn loss_ratio(&self) -> f64 {
+        if let (Some(transmitted), Some(received)) = (self.transmitted, self.received) {
+            return transmitted.saturating_sub(received) as f64 / transmitted as f64;
+        }
+        let stats = self.sequence_stats();
+        // Unwrap the reply sequence numbers like the stats, for their range.
+        let mut range: Option<(u64, u64)> = None;
+        for seq in self.replies.iter().filter_map(|reply| reply.seq) {
+            let seq = unwrap_u16(range.map_or(1 << 16, |(_, highest)| highest), seq);
+            range = Some(range.map_or((seq, seq), |(lowest, highest)| (lowest.min(seq), highest.max(seq))));
+        }
+        // Timeouts within the range of replies are already lost in the stats.
+        let outside = self.timeouts
+            .iter()
+            .filter(|seq| match (seq, range) {
+                (Some(seq), Some((lowest, highest))) => {
+                    let seq = unwrap_u16(highest, *seq);
+                    seq < lowest || seq > highest
+                }
+                _ => true,
+            })
+            .count() as u64;
+        (stats.lost + outside) as f64 / (stats.expected + outside) as f64
+    }
+
+    /// Create a display builder for formatting the statistics,
+    /// such as with precision and the unit "ms".
+    pub fn display(&self) -> StatsDisplay {
+        let rtts = self.rtts();
+        let sorted = sorted(&rtts);
+        let all = All::new(&rtts);
+        let jitter = self.jitter();
+        let stats = self.sequence_stats();
+        StatsDisplay::new(vec![
+            Field::new("count", rtts.len() as f64, Kind::Count),
+            Field::new("min", all.min, Kind::Value),
+            Field::new("average", all.average, Kind::Value),
+            Field::new("median", quantile_sorted(&sorted, 0.5), Kind::Value),
+            Field::new("90%", quantile_sorted(&sorted, 0.9), Kind::Value),
+            Field::new("99%", quantile_sorted(&sorted, 0.99), Kind::Value),
+            Field::new("max", all.max, Kind::Value),
+            Field::new("standard deviation", all.standard_deviation, Kind::Value),
+            Field::new("jitter", jitter.jitter(), Kind::Value),
+            Field::new("successive difference", jitter.mean_absolute_successive_difference(), Kind::Value),
+            Field::new("loss ratio", self.loss_ratio(), Kind::Unitless),
+            Field::new("duplicates", stats.duplicates as f64, Kind::Count),
+            Field::new("reordered", stats.reordered as f64, Kind::Count),
+        ])
+    }
+}
+
+/// One probe of a `traceroute` hop.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TracerouteProbe {
+    /// Host name or address that replied, if any.
+    pub host: Option<String>,
+    /// Address in parentheses after the host name, if shown.
+    pub address: Option<String>,
+    /// Round trip time in milliseconds, or NaN if the probe had no reply.
+    pub rtt: f64,
+}
+
+/// One hop of `traceroute`, with its probes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TracerouteHop {
+    pub hop: u32,
+    pub probes: Vec<TracerouteProbe>,
+}
+
+impl TracerouteHop {
+    /// Round trip times of the probes, with NaN for a probe with no reply.
+    pub fn rtts(&self) -> Vec<f64> {
+        self.probes.iter().map(|probe| probe.rtt).collect()
+    }
+
+    /// Calculate all statistics of the round trip times.
+    pub fn all(&self) -> All {
+        All::new(self.rtts())
+    }
+
+    /// Ratio of probes with no reply, or NaN if there are no probes.
+    pub fn loss_ratio(&self) -> f64 {
+        self.probes.iter().filter(|probe| probe.rtt.is_nan()).count() as f64 / self.probes.len() as f64
+    }
+}
+
+/// Parse the probes of a hop, such as "gw (10.0.0.1)  1.2 ms * 1.5 ms".
+fn parse_probes<'a>(tokens: impl Iterator<Item = &'a str>) -> Vec<TracerouteProbe> {
+    let mut tokens = tokens.peekable();
+    let mut probes = Vec::new();
+    let (mut host, mut address): (Option<String>, Option<String>) = (None, None);
+    while let Some(token) = tokens.next() {
+        if token == "*" {
+            probes.push(TracerouteProbe { host: None, address: None, rtt: f64::NAN });
+        } else if token.starts_with('!') || token == "ms" {
+            // Skip annotations such as !H, and units.
+        } else if let Some(inner) = token.strip_prefix('(').and_then(|t| t.strip_suffix(')')) {
+            address = Some(inner.to_string());
+        } else if let Some(rtt) = token.strip_suffix("ms").and_then(|t| t.parse::<f64>().ok()) {
+            probes.push(TracerouteProbe { host: host.clone(), address: address.clone(), rtt });
+        } else if let Some(rtt) = token.parse::<f64>().ok().filter(|_| tokens.peek() == Some(&"ms")) {
+            probes.push(TracerouteProbe { host: host.clone(), address: address.clone(), rtt });
+        } else {
+            host = Some(token.to_string());
+            address = None;
+        }
+    }
+    probes
+}
+
+/// Parse one hop line of `traceroute` output.
+///
+/// Return None if the line does not start with a hop number.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::ping::*;
+/// let hop = parse_traceroute_line(" 2  router (10.0.0.1)  1.5 ms *  2.5 ms").unwrap();
+/// assert_eq!(hop.hop, 2);
+/// assert_eq!(hop.probes.len(), 3);
+/// assert_eq!(hop.probes[0].host.as_deref(), Some("router"));
+/// assert_eq!(hop.probes[0].address.as_deref(), Some("10.0.0.1"));
+/// assert_eq!(hop.all().average, 2.0);
+/// ```
+///
+pub fn parse_traceroute_line(line: &str) -> Option<TracerouteHop> {
+    let mut tokens = line.split_whitespace();
+    let hop = tokens.next()?.parse().ok()?;
+    Some(TracerouteHop { hop, probes: parse_probes(tokens) })
+}
+
+/// Parse `traceroute` output into hops.
+///
+/// An indented line without a hop number, such as for another host
+/// that replied, continues the previous hop. Skip other lines.
+pub fn parse_traceroute(text: &str) -> Vec<TracerouteHop> {
+    let mut hops: Vec<TracerouteHop> = Vec::new();
+    for line in text.lines() {
+        if let Some(hop) = parse_traceroute_line(line) {
+            hops.push(hop);
+        } else if line.starts_with(char::is_whitespace) && let Some(last) = hops.last_mut() {
+            last.probes.extend(parse_probes(line.split_whitespace()));
+        }
+    }
+    hops
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const IPUTILS: &str = "\
+PING example.com (93.184.216.34) 56(84) bytes of data.
+64 bytes from example.com (93.184.216.34): icmp_seq=1 ttl=56 time=11.2 ms
+64 bytes from example.com (93.184.216.34): icmp_seq=2 ttl=56 time=12.8 ms
+64 bytes from example.com (93.184.216.34): icmp_seq=2 ttl=56 time=13.0 ms (DUP!)
+64 bytes from example.com (93.184.216.34): icmp_seq=4 ttl=56 time=10.6 ms
+
+--- example.com ping statistics ---
+4 packets transmitted, 3 received, +1 duplicates, 25% packet loss, time 3004ms
+rtt min/avg/max/mdev = 10.600/11.533/12.800/0.929 ms
+";
+
+    const BSD: &str = "\
+PING 1.1.1.1 (1.1.1.1): 56 data bytes
+64 bytes from 1.1.1.1: icmp_seq=0 ttl=57 time=9.5 ms
+Request timeout for icmp_seq 1
+64 bytes from 1.1.1.1: icmp_seq=2 ttl=57 time=10.5 ms
+Request timeout for icmp_seq 3
+";
+
+    #[test]
+    fn test_empty() {
+        let ping = parse_ping("");
+        assert!(ping.rtts().is_empty());
+        assert!(ping.all().average.is_nan());
+        assert!(ping.loss_ratio().is_nan());
+        assert!(parse_traceroute("").is_empty());
+    }
+
+    #[test]
+    fn test_iputils() {
+        let ping = parse_ping(IPUTILS);
+        assert_eq!(ping.replies.len(), 4);
+        assert_eq!(ping.rtts(), vec![11.2, 12.8, 10.6]);
+        assert_eq!(ping.replies[0].ttl, Some(56));
+        assert!(ping.replies[2].duplicate);
+        assert_eq!((ping.transmitted, ping.received), (Some(4), Some(3)));
+        assert_eq!(ping.loss_ratio(), 0.25);
+        let stats = ping.sequence_stats();
+        assert_eq!(stats.duplicates, 1);
+        assert_eq!(stats.lost, 1);
+        assert!((ping.all().average - 11.533333333333333).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_bsd_timeouts() {
+        let ping = parse_ping(BSD);
+        assert_eq!(ping.rtts(), vec![9.5, 10.5]);
+        assert_eq!(ping.timeouts, vec![Some(1), Some(3)]);
+        // Seq 1 is a gap in the replies, and seq 3 is after them.
+        assert_eq!(ping.loss_ratio(), 0.5);
+        assert_eq!(ping.jitter().mean_absolute_successive_difference(), 1.0);
+    }
+
+    #[test]
+    fn test_other_formats() {
+        let reply = parse_ping_line("Reply from 10.0.0.1: bytes=32 time<1ms TTL=128");
+        assert!(matches!(reply, Some(PingLine::Reply(PingReply { rtt: 1.0, ttl: Some(128), .. }))));
+        let totals = parse_ping_line("5 packets transmitted, 5 packets received, 0.0% packet loss");
+        assert_eq!(totals, Some(PingLine::Totals { transmitted: 5, received: 5 }));
+        assert_eq!(parse_ping_line("no answer yet for icmp_seq=7"), Some(PingLine::Timeout(Some(7))));
+        assert_eq!(parse_ping_line("--- 1.1.1.1 ping statistics ---"), None);
+        // ICMP error replies and Windows timeouts are lost requests.
+        for (line, seq) in [
+            ("From 10.0.0.1 icmp_seq=4 Destination Host Unreachable", Some(4)),
+            ("From 10.0.0.1 icmp_seq=5 Destination Net Unreachable", Some(5)),
+            ("From 10.0.0.1 icmp_seq=6 Time to live exceeded", Some(6)),
+            ("From 10.0.0.1 icmp_seq=7 Packet filtered", Some(7)),
+            ("92 bytes from 10.0.0.1: Destination Host Unreachable", None),
+            ("Reply from 10.0.0.1: Destination host unreachable.", None),
+            ("Request timed out.", None),
+        ] {
+            assert_eq!(parse_ping_line(line), Some(PingLine::Timeout(seq)), "{}", line);
+        }
+        assert_eq!(parse_ping_line("ping: connect: Network is unreachable"), None);
+        // ICMP sequence numbers are 16 bits.
+        let reply = parse_ping_line("64 bytes from 1.1.1.1: icmp_seq=1099511627776 ttl=57 time=9.5 ms");
+        assert!(matches!(reply, Some(PingLine::Reply(PingReply { seq: None, .. }))));
+    }
+
+    #[test]
+    fn test_unreachable() {
+        let text = "\
+64 bytes from 10.0.0.2: icmp_seq=1 ttl=64 time=0.5 ms
+From 10.0.0.1 icmp_seq=2 Destination Host Unreachable
+From 10.0.0.1 icmp_seq=3 Destination Host Unreachable
+64 bytes from 10.0.0.2: icmp_seq=4 ttl=64 time=0.7 ms
+From 10.0.0.1 icmp_seq=5 Destination Net Unreachable
+";
+        let ping = parse_ping(text);
+        assert_eq!(ping.rtts(), vec![0.5, 0.7]);
+        assert_eq!(ping.timeouts, vec![Some(2), Some(3), Some(5)]);
+        assert_eq!(ping.loss_ratio(), 3.0 / 5.0);
+    }
+
+    #[test]
+    fn test_sequence_wrap() {
+        let text = "\
+64 bytes from 1.1.1.1: icmp_seq=65534 ttl=57 time=9.5 ms
+64 bytes from 1.1.1.1: icmp_seq=65535 ttl=57 time=9.6 ms
+64 bytes from 1.1.1.1: icmp_seq=0 ttl=57 time=9.7 ms
+Request timeout for icmp_seq 1
+64 bytes from 1.1.1.1: icmp_seq=2 ttl=57 time=9.8 ms
+Request timeout for icmp_seq 3
+";
+        let ping = parse_ping(text);
+        let stats = ping.sequence_stats();
+        assert_eq!((stats.expected, stats.lost, stats.reordered, stats.duplicates), (5, 1, 0, 0));
+        // Seq 1 is a gap in the replies, and seq 3 is after them.
+        assert_eq!(ping.loss_ratio(), 2.0 / 6.0);
+    }
+
+    #[test]
+    fn test_traceroute() {
+        let text = "\
+traceroute to example.com (93.184.216.34), 30 hops max, 60 byte packets
+ 1  gateway (192.168.1.1)  1.123 ms  0.987 ms  1.050 ms
+ 2  * * *
+ 3  a.example.net (10.0.0.1)  5.100 ms b.example.net (10.0.0.2)  6.200 ms *
+    c.example.net (10.0.0.3)  7.300 ms
+ 4  93.184.216.34  9.9 ms !H
+";
+        let hops = parse_traceroute(text);
+        assert_eq!(hops.iter().map(|hop| hop.hop).collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert_eq!(hops[0].rtts(), vec![1.123, 0.987, 1.050]);
+        assert_eq!(hops[1].loss_ratio(), 1.0);
+        assert_eq!(hops[2].probes.len(), 4);
+        assert_eq!(hops[2].probes[1].host.as_deref(), Some("b.example.net"));
+        assert_eq!(hops[2].probes[3].address.as_deref(), Some("10.0.0.3"));
+        assert_eq!(hops[2].loss_ratio(), 0.25);
+        assert_eq!(hops[3].probes[0].host.as_deref(), Some("93.184.216.34"));
+        assert_eq!(hops[3].probes[0].rtt, 9.9);
+    }
+
+}
@@ -0,0 +1,345 @@
+//! Terminal plots: horizontal-bar histograms, sparklines, and box plots.
+//!
+//! Each plot can write to any `fmt::Write`, or return a `String`.
+//! ASCII-only mode avoids Unicode block characters, for terminals and CI
+//! logs that cannot show them.
+//!
+//! # Example
+//!
+//! ```rust
+//! use numeric_statistics::f64::plot::*;
+//!
+//! let values = &[1.0, 2.0, 3.0, 5.0, 8.0, 5.0, 3.0, 2.0, 1.0];
+//! let options = PlotOptions::default();
+//! assert_eq!(sparkline(values, &options), "▁▂▃▅█▅▃▂▁");
+//! assert_eq!(sparkline(values, &options.ascii(true)), "_.,=#=,._");
+//! ```
+
+use std::fmt;
+use super::{
+    box_plot::BoxPlotStats,
+    five_number_summary::FiveNumberSummary,
+    histogram::Histogram,
+};
+
+/// Unicode sparkline levels, lowest to highest.
+const SPARK_UNICODE: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// ASCII sparkline levels, lowest to highest.
+const SPARK_ASCII: [char; 8] = ['_', '.', ',', '-', '=', '+', '*', '#'];
+
+/// Unicode partial bar blocks, in eighths.
+const BAR_EIGHTHS: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+/// Options for terminal plots.
+///
+/// * `width`: max columns of a histogram bar, sparkline, or box plot.
+///
+/// * `height`: rows of a sparkline.
+///
+/// * `ascii`: use ASCII characters only.
+///
+/// * `precision`: decimal places of value labels.
+///
+/// * `tukey`: draw box plot whiskers to Tukey's inner fences, and mark
+///   outliers, rather than whiskers to the min and max.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlotOptions {
+    pub width: usize,
+    pub height: usize,
+    pub ascii: bool,
+    pub precision: usize,
+    pub tukey: bool,
+}
+
+impl Default for PlotOptions {
+    fn default() -> Self {
+        PlotOptions { width: 60, height: 1, ascii: false, precision: 2, tukey: false }
+    }
+}
+
+impl PlotOptions {
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = width.max(1);
+        self
+    }
+
+    pub fn height(mut self, height: usize) -> Self {
+        self.height = height.max(1);
+        self
+    }
+
+    pub fn ascii(mut self, ascii: bool) -> Self {
+        self.ascii = ascii;
+        self
+    }
+
+    pub fn precision(mut self, precision: usize) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    pub fn tukey(mut self, tukey: bool) -> Self {
+        self.tukey = tukey;
+        self
+    }
+}
+
+/// Write a horizontal-bar histogram, one row per bin, with the bin range,
+/// the bar scaled so the largest count fills the width, and the count.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::{histogram::*, plot::*};
+/// let values = &[1.0, 2.0, 2.0, 3.0, 3.0, 3.0, 3.0];
+/// let histogram = Histogram::new(values, &BinRule::Count(2));
+/// let plot = histogram_plot(&histogram, &PlotOptions::default().width(8).precision(1).ascii(true));
+/// assert_eq!(plot, concat!(
+///     "[1.0, 2.0) #        1\n",
+///     "[2.0, 3.0] ######## 6\n",
+/// ));
+/// ```
+///
+pub fn write_histogram_plot<W: fmt::Write>(w: &mut W, histogram: &Histogram, options: &PlotOptions) -> fmt::Result {
+    let precision = options.precision;
+    let lowers: Vec<String> = histogram.edges.iter().map(|x| format!("{:.*}", precision, x)).collect();
+    let lower_width = lowers.iter().map(|s| s.len()).max().unwrap_or(0);
+    let max_count = histogram.counts.iter().copied().max().unwrap_or(0);
+    let count_width = max_count.to_string().len();
+    let last = histogram.counts.len().saturating_sub(1);
+    for (i, count) in histogram.counts.iter().enumerate() {
+        let close = if i == last { ']' } else { ')' };
+        write!(w, "[{:>lower_width$}, {:>lower_width$}{} ", lowers[i], lowers[i + 1], close)?;
+        let length = if max_count == 0 { 0.0 } else { *count as f64 / max_count as f64 * options.width as f64 };
+        write!(w, "{}", bar(length, options.width, options.ascii))?;
+        writeln!(w, " {:>count_width$}", count)?;
+    }
+    Ok(())
+}
+
+/// Render a horizontal-bar histogram to a string. See `write_histogram_plot`.
+pub fn histogram_plot(histogram: &Histogram, options: &PlotOptions) -> String {
+    let mut s = String::new();
+    write_histogram_plot(&mut s, histogram, options).expect("write to string");
+    s
+}
+
+/// Render a bar of the length in columns, padded to the width.
+fn bar(length: f64, width: usize, ascii: bool) -> String {
+    let eighths = (length * 8.0).round() as usize;
+    let (full, part) = (eighths / 8, eighths % 8);
+    let mut s = String::new();
+    if ascii {
+        let full = (length.round() as usize).min(width);
+        s.extend(std::iter::repeat_n('#', full));
+        s.extend(std::iter::repeat_n(' ', width - full));
+    } else {
+        s.extend(std::iter::repeat_n('█', full));
+        if part > 0 { s.push(BAR_EIGHTHS[part]); }
+        s.extend(std::iter::repeat_n(' ', width - full - usize::from(part > 0)));
+    }
+    s
+}
+
+/// Write a sparkline of a series, scaled from the min to the max.
+///
+/// If the series is longer than the width, each column shows the average
+/// of a run of consecutive values. With a height above 1, the sparkline
+/// uses that many rows, for finer resolution.
+///
+/// Show NaN values as blanks.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::plot::*;
+/// let values = &[0.0, 1.0, 2.0, 3.0];
+/// let plot = sparkline(values, &PlotOptions::default().height(2));
+/// assert_eq!(plot, "  ▃█\n▁▆██");
+/// ```
+///
+pub fn write_sparkline<W: fmt::Write, T: AsRef<[f64]>>(w: &mut W, values: T, options: &PlotOptions) -> fmt::Result {
+    let columns = downsample(values.as_ref(), options.width);
+    let lo = columns.iter().fold(f64::NAN, |a, x| a.min(*x));
+    let hi = columns.iter().fold(f64::NAN, |a, x| a.max(*x));
+    let height = options.height;
+    let steps = 8 * height;
+    // Level 1 is the lowest visible mark; level steps is a full column.
+    let levels: Vec<Option<usize>> = columns
+        .iter()
+        .map(|x| {
+            if x.is_nan() { return None; }
+            if hi == lo { return Some(1); }
+            Some(1 + ((x - lo) / (hi - lo) * (steps - 1) as f64).round() as usize)
+        })
+        .collect();
+    let chars = if options.ascii { &SPARK_ASCII } else { &SPARK_UNICODE };
+    for row in (0..height).rev() {
+        for level in levels.iter() {
+            let c = match level {
+                Some(level) if *level > 8 * row => chars[(*level - 8 * row).min(8) - 1],
+                _ => ' ',
+            };
+            w.write_char(c)?;
+        }
+        if row > 0 { writeln!(w)?; }
+    }
+    Ok(())
+}
+
+/// Render a sparkline to a string. See `write_sparkline`.
+pub fn sparkline<T: AsRef<[f64]>>(values: T, options: &PlotOptions) -> String {
+    let mut s = String::new();
+    write_sparkline(&mut s, values, options).expect("write to string");
+    s
+}
+
+/// Shrink values to at most width columns, by averaging consecutive runs.
+fn downsample(values: &[f64], width: usize) -> Vec<f64> {
+    if values.len() <= width { return values.to_vec(); }
+    (0..width)
+        .map(|i| {
+            let run = &values[i * values.len() / width..(i + 1) * values.len() / width];
+            super::average(run)
+        })
+        .collect()
+}
+
+/// Write a one-line box plot with whiskers, built from the five-number
+/// summary: min, first quartile, median, third quartile, and max.
+///
+/// The line starts with the min label and ends with the max label.
+/// The whiskers span from the min to the max; the box spans from the
+/// first quartile to the third quartile, with a mark at the median.
+///
+/// With the `tukey` option, the whiskers reach the most extreme values
+/// within the inner fences instead. Mild outliers show as `o` (Unicode
+/// `○`), and extreme outliers show as `*` (Unicode `●`). See `BoxPlotStats`.
+///
+/// Write nothing if the values are empty.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::plot::*;
+/// let values = &[0.0, 2.0, 4.0, 6.0, 8.0, 20.0];
+/// let options = PlotOptions::default().width(21).precision(0);
+/// assert_eq!(box_plot(values, &options), "0 ├──██┃███───────────┤ 20");
+/// assert_eq!(box_plot(values, &options.clone().ascii(true)), "0 |--[=|==]-----------| 20");
+/// assert_eq!(box_plot(values, &options.tukey(true)), "0 ├──██┃███           ○ 20");
+/// ```
+///
+pub fn write_box_plot<W: fmt::Write, T: AsRef<[f64]>>(w: &mut W, values: T, options: &PlotOptions) -> fmt::Result {
+    let stats = BoxPlotStats::new(values);
+    let FiveNumberSummary { min, q1, median, q3, max } = stats.summary;
+    if min.is_nan() { return Ok(()); }
+    let width = options.width.max(5);
+    let column = |x: f64| if max == min { width / 2 } else { ((x - min) / (max - min) * (width - 1) as f64).round() as usize };
+    let tukey = options.tukey;
+    let (c_low, c_high) = if tukey { (column(stats.whisker_low), column(stats.whisker_high)) } else { (0, width - 1) };
+    let (c_q1, c_median, c_q3) = (column(q1), column(median), column(q3));
+    let mut line: Vec<char> = (0..width)
+        .map(|c| match (options.ascii, c) {
+            // Whiskers to the min and max end the line, over the box.
+            (true, c) if !tukey && (c == c_low || c == c_high) => '|',
+            (false, c) if !tukey && c == c_low => '├',
+            (false, c) if !tukey && c == c_high => '┤',
+            (true, c) if c == c_median => '|',
+            (false, c) if c == c_median => '┃',
+            (true, c) if c == c_q1 => '[',
+            (true, c) if c == c_q3 => ']',
+            (true, c) if c > c_q1 && c < c_q3 => '=',
+            (false, c) if c >= c_q1 && c <= c_q3 => '█',
+            (true, c) if c == c_low || c == c_high => '|',
+            (false, c) if c == c_low => '├',
+            (false, c) if c == c_high => '┤',
+            (true, c) if c > c_low && c < c_high => '-',
+            (false, c) if c > c_low && c < c_high => '─',
+            _ => ' ',
+        })
+        .collect();
+    if tukey {
+        let (mild, extreme) = if options.ascii { ('o', '*') } else { ('○', '●') };
+        stats.mild_outliers.iter().for_each(|o| line[column(o.value)] = mild);
+        stats.extreme_outliers.iter().for_each(|o| line[column(o.value)] = extreme);
+    }
+    let line: String = line.into_iter().collect();
+    let precision = options.precision;
+    write!(w, "{:.*} {} {:.*}", precision, min, line, precision, max)
+}
+
+/// Render a one-line box plot to a string. See `write_box_plot`.
+pub fn box_plot<T: AsRef<[f64]>>(values: T, options: &PlotOptions) -> String {
+    let mut s = String::new();
+    write_box_plot(&mut s, values, options).expect("write to string");
+    s
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::f64::histogram::BinRule;
+
+    #[test]
+    fn test_empty() {
+        let x: &[f64] = &[];
+        let options = PlotOptions::default();
+        assert_eq!(sparkline(x, &options), "");
+        assert_eq!(box_plot(x, &options), "");
+        assert_eq!(histogram_plot(&Histogram::new(x, &BinRule::Sturges), &options), "");
+    }
+
+    #[test]
+    fn test_sparkline_nan() {
+        let x = &[1.0, f64::NAN, 3.0];
+        assert_eq!(sparkline(x, &PlotOptions::default()), "▁ █");
+    }
+
+    #[test]
+    fn test_sparkline_constant() {
+        let x = &[5.0, 5.0, 5.0];
+        assert_eq!(sparkline(x, &PlotOptions::default()), "▁▁▁");
+    }
+
+    #[test]
+    fn test_sparkline_downsample() {
+        let x: Vec<f64> = (0..100).map(|i| i as f64).collect();
+        let plot = sparkline(&x, &PlotOptions::default().width(8));
+        assert_eq!(plot, "▁▂▃▄▅▆▇█");
+    }
+
+    #[test]
+    fn test_sparkline_height() {
+        let x = &[0.0, 1.0, 2.0, 3.0];
+        let plot = sparkline(x, &PlotOptions::default().height(3).ascii(true));
+        assert_eq!(plot, "   #\n _##\n_###");
+    }
+
+    #[test]
+    fn test_histogram_plot_unicode() {
+        let x = &[1.0, 2.0, 2.0, 3.0, 3.0, 3.0, 3.0];
+        let histogram = Histogram::new(x, &BinRule::Count(2));
+        let plot = histogram_plot(&histogram, &PlotOptions::default().width(4).precision(0));
+        assert_eq!(plot, "[1, 2) ▋    1\n[2, 3] ████ 6\n");
+    }
+
+    #[test]
+    fn test_box_plot_extreme_outlier() {
+        let x = &[1.0, 2.0, 3.0, 4.0, 5.0, 50.0];
+        let options = PlotOptions::default().width(50).precision(0).ascii(true);
+        assert_eq!(box_plot(x, &options.clone().tukey(true)), format!("1 |[=|]{}* 50", " ".repeat(44)));
+        assert_eq!(box_plot(x, &options), format!("1 |[=|]{}| 50", "-".repeat(44)));
+    }
+
+    #[test]
+    fn test_box_plot_single_value() {
+        let x = &[3.0];
+        let plot = box_plot(x, &PlotOptions::default().width(5).precision(0).ascii(true));
+        assert_eq!(plot, "3 |-|-| 3");
+    }
+
+}
@@ -0,0 +1,196 @@
+/// Calculate statistical quantile for values, such as 0.5 for the median.
+///
+/// This uses linear interpolation between closest ranks, which is the
+/// default method in R (type 7), NumPy, and spreadsheets.
+///
+/// Return NaN if the values are empty, or if p is not within 0.0 to 1.0.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::quantile::*;
+/// let values = &[1.0, 2.0, 4.0, 8.0];
+/// let quantile = quantile(values, 0.5);
+/// assert_eq!(quantile, 3.0);
+/// ```
+///
+pub fn quantile<T: AsRef<[f64]>>(values: T, p: f64) -> f64 {
+    quantile_sorted(sorted(values), p)
+}
+
+/// Calculate statistical quantiles for values, for each p in ps.
+///
+/// This sorts the values once, so is faster than calling `quantile` repeatedly.
+///
+/// Return NaN for each p if the values are empty, or if the p is not within 0.0 to 1.0.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::quantile::*;
+/// let values = &[1.0, 2.0, 3.0, 4.0, 5.0];
+/// let quantiles = quantiles(values, &[0.25, 0.5, 0.75]);
+/// assert_eq!(quantiles, vec![2.0, 3.0, 4.0]);
+/// ```
+///
+pub fn quantiles<T: AsRef<[f64]>>(values: T, ps: &[f64]) -> Vec<f64> {
+    let sorted = sorted(values);
+    ps.iter().map(|p| quantile_sorted(&sorted, *p)).collect()
+}
+
+/// Calculate statistical quantile for values that are already sorted
+/// ascending and have no NaN values.
+///
+/// Return NaN if the values are empty, or if p is not within 0.0 to 1.0.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::quantile::*;
+/// let sorted = &[1.0, 2.0, 4.0, 8.0];
+/// let quantile = quantile_sorted(sorted, 0.25);
+/// assert_eq!(quantile, 1.75);
+/// ```
+///
+pub fn quantile_sorted<T: AsRef<[f64]>>(sorted: T, p: f64) -> f64 {
+    let sorted = sorted.as_ref();
+    if sorted.is_empty() || !(0.0..=1.0).contains(&p) { return f64::NAN; }
+    let h = (sorted.len() - 1) as f64 * p;
+    let lo = h.floor() as usize;
+    let hi = h.ceil() as usize;
+    let fraction = h - lo as f64;
+    // Interpolate only between unequal values, and weigh an infinite value
+    // rather than subtract it, so infinite values do not give NaN.
+    if fraction == 0.0 || sorted[lo] == sorted[hi] { return sorted[lo]; }
+    if sorted[lo].is_infinite() || sorted[hi].is_infinite() {
+        return (1.0 - fraction) * sorted[lo] + fraction * sorted[hi];
+    }
+    sorted[lo] + fraction * (sorted[hi] - sorted[lo])
+}
+
+/// Calculate statistical median for values.
+///
+/// Return NaN if the values are empty.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::quantile::*;
+/// let values = &[4.0, 1.0, 2.0];
+/// let median = median(values);
+/// assert_eq!(median, 2.0);
+/// ```
+///
+pub fn median<T: AsRef<[f64]>>(values: T) -> f64 {
+    quantile(values, 0.5)
+}
+
+/// Calculate statistical interquartile range for values, which is Q3 - Q1.
+///
+/// Return NaN if the values are empty.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::quantile::*;
+/// let values = &[1.0, 2.0, 3.0, 4.0, 5.0];
+/// let interquartile_range = interquartile_range(values);
+/// assert_eq!(interquartile_range, 2.0);
+/// ```
+///
+pub fn interquartile_range<T: AsRef<[f64]>>(values: T) -> f64 {
+    let sorted = sorted(values);
+    quantile_sorted(&sorted, 0.75) - quantile_sorted(&sorted, 0.25)
+}
+
+/// Copy values without NaN values, then sort ascending.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::quantile::*;
+/// let values = &[4.0, f64::NAN, 1.0, 2.0];
+/// let sorted = sorted(values);
+/// assert_eq!(sorted, vec![1.0, 2.0, 4.0]);
+/// ```
+///
+pub fn sorted<T: AsRef<[f64]>>(values: T) -> Vec<f64> {
+    let mut sorted: Vec<f64> = values.as_ref().iter().copied().filter(|x| !x.is_nan()).collect();
+    sorted.sort_by(f64::total_cmp);
+    sorted
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_empty() {
+        let x: &[f64] = &[];
+        assert!(quantile(x, 0.5).is_nan());
+    }
+
+    #[test]
+    fn test_nan() {
+        let x: &[f64] = &[f64::NAN];
+        assert!(quantile(x, 0.5).is_nan());
+    }
+
+    #[test]
+    fn test_value() {
+        let x: &[f64] = &[1.0];
+        assert_eq!(quantile(x, 0.0), 1.0);
+        assert_eq!(quantile(x, 0.5), 1.0);
+        assert_eq!(quantile(x, 1.0), 1.0);
+    }
+
+    #[test]
+    fn test_p_out_of_range() {
+        let x = &[1.0, 2.0, 4.0];
+        assert!(quantile(x, -0.1).is_nan());
+        assert!(quantile(x, 1.1).is_nan());
+        assert!(quantile(x, f64::NAN).is_nan());
+    }
+
+    #[test]
+    fn test_values_interpolate() {
+        let x = &[4.0, f64::NAN, 1.0, 8.0, 2.0];
+        assert_eq!(quantile(x, 0.0), 1.0);
+        assert_eq!(quantile(x, 0.25), 1.75);
+        assert_eq!(quantile(x, 0.5), 3.0);
+        assert_eq!(quantile(x, 0.75), 5.0);
+        assert_eq!(quantile(x, 1.0), 8.0);
+        assert!((quantile(x, 0.9) - 6.8).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_infinite_values() {
+        let x = &[1.0, f64::INFINITY];
+        assert_eq!(quantile(x, 0.0), 1.0);
+        assert_eq!(quantile(x, 0.5), f64::INFINITY);
+        assert_eq!(quantile(x, 1.0), f64::INFINITY);
+        let x = &[f64::NEG_INFINITY, 1.0];
+        assert_eq!(quantile(x, 0.0), f64::NEG_INFINITY);
+        assert_eq!(quantile(x, 0.5), f64::NEG_INFINITY);
+        assert_eq!(quantile(x, 1.0), 1.0);
+        let x = &[f64::INFINITY, f64::INFINITY];
+        assert_eq!(median(x), f64::INFINITY);
+        assert!(median([f64::NEG_INFINITY, f64::INFINITY]).is_nan());
+    }
+
+    #[test]
+    fn test_median_and_interquartile_range() {
+        let x = &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        assert_eq!(median(x), 3.5);
+        assert_eq!(interquartile_range(x), 2.5);
+    }
+
+}
@@ -0,0 +1,77 @@
+use super::quantile::sorted;
+
+/// Calculate ranks for values, from 1 for the least value, where tied
+/// values share the average of their ranks.
+///
+/// This is the ranking used by Spearman's rho and by rank tests.
+///
+/// Return NaN for each NaN value, and rank the other values among themselves.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::rank::*;
+/// let ranks = ranks(&[10.0, 30.0, 20.0, 20.0]);
+/// assert_eq!(ranks, vec![1.0, 4.0, 2.5, 2.5]);
+/// ```
+///
+pub fn ranks<T: AsRef<[f64]>>(values: T) -> Vec<f64> {
+    let values = values.as_ref();
+    let mut order: Vec<usize> = (0..values.len()).filter(|i| !values[*i].is_nan()).collect();
+    order.sort_by(|a, b| values[*a].total_cmp(&values[*b]));
+    let mut ranks = vec![f64::NAN; values.len()];
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start + 1;
+        while end < order.len() && values[order[end]] == values[order[start]] { end += 1; }
+        // Ranks start + 1 to end, inclusive, share their average.
+        let rank = (start + 1 + end) as f64 / 2.0;
+        order[start..end].iter().for_each(|i| ranks[*i] = rank);
+        start = end;
+    }
+    ranks
+}
+
+/// Calculate the sizes of groups of tied values, with a size for each
+/// distinct value, such as for tie corrections in rank tests.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::rank::*;
+/// let ties = tie_sizes(&[1.0, 2.0, 2.0, 3.0, 3.0, 3.0]);
+/// assert_eq!(ties, vec![1, 2, 3]);
+/// ```
+///
+pub fn tie_sizes<T: AsRef<[f64]>>(values: T) -> Vec<usize> {
+    sorted(values).chunk_by(|a, b| a == b).map(<[f64]>::len).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_empty() {
+        let x: &[f64] = &[];
+        assert!(ranks(x).is_empty());
+        assert!(tie_sizes(x).is_empty());
+    }
+
+    #[test]
+    fn test_nan() {
+        let ranks = ranks([3.0, f64::NAN, 1.0]);
+        assert_eq!(ranks[0], 2.0);
+        assert!(ranks[1].is_nan());
+        assert_eq!(ranks[2], 1.0);
+    }
+
+    #[test]
+    fn test_ties() {
+        assert_eq!(ranks([2.0, 2.0, 2.0]), vec![2.0, 2.0, 2.0]);
+        assert_eq!(ranks([5.0, 1.0, 5.0, 0.0]), vec![3.5, 2.0, 3.5, 1.0]);
+    }
+
+}
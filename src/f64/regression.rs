@@ -0,0 +1,381 @@
+//! Linear regression by ordinary least squares (OLS): simple regression
+//! of y on one x, and multiple regression of y on several predictors.
+//!
+//! Multiple regression solves with a Householder QR decomposition, which
+//! is numerically stable, and needs no external linear algebra library.
+//!
+//! # Example
+//!
+//! ```rust
+//! use numeric_statistics::f64::regression::*;
+//!
+//! // Monthly peak traffic, for capacity planning.
+//! let months = [1.0, 2.0, 3.0, 4.0, 5.0];
+//! let traffic = [2.0, 4.0, 5.0, 4.0, 5.0];
+//! let fit = simple_linear_regression(months, traffic);
+//! assert!((fit.slope - 0.6).abs() < 1e-12);
+//! assert!((fit.intercept - 2.2).abs() < 1e-12);
+//! assert!((fit.predict(12.0) - 9.4).abs() < 1e-12);
+//! ```
+
+use super::{average, distribution::*};
+
+/// Result of simple linear regression y = intercept + slope × x.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimpleRegression {
+    /// Count of pairs without NaN.
+    pub n: usize,
+    pub slope: f64,
+    pub intercept: f64,
+    /// Coefficient of determination.
+    pub r_squared: f64,
+    pub slope_standard_error: f64,
+    pub intercept_standard_error: f64,
+    /// Residual standard error, which is the square root of SSE / (n - 2).
+    pub residual_standard_error: f64,
+    /// Residual y - prediction for each input position, or NaN for a pair with NaN.
+    pub residuals: Vec<f64>,
+}
+
+impl SimpleRegression {
+    /// Predict y for x.
+    pub fn predict(&self, x: f64) -> f64 {
+        self.intercept + self.slope * x
+    }
+}
+
+/// Calculate simple linear regression of ys on xs by ordinary least squares.
+///
+/// Return NaN statistics if there are fewer than 2 pairs, if the xs are all
+/// equal, or if xs and ys have different lengths. Standard errors need
+/// at least 3 pairs.
+///
+/// Filter pairs with a NaN value in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::regression::*;
+/// let fit = simple_linear_regression([1.0, 2.0, 3.0], [3.0, 5.0, 7.0]);
+/// assert_eq!(fit.slope, 2.0);
+/// assert_eq!(fit.intercept, 1.0);
+/// assert_eq!(fit.r_squared, 1.0);
+/// ```
+///
+pub fn simple_linear_regression<X: AsRef<[f64]>, Y: AsRef<[f64]>>(xs: X, ys: Y) -> SimpleRegression {
+    let (xs, ys) = (xs.as_ref(), ys.as_ref());
+    let nan = |n, residuals| SimpleRegression {
+        n,
+        slope: f64::NAN,
+        intercept: f64::NAN,
+        r_squared: f64::NAN,
+        slope_standard_error: f64::NAN,
+        intercept_standard_error: f64::NAN,
+        residual_standard_error: f64::NAN,
+        residuals,
+    };
+    if xs.len() != ys.len() { return nan(0, Vec::new()); }
+    let (px, py): (Vec<f64>, Vec<f64>) = xs.iter().zip(ys).filter(|(x, y)| !x.is_nan() && !y.is_nan()).unzip();
+    let n = px.len();
+    if n < 2 { return nan(n, vec![f64::NAN; xs.len()]); }
+    let (x_average, y_average) = (average(&px), average(&py));
+    let (mut sxx, mut sxy, mut syy) = (0.0, 0.0, 0.0);
+    for (x, y) in px.iter().zip(&py) {
+        let (dx, dy) = (x - x_average, y - y_average);
+        sxx += dx * dx;
+        sxy += dx * dy;
+        syy += dy * dy;
+    }
+    if sxx == 0.0 { return nan(n, vec![f64::NAN; xs.len()]); }
+    let slope = sxy / sxx;
+    let intercept = y_average - slope * x_average;
+    let residuals: Vec<f64> = xs.iter().zip(ys).map(|(x, y)| y - (intercept + slope * x)).collect();
+    let sse: f64 = residuals.iter().filter(|e| !e.is_nan()).map(|e| e * e).sum();
+    let variance = if n > 2 { sse / (n - 2) as f64 } else { f64::NAN };
+    SimpleRegression {
+        n,
+        slope,
+        intercept,
+        r_squared: if syy == 0.0 { f64::NAN } else { 1.0 - sse / syy },
+        slope_standard_error: (variance / sxx).sqrt(),
+        intercept_standard_error: (variance * (1.0 / n as f64 + x_average * x_average / sxx)).sqrt(),
+        residual_standard_error: variance.sqrt(),
+        residuals,
+    }
+}
+
+/// Result of multiple linear regression by ordinary least squares.
+///
+/// Coefficient 0 is the intercept, and coefficient i is for predictor i - 1.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ols {
+    /// Count of rows without NaN.
+    pub n: usize,
+    pub coefficients: Vec<f64>,
+    pub standard_errors: Vec<f64>,
+    pub t_statistics: Vec<f64>,
+    /// Two-sided p-values of the t statistics.
+    pub p_values: Vec<f64>,
+    /// Coefficient of determination.
+    pub r_squared: f64,
+    pub adjusted_r_squared: f64,
+    /// F statistic that every coefficient except the intercept is zero.
+    pub f_statistic: f64,
+    pub f_p_value: f64,
+    /// Residual degrees of freedom, which is n - coefficients.
+    pub degrees_of_freedom: usize,
+    /// Residual standard error, which is the square root of SSE / degrees of freedom.
+    pub residual_standard_error: f64,
+    /// Residual y - prediction for each input row, or NaN for a row with NaN.
+    pub residuals: Vec<f64>,
+}
+
+impl Ols {
+    /// Predict y for one row of predictors.
+    ///
+    /// Return NaN if the row has the wrong length.
+    pub fn predict<R: AsRef<[f64]>>(&self, row: R) -> f64 {
+        let row = row.as_ref();
+        if row.len() + 1 != self.coefficients.len() { return f64::NAN; }
+        self.coefficients[0] + row.iter().zip(&self.coefficients[1..]).map(|(x, b)| x * b).sum::<f64>()
+    }
+}
+
+/// Householder QR decomposition of a column-major n × k matrix.
+///
+/// Overwrite the upper triangle of the matrix with R, and apply Q^T to y.
+/// Return false if the matrix is rank-deficient.
+fn householder_qr(a: &mut [f64], n: usize, k: usize, y: &mut [f64]) -> bool {
+    let scale = (0..k)
+        .map(|j| a[j * n..(j + 1) * n].iter().map(|x| x * x).sum::<f64>().sqrt())
+        .fold(0.0, f64::max);
+    let tolerance = scale * n as f64 * f64::EPSILON;
+    for j in 0..k {
+        let column = j * n;
+        let norm = a[column + j..column + n].iter().map(|x| x * x).sum::<f64>().sqrt();
+        if norm <= tolerance { return false; }
+        let alpha = if a[column + j] > 0.0 { -norm } else { norm };
+        // Householder vector v = a[j..n, j] - alpha e1, kept in a[j..n, j].
+        a[column + j] -= alpha;
+        let v_square = a[column + j..column + n].iter().map(|x| x * x).sum::<f64>();
+        let reflect = |target: &mut [f64], v: &[f64]| {
+            let dot: f64 = target.iter().zip(v).map(|(t, v)| t * v).sum();
+            let factor = 2.0 * dot / v_square;
+            target.iter_mut().zip(v).for_each(|(t, v)| *t -= factor * v);
+        };
+        let v = a[column + j..column + n].to_vec();
+        for c in j + 1..k {
+            reflect(&mut a[c * n + j..(c + 1) * n], &v);
+        }
+        reflect(&mut y[j..n], &v);
+        a[column + j] = alpha;
+    }
+    true
+}
+
+/// Calculate multiple linear regression of ys on rows of predictors,
+/// with an intercept, by ordinary least squares.
+///
+/// Each row is one observation, with one value per predictor.
+///
+/// Return NaN statistics if the rows have different lengths, if the rows
+/// and ys have different lengths, if there are not more rows than
+/// coefficients, or if the predictors are collinear.
+///
+/// Filter rows with a NaN value in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::regression::*;
+/// let rows = [[1.0, 0.0], [2.0, 1.0], [3.0, 0.0], [4.0, 1.0], [5.0, 0.0]];
+/// let ys = [3.0, 8.0, 7.0, 12.0, 11.0];
+/// let fit = ols(rows, ys);
+/// // y = 1 + 2 a + 3 b
+/// assert!((fit.coefficients[0] - 1.0).abs() < 1e-12);
+/// assert!((fit.coefficients[1] - 2.0).abs() < 1e-12);
+/// assert!((fit.coefficients[2] - 3.0).abs() < 1e-12);
+/// assert!((fit.r_squared - 1.0).abs() < 1e-12);
+/// ```
+///
+pub fn ols<M: AsRef<[R]>, R: AsRef<[f64]>, Y: AsRef<[f64]>>(rows: M, ys: Y) -> Ols {
+    weighted_least_squares(rows.as_ref(), ys.as_ref(), None)
+}
+
+/// Calculate linear regression by weighted least squares, with a weight
+/// per row, or by ordinary least squares if there are no weights.
+///
+/// The statistics are those of the weighted fit, and the residuals are unweighted.
+pub(crate) fn weighted_least_squares<R: AsRef<[f64]>>(rows: &[R], ys: &[f64], weights: Option<&[f64]>) -> Ols {
+    let weight = |i: usize| weights.map_or(1.0, |w| w[i]);
+    let p = rows.first().map_or(0, |row| row.as_ref().len());
+    let k = p + 1;
+    let nan = |n, residuals| Ols {
+        n,
+        coefficients: vec![f64::NAN; k],
+        standard_errors: vec![f64::NAN; k],
+        t_statistics: vec![f64::NAN; k],
+        p_values: vec![f64::NAN; k],
+        r_squared: f64::NAN,
+        adjusted_r_squared: f64::NAN,
+        f_statistic: f64::NAN,
+        f_p_value: f64::NAN,
+        degrees_of_freedom: n.saturating_sub(k),
+        residual_standard_error: f64::NAN,
+        residuals,
+    };
+    if rows.len() != ys.len() || rows.iter().any(|row| row.as_ref().len() != p) {
+        return nan(0, Vec::new());
+    }
+    let kept: Vec<usize> = (0..rows.len())
+        .filter(|i| !ys[*i].is_nan() && !rows[*i].as_ref().iter().any(|x| x.is_nan()))
+        .filter(|i| weight(*i) > 0.0)
+        .collect();
+    let n = kept.len();
+    if n <= k { return nan(n, vec![f64::NAN; rows.len()]); }
+
+    // Column-major design matrix with a column of ones for the intercept,
+    // with each row scaled by the square root of its weight.
+    let mut a = vec![0.0; n * k];
+    for (r, i) in kept.iter().enumerate() {
+        let w = weight(*i).sqrt();
+        a[r] = w;
+        for (c, x) in rows[*i].as_ref().iter().enumerate() {
+            a[(c + 1) * n + r] = w * x;
+        }
+    }
+    let mut qty: Vec<f64> = kept.iter().map(|i| weight(*i).sqrt() * ys[*i]).collect();
+    if !householder_qr(&mut a, n, k, &mut qty) { return nan(n, vec![f64::NAN; rows.len()]); }
+    let r = |i: usize, j: usize| a[j * n + i];
+
+    // Solve R β = Q^T y by back substitution.
+    let mut coefficients = vec![0.0; k];
+    for i in (0..k).rev() {
+        let sum: f64 = (i + 1..k).map(|j| r(i, j) * coefficients[j]).sum();
+        coefficients[i] = (qty[i] - sum) / r(i, i);
+    }
+    // Invert R, so (X^T X)^-1 = R^-1 R^-T.
+    let mut r_inverse = vec![0.0; k * k];
+    for j in 0..k {
+        r_inverse[j * k + j] = 1.0 / r(j, j);
+        for i in (0..j).rev() {
+            let sum: f64 = (i + 1..=j).map(|m| r(i, m) * r_inverse[m * k + j]).sum();
+            r_inverse[i * k + j] = -sum / r(i, i);
+        }
+    }
+
+    let fit = Ols {
+        n,
+        coefficients,
+        standard_errors: Vec::new(),
+        t_statistics: Vec::new(),
+        p_values: Vec::new(),
+        r_squared: f64::NAN,
+        adjusted_r_squared: f64::NAN,
+        f_statistic: f64::NAN,
+        f_p_value: f64::NAN,
+        degrees_of_freedom: n - k,
+        residual_standard_error: f64::NAN,
+        residuals: Vec::new(),
+    };
+    let residuals: Vec<f64> = rows.iter().zip(ys).map(|(row, y)| y - fit.predict(row)).collect();
+    let sse: f64 = kept.iter().map(|i| weight(*i) * residuals[*i] * residuals[*i]).sum();
+    let weight_sum: f64 = kept.iter().map(|i| weight(*i)).sum();
+    let y_average = kept.iter().map(|i| weight(*i) * ys[*i]).sum::<f64>() / weight_sum;
+    let sst: f64 = kept.iter().map(|i| weight(*i) * (ys[*i] - y_average).powi(2)).sum();
+    let df = (n - k) as f64;
+    let variance = sse / df;
+    let standard_errors: Vec<f64> = (0..k)
+        .map(|i| (variance * (i..k).map(|j| r_inverse[i * k + j].powi(2)).sum::<f64>()).sqrt())
+        .collect();
+    let t_statistics: Vec<f64> = fit.coefficients.iter().zip(&standard_errors).map(|(b, se)| b / se).collect();
+    let p_values = t_statistics.iter().map(|t| 2.0 * StudentT::new(df).sf(t.abs())).collect();
+    let r_squared = 1.0 - sse / sst;
+    let f_statistic = if p == 0 { f64::NAN } else { ((sst - sse) / p as f64) / variance };
+    Ols {
+        standard_errors,
+        t_statistics,
+        p_values,
+        r_squared,
+        adjusted_r_squared: 1.0 - (1.0 - r_squared) * (n - 1) as f64 / df,
+        f_statistic,
+        f_p_value: if p == 0 { f64::NAN } else { FisherF::new(p as f64, df).sf(f_statistic) },
+        residual_standard_error: variance.sqrt(),
+        residuals,
+        ..fit
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_empty() {
+        let x: &[f64] = &[];
+        let fit = simple_linear_regression(x, x);
+        assert_eq!(fit.n, 0);
+        assert!(fit.slope.is_nan());
+        let rows: &[[f64; 1]] = &[];
+        assert!(ols(rows, x).coefficients.iter().all(|b| b.is_nan()));
+    }
+
+    #[test]
+    fn test_nan() {
+        let xs = [1.0, 2.0, f64::NAN, 3.0];
+        let ys = [3.0, 5.0, 100.0, 7.0];
+        let fit = simple_linear_regression(xs, ys);
+        assert_eq!(fit.n, 3);
+        assert_eq!(fit.slope, 2.0);
+        assert!(fit.residuals[2].is_nan());
+        assert_eq!(fit.residuals.len(), 4);
+    }
+
+    #[test]
+    fn test_simple() {
+        let fit = simple_linear_regression([1.0, 2.0, 3.0, 4.0, 5.0], [2.0, 4.0, 5.0, 4.0, 5.0]);
+        assert!((fit.r_squared - 0.6).abs() < 1e-12);
+        assert!((fit.slope_standard_error - 0.28284271247461906).abs() < 1e-12);
+        assert!((fit.intercept_standard_error - 0.938083151964686).abs() < 1e-12);
+        let expected = [-0.8, 0.6, 1.0, -0.6, -0.2];
+        assert!(fit.residuals.iter().zip(expected).all(|(a, b)| (a - b).abs() < 1e-12));
+    }
+
+    #[test]
+    fn test_ols_matches_simple() {
+        let xs = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let ys = [2.0, 4.0, 5.0, 4.0, 5.0];
+        let rows: Vec<[f64; 1]> = xs.iter().map(|x| [*x]).collect();
+        let fit = ols(&rows, ys);
+        let simple = simple_linear_regression(xs, ys);
+        assert!((fit.coefficients[0] - simple.intercept).abs() < 1e-12);
+        assert!((fit.coefficients[1] - simple.slope).abs() < 1e-12);
+        assert!((fit.standard_errors[1] - simple.slope_standard_error).abs() < 1e-12);
+        assert!((fit.t_statistics[1] - 2.1213203435596424).abs() < 1e-12);
+        assert!((fit.p_values[1] - 0.12402706265755459).abs() < 1e-12);
+        // With one predictor, F = t^2, with the same p-value.
+        assert!((fit.f_statistic - 4.5).abs() < 1e-12);
+        assert!((fit.f_p_value - fit.p_values[1]).abs() < 1e-12);
+        assert!((fit.adjusted_r_squared - 0.4666666666666667).abs() < 1e-12);
+        assert_eq!(fit.degrees_of_freedom, 3);
+    }
+
+    #[test]
+    fn test_ols_collinear() {
+        let rows = [[1.0, 2.0], [2.0, 4.0], [3.0, 6.0], [4.0, 8.0]];
+        let fit = ols(rows, [1.0, 2.0, 3.0, 5.0]);
+        assert!(fit.coefficients.iter().all(|b| b.is_nan()));
+    }
+
+    #[test]
+    fn test_ols_ill_conditioned() {
+        // Large offsets that would lose precision with the normal equations.
+        let rows: Vec<[f64; 2]> = (0..20).map(|i| [1e6 + i as f64, ((i * 7) % 5) as f64]).collect();
+        let ys: Vec<f64> = rows.iter().map(|r| 3.0 + 0.5 * r[0] - 2.0 * r[1]).collect();
+        let fit = ols(&rows, &ys);
+        assert!((fit.coefficients[1] - 0.5).abs() < 1e-9);
+        assert!((fit.coefficients[2] + 2.0).abs() < 1e-9);
+        assert!((fit.coefficients[0] - 3.0).abs() < 1e-3);
+    }
+
+}
@@ -0,0 +1,666 @@
+//! Robust regression, which resists outliers such as latency spikes:
+//! Theil–Sen, Siegel repeated medians, and Huber M-estimation.
+//!
+//! * Theil–Sen: the slope is the median of the slopes of every pair of
+//!   points. It selects the median slope with a randomized O(n log n)
+//!   algorithm, without listing the n² slopes, and has confidence bounds
+//!   and a p-value by Kendall's test.
+//!
+//! * Siegel repeated medians: the slope is the median, over each point, of
+//!   the median slope from that point to the others. It tolerates up to
+//!   half of the points as outliers, and takes O(n²).
+//!
+//! * Huber: multiple regression by iteratively reweighted least squares,
+//!   where large residuals get less weight.
+//!
+//! # Example
+//!
+//! ```rust
+//! use numeric_statistics::f64::robust_regression::*;
+//!
+//! let xs = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+//! let ys = [2.0, 4.0, 6.0, 80.0, 10.0, 12.0];
+//! let fit = theil_sen(xs, ys, 0.95);
+//! assert_eq!(fit.slope, 2.0);
+//! assert_eq!(fit.intercept, 0.0);
+//! ```
+
+use super::{
+    quantile::*,
+    regression::*,
+    special::{erfc, erfc_inv},
+};
+
+/// Result of robust simple regression y = intercept + slope × x.
+///
+/// There is no standard error of the slope: the median of slopes has no
+/// simple variance, so inference is by the distribution-free confidence
+/// bounds and p-value, which need no assumption about the errors.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RobustLine {
+    /// Count of pairs without NaN.
+    pub n: usize,
+    pub slope: f64,
+    /// Median of y - slope × x.
+    pub intercept: f64,
+    /// Confidence bounds of the slope, or NaN if the method has none.
+    pub slope_lower: f64,
+    pub slope_upper: f64,
+    /// Two-sided p-value of the test of slope 0, or NaN if the method has none.
+    pub p_value: f64,
+    /// Residual y - prediction for each input position, or NaN for a pair with NaN.
+    pub residuals: Vec<f64>,
+}
+
+impl RobustLine {
+    /// Predict y for x.
+    pub fn predict(&self, x: f64) -> f64 {
+        self.intercept + self.slope * x
+    }
+
+    fn new(xs: &[f64], ys: &[f64], px: &[f64], py: &[f64], slope: f64) -> RobustLine {
+        let intercept = median(px.iter().zip(py).map(|(x, y)| y - slope * x).collect::<Vec<f64>>());
+        RobustLine {
+            n: px.len(),
+            slope,
+            intercept,
+            slope_lower: f64::NAN,
+            slope_upper: f64::NAN,
+            p_value: f64::NAN,
+            residuals: xs.iter().zip(ys).map(|(x, y)| y - (intercept + slope * x)).collect(),
+        }
+    }
+}
+
+/// Drop each pair where either side is NaN.
+fn pairs(xs: &[f64], ys: &[f64]) -> (Vec<f64>, Vec<f64>) {
+    xs.iter().zip(ys).filter(|(x, y)| !x.is_nan() && !y.is_nan()).unzip()
+}
+
+/// Pseudorandom numbers with xorshift64, so results are deterministic.
+struct Random(u64);
+
+impl Random {
+    fn below(&mut self, n: u64) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0 % n
+    }
+}
+
+/// Fenwick tree of counts, for ranks among inserted positions.
+struct Fenwick(Vec<u64>);
+
+impl Fenwick {
+    fn new(n: usize) -> Fenwick {
+        Fenwick(vec![0; n + 1])
+    }
+
+    fn add(&mut self, i: usize) {
+        let mut i = i + 1;
+        while i < self.0.len() {
+            self.0[i] += 1;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    /// Count of inserted positions less than i.
+    fn count_below(&self, i: usize) -> u64 {
+        let (mut i, mut sum) = (i, 0);
+        while i > 0 {
+            sum += self.0[i];
+            i &= i - 1;
+        }
+        sum
+    }
+
+    /// Least position p such that the count of inserted positions up to p is at least rank.
+    fn find(&self, rank: u64) -> usize {
+        let (mut position, mut rank) = (0, rank);
+        let mut step = (self.0.len() - 1).next_power_of_two();
+        while step > 0 {
+            if position + step < self.0.len() && self.0[position + step] < rank {
+                position += step;
+                rank -= self.0[position];
+            }
+            step >>= 1;
+        }
+        position
+    }
+}
+
+/// Count of pairs a before b in the sequence with sequence[a] > sequence[b].
+fn inversions(sequence: &[usize]) -> u64 {
+    let mut fenwick = Fenwick::new(sequence.len());
+    let mut count = 0;
+    for (seen, v) in sequence.iter().enumerate() {
+        count += seen as u64 - fenwick.count_below(*v + 1);
+        fenwick.add(*v);
+    }
+    count
+}
+
+/// Selection of pairwise slopes by rank, in expected O(n log n) per rank.
+///
+/// Points are in base order, by x ascending then y descending. For a pair
+/// i < j with x[i] < x[j], the slope is at most t if and only if
+/// u[j] <= u[i] for u = y - t x. So the count of slopes at most t is the
+/// count of inversions of the order of the points by u, which merge sort
+/// or a Fenwick tree counts in O(n log n). The pairs with slopes within
+/// (lo, hi] are the pairs in a different order by u at lo than at hi,
+/// which can be sampled uniformly, or listed.
+struct SlopeSelector {
+    x: Vec<f64>,
+    y: Vec<f64>,
+    /// Pairs with equal x, which are inverted by u at every t.
+    equal_x_pairs: u64,
+    /// Pairs with equal x and unequal y, which are strictly inverted by u at every t.
+    equal_x_strict_pairs: u64,
+    /// Count of pairs with unequal x, which each have a slope.
+    count: u64,
+    random: Random,
+}
+
+impl SlopeSelector {
+    fn new(xs: &[f64], ys: &[f64]) -> SlopeSelector {
+        let mut points: Vec<(f64, f64)> = xs.iter().copied().zip(ys.iter().copied()).collect();
+        points.sort_by(|a, b| a.0.total_cmp(&b.0).then(b.1.total_cmp(&a.1)));
+        let pairs = |n: usize| (n * n.saturating_sub(1) / 2) as u64;
+        let mut equal_x_pairs = 0;
+        let mut equal_x_strict_pairs = 0;
+        for group in points.chunk_by(|a, b| a.0 == b.0) {
+            equal_x_pairs += pairs(group.len());
+            equal_x_strict_pairs += pairs(group.len()) - group.chunk_by(|a, b| a.1 == b.1).map(|g| pairs(g.len())).sum::<u64>();
+        }
+        let n = points.len();
+        SlopeSelector {
+            x: points.iter().map(|p| p.0).collect(),
+            y: points.iter().map(|p| p.1).collect(),
+            equal_x_pairs,
+            equal_x_strict_pairs,
+            count: pairs(n) - equal_x_pairs,
+            random: Random(0x9E37_79B9_7F4A_7C15),
+        }
+    }
+
+    fn slope(&self, i: usize, j: usize) -> f64 {
+        (self.y[j] - self.y[i]) / (self.x[j] - self.x[i])
+    }
+
+    /// Points in order by u = y - t x. Ties are by base index descending,
+    /// so a tie counts as an inversion; or if strict, ascending, so it does not.
+    fn order(&self, t: f64, strict: bool) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.x.len()).collect();
+        let tie = |a: &usize, b: &usize| if strict { a.cmp(b) } else { b.cmp(a) };
+        if t == f64::NEG_INFINITY {
+            order.sort_by(|a, b| self.x[*a].total_cmp(&self.x[*b]).then(tie(a, b)));
+        } else if t == f64::INFINITY {
+            order.sort_by(|a, b| self.x[*b].total_cmp(&self.x[*a]).then(tie(a, b)));
+        } else {
+            let u: Vec<f64> = self.x.iter().zip(&self.y).map(|(x, y)| y - t * x).collect();
+            order.sort_by(|a, b| u[*a].total_cmp(&u[*b]).then(tie(a, b)));
+        }
+        order
+    }
+
+    /// Count of slopes at most t, or if strict, less than t.
+    fn count_at(&self, t: f64, strict: bool) -> u64 {
+        let equal = if strict { self.equal_x_strict_pairs } else { self.equal_x_pairs };
+        inversions(&self.order(t, strict)).saturating_sub(equal)
+    }
+
+    /// Positions at lo of the points in order at hi.
+    fn positions(lo: &[usize], hi: &[usize]) -> Vec<usize> {
+        let mut position = vec![0; lo.len()];
+        lo.iter().enumerate().for_each(|(i, p)| position[*p] = i);
+        hi.iter().map(|p| position[*p]).collect()
+    }
+
+    /// Sample slopes uniformly, with replacement, from the slopes within (lo, hi].
+    fn sample(&mut self, lo: &[usize], hi: &[usize], samples: usize) -> Vec<f64> {
+        let sequence = InversionSequence::new(lo, hi);
+        if sequence.total == 0 { return Vec::new(); }
+        // Draw inversions by global rank, then find each by its position in the sequence.
+        let mut requests: Vec<(usize, u64)> = (0..samples)
+            .map(|_| {
+                let rank = self.random.below(sequence.total);
+                let q = sequence.cumulative.partition_point(|c| *c <= rank);
+                let before = if q == 0 { 0 } else { sequence.cumulative[q - 1] };
+                (q, rank - before)
+            })
+            .collect();
+        requests.sort_unstable();
+        let mut fenwick = Fenwick::new(lo.len());
+        let mut slopes = Vec::with_capacity(samples);
+        let mut next = 0;
+        for (q, v) in sequence.values.iter().enumerate() {
+            while next < requests.len() && requests[next].0 == q {
+                // The earlier positions with values greater than v, by ascending value.
+                let rank = fenwick.count_below(*v + 1) + requests[next].1 + 1;
+                let earlier = lo[fenwick.find(rank)];
+                slopes.push(self.slope(earlier.min(hi[q]), earlier.max(hi[q])));
+                next += 1;
+            }
+            fenwick.add(*v);
+        }
+        slopes
+    }
+
+    /// List the slopes within (lo, hi].
+    fn list(&self, lo: &[usize], hi: &[usize]) -> Vec<f64> {
+        let mut values = SlopeSelector::positions(lo, hi);
+        let mut buffer = values.clone();
+        let mut slopes = Vec::new();
+        self.list_inversions(&mut values, &mut buffer, lo, &mut slopes);
+        slopes
+    }
+
+    /// Merge sort positions, and push the slope of each inversion.
+    fn list_inversions(&self, values: &mut [usize], buffer: &mut [usize], lo: &[usize], slopes: &mut Vec<f64>) {
+        let n = values.len();
+        if n < 2 { return; }
+        let middle = n / 2;
+        self.list_inversions(&mut values[..middle], &mut buffer[..middle], lo, slopes);
+        self.list_inversions(&mut values[middle..], &mut buffer[middle..], lo, slopes);
+        let (mut i, mut j) = (0, middle);
+        for slot in buffer[..n].iter_mut() {
+            if j == n || (i < middle && values[i] <= values[j]) {
+                *slot = values[i];
+                i += 1;
+            } else {
+                let b = lo[values[j]];
+                for a in values[i..middle].iter().map(|v| lo[*v]) {
+                    slopes.push(self.slope(a.min(b), a.max(b)));
+                }
+                *slot = values[j];
+                j += 1;
+            }
+        }
+        values.copy_from_slice(&buffer[..n]);
+    }
+
+    /// Select the slope with the rank, from 1 for the least slope.
+    fn select(&mut self, rank: u64) -> f64 {
+        let n = self.x.len();
+        let (mut lo, mut hi) = (f64::NEG_INFINITY, f64::INFINITY);
+        let (mut count_lo, mut count_hi) = (0, self.count);
+        let (mut order_lo, mut order_hi) = (self.order(lo, false), self.order(hi, false));
+        for _ in 0..64 {
+            let within = count_hi - count_lo;
+            if within <= 4 * n as u64 { break; }
+            let samples = n.max(64);
+            let mut sample = self.sample(&order_lo, &order_hi, samples);
+            if sample.is_empty() { break; }
+            sample.sort_by(f64::total_cmp);
+            // Bracket the rank in the sample, with a margin for sampling error.
+            let target = (rank - count_lo) as f64 / within as f64 * samples as f64;
+            let margin = 2.0 * (samples as f64).sqrt();
+            let mut progress = false;
+            if target - margin >= 0.0 {
+                let t = sample[(target - margin) as usize];
+                let count = self.count_at(t, false);
+                if count < rank && count > count_lo {
+                    (lo, count_lo, order_lo, progress) = (t, count, self.order(t, false), true);
+                }
+            }
+            if ((target + margin) as usize) < sample.len() {
+                let t = sample[(target + margin) as usize];
+                let count = self.count_at(t, false);
+                if count >= rank && count < count_hi {
+                    (hi, count_hi, order_hi, progress) = (t, count, self.order(t, false), true);
+                }
+            }
+            // Many slopes may equal hi, such as for collinear points.
+            if hi.is_finite() && self.count_at(hi, true) < rank { return hi; }
+            if !progress { break; }
+        }
+        let mut slopes = self.list(&order_lo, &order_hi);
+        if slopes.is_empty() { return if hi.is_finite() { hi } else { lo }; }
+        let index = ((rank - count_lo) as usize).clamp(1, slopes.len()) - 1;
+        *slopes.select_nth_unstable_by(index, f64::total_cmp).1
+    }
+
+    /// Median of the slopes, or NaN if there are none.
+    fn median(&mut self) -> f64 {
+        match self.count {
+            0 => f64::NAN,
+            c if c % 2 == 1 => self.select(c / 2 + 1),
+            c => (self.select(c / 2) + self.select(c / 2 + 1)) / 2.0,
+        }
+    }
+}
+
+/// Positions at lo of the points in order at hi, with the cumulative
+/// count of inversions that end at each position.
+struct InversionSequence {
+    values: Vec<usize>,
+    cumulative: Vec<u64>,
+    total: u64,
+}
+
+impl InversionSequence {
+    fn new(lo: &[usize], hi: &[usize]) -> InversionSequence {
+        let values = SlopeSelector::positions(lo, hi);
+        let mut fenwick = Fenwick::new(values.len());
+        let mut cumulative = Vec::with_capacity(values.len());
+        let mut total = 0;
+        for (seen, v) in values.iter().enumerate() {
+            total += seen as u64 - fenwick.count_below(*v + 1);
+            cumulative.push(total);
+            fenwick.add(*v);
+        }
+        InversionSequence { values, cumulative, total }
+    }
+}
+
+/// Calculate Theil–Sen regression of ys on xs: the slope is the median of
+/// the slopes of every pair of points with unequal x, and the intercept is
+/// the median of y - slope × x.
+///
+/// The confidence bounds of the slope are at the confidence level, such as
+/// 0.95, by Sen's method with the normal approximation, corrected for ties.
+/// The p-value is of Kendall's test of slope 0, by the normal approximation
+/// of Kendall's S with the same variance, and continuity correction.
+///
+/// Use a randomized selection algorithm with expected O(n log n) time.
+///
+/// Return NaN statistics if every x is equal, or if xs and ys have different lengths.
+/// Return NaN bounds if the confidence is not within 0.0 to 1.0 exclusive.
+///
+/// Filter pairs with a NaN value in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::robust_regression::*;
+/// let xs: Vec<f64> = (0..100).map(|i| i as f64).collect();
+/// let ys: Vec<f64> = xs.iter().map(|x| 3.0 * x + if *x as u64 % 10 == 0 { 500.0 } else { 0.0 }).collect();
+/// let fit = theil_sen(&xs, &ys, 0.95);
+/// assert_eq!(fit.slope, 3.0);
+/// assert!(fit.slope_lower <= 3.0 && 3.0 <= fit.slope_upper);
+/// ```
+///
+pub fn theil_sen<X: AsRef<[f64]>, Y: AsRef<[f64]>>(xs: X, ys: Y, confidence: f64) -> RobustLine {
+    let (xs, ys) = (xs.as_ref(), ys.as_ref());
+    if xs.len() != ys.len() { return RobustLine::new(&[], &[], &[], &[], f64::NAN); }
+    let (px, py) = pairs(xs, ys);
+    let mut selector = SlopeSelector::new(&px, &py);
+    let mut line = RobustLine::new(xs, ys, &px, &py, selector.median());
+    let count = selector.count;
+    if count == 0 { return line; }
+    // Variance of Kendall's S, corrected for ties in x and in y.
+    let ties = |values: &[f64]| -> f64 {
+        let mut sorted = values.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        sorted.chunk_by(|a, b| a == b).map(|g| g.len() as f64).map(|t| t * (t - 1.0) * (2.0 * t + 5.0)).sum()
+    };
+    let n = px.len() as f64;
+    let variance = (n * (n - 1.0) * (2.0 * n + 5.0) - ties(&px) - ties(&py)) / 18.0;
+    // S is the count of pairs with positive slope less the count with
+    // negative slope, which step by 2, so the continuity correction is 1.
+    let s = (count - selector.count_at(0.0, false)) as f64 - selector.count_at(0.0, true) as f64;
+    let z = if s == 0.0 { 0.0 } else { (s.abs() - 1.0) / variance.sqrt() };
+    line.p_value = if variance > 0.0 { erfc(z / std::f64::consts::SQRT_2) } else { f64::NAN };
+    if !(confidence > 0.0 && confidence < 1.0) { return line; }
+    let z = std::f64::consts::SQRT_2 * erfc_inv(1.0 - confidence);
+    let spread = z * variance.sqrt();
+    let rank = |r: f64| (r.round().max(1.0) as u64).min(count);
+    line.slope_lower = selector.select(rank((count as f64 - spread) / 2.0));
+    line.slope_upper = selector.select(rank((count as f64 + spread) / 2.0 + 1.0));
+    line
+}
+
+/// Calculate Siegel repeated medians regression of ys on xs: the slope is
+/// the median over each point of the median slope from that point to each
+/// other point with unequal x, and the intercept is the median of
+/// y - slope × x.
+///
+/// Return NaN statistics if every x is equal, or if xs and ys have different lengths.
+///
+/// Filter pairs with a NaN value in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::robust_regression::*;
+/// let xs = [1.0, 2.0, 3.0, 4.0, 5.0];
+/// let ys = [1.0, 2.0, 30.0, 4.0, 5.0];
+/// let fit = siegel_repeated_medians(xs, ys);
+/// assert_eq!(fit.slope, 1.0);
+/// assert_eq!(fit.intercept, 0.0);
+/// ```
+///
+pub fn siegel_repeated_medians<X: AsRef<[f64]>, Y: AsRef<[f64]>>(xs: X, ys: Y) -> RobustLine {
+    let (xs, ys) = (xs.as_ref(), ys.as_ref());
+    if xs.len() != ys.len() { return RobustLine::new(&[], &[], &[], &[], f64::NAN); }
+    let (px, py) = pairs(xs, ys);
+    let mut slopes = Vec::with_capacity(px.len());
+    let medians: Vec<f64> = (0..px.len())
+        .map(|i| {
+            slopes.clear();
+            slopes.extend((0..px.len()).filter(|j| px[*j] != px[i]).map(|j| (py[j] - py[i]) / (px[j] - px[i])));
+            median(&slopes)
+        })
+        .collect();
+    RobustLine::new(xs, ys, &px, &py, median(medians))
+}
+
+/// Default Huber tuning constant, which has 95% efficiency for normal errors.
+pub const HUBER_TUNING: f64 = 1.345;
+
+/// Result of Huber M-estimator regression.
+///
+/// Coefficient 0 is the intercept, and coefficient i is for predictor i - 1.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Huber {
+    /// Count of rows without NaN.
+    pub n: usize,
+    pub coefficients: Vec<f64>,
+    /// Standard errors of the final weighted least squares fit.
+    pub standard_errors: Vec<f64>,
+    pub t_statistics: Vec<f64>,
+    /// Two-sided p-values of the t statistics.
+    pub p_values: Vec<f64>,
+    /// Robust scale of the residuals, as the normalized median absolute deviation.
+    pub scale: f64,
+    /// Final weight of each input row, from 0.0 to 1.0, or NaN for a row with NaN.
+    pub weights: Vec<f64>,
+    pub iterations: usize,
+    pub converged: bool,
+    /// Residual y - prediction for each input row, or NaN for a row with NaN.
+    pub residuals: Vec<f64>,
+}
+
+impl Huber {
+    /// Predict y for one row of predictors.
+    ///
+    /// Return NaN if the row has the wrong length.
+    pub fn predict<R: AsRef<[f64]>>(&self, row: R) -> f64 {
+        let row = row.as_ref();
+        if row.len() + 1 != self.coefficients.len() { return f64::NAN; }
+        self.coefficients[0] + row.iter().zip(&self.coefficients[1..]).map(|(x, b)| x * b).sum::<f64>()
+    }
+}
+
+/// Calculate Huber M-estimator regression of ys on rows of predictors,
+/// with an intercept, by iteratively reweighted least squares (IRLS).
+///
+/// Start from ordinary least squares. At each iteration, estimate the scale
+/// s of the residuals by the median absolute deviation divided by 0.6745,
+/// and give each row the weight min(1, tuning / |residual / s|), such as
+/// with tuning `HUBER_TUNING`. Stop when no coefficient changes by more than
+/// 1e-10 relative, or after `max_iterations`.
+///
+/// Return NaN statistics as for `ols`.
+///
+/// Filter rows with a NaN value in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::robust_regression::*;
+/// let rows: Vec<[f64; 1]> = (0..20).map(|i| [i as f64]).collect();
+/// let mut ys: Vec<f64> = (0..20).map(|i| 1.0 + 2.0 * i as f64 + [0.1, -0.1][i % 2]).collect();
+/// ys[7] = 500.0;
+/// let fit = huber(&rows, &ys, HUBER_TUNING, 100);
+/// assert!(fit.converged);
+/// assert!((fit.coefficients[1] - 2.0).abs() < 0.01);
+/// assert!(fit.weights[7] < 0.01);
+/// ```
+///
+pub fn huber<M: AsRef<[R]>, R: AsRef<[f64]>, Y: AsRef<[f64]>>(rows: M, ys: Y, tuning: f64, max_iterations: usize) -> Huber {
+    let (rows, ys) = (rows.as_ref(), ys.as_ref());
+    let mut fit = weighted_least_squares(rows, ys, None);
+    let mut weights: Vec<f64> = fit.residuals.iter().map(|e| if e.is_nan() { f64::NAN } else { 1.0 }).collect();
+    let mut scale = f64::NAN;
+    let (mut iterations, mut converged) = (0, false);
+    while iterations < max_iterations && !fit.coefficients[0].is_nan() {
+        iterations += 1;
+        let residuals: Vec<f64> = fit.residuals.iter().copied().filter(|e| !e.is_nan()).collect();
+        let center = median(&residuals);
+        scale = median(residuals.iter().map(|e| (e - center).abs()).collect::<Vec<f64>>()) / 0.6745;
+        if scale.is_nan() || scale <= 0.0 { converged = true; break; }
+        weights = fit.residuals
+            .iter()
+            .map(|e| {
+                let z = (e / scale).abs();
+                if z <= tuning { 1.0 } else { tuning / z }
+            })
+            .collect();
+        let zero_nan: Vec<f64> = weights.iter().map(|w| if w.is_nan() { 0.0 } else { *w }).collect();
+        let next = weighted_least_squares(rows, ys, Some(&zero_nan));
+        converged = next.coefficients
+            .iter()
+            .zip(&fit.coefficients)
+            .all(|(a, b)| (a - b).abs() <= 1e-10 * a.abs().max(b.abs()).max(1.0));
+        fit = next;
+        if converged { break; }
+    }
+    Huber {
+        n: fit.n,
+        coefficients: fit.coefficients,
+        standard_errors: fit.standard_errors,
+        t_statistics: fit.t_statistics,
+        p_values: fit.p_values,
+        scale,
+        weights,
+        iterations,
+        converged,
+        residuals: fit.residuals,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Median of every pairwise slope, in O(n^2).
+    fn theil_sen_naive(xs: &[f64], ys: &[f64]) -> f64 {
+        let mut slopes = Vec::new();
+        for i in 0..xs.len() {
+            for j in i + 1..xs.len() {
+                if xs[i] != xs[j] { slopes.push((ys[j] - ys[i]) / (xs[j] - xs[i])); }
+            }
+        }
+        median(slopes)
+    }
+
+    #[test]
+    fn test_empty() {
+        let x: &[f64] = &[];
+        assert!(theil_sen(x, x, 0.95).slope.is_nan());
+        assert!(siegel_repeated_medians(x, x).slope.is_nan());
+        let rows: &[[f64; 1]] = &[];
+        assert!(huber(rows, x, HUBER_TUNING, 10).coefficients[0].is_nan());
+    }
+
+    #[test]
+    fn test_nan() {
+        let xs = [1.0, 2.0, f64::NAN, 3.0];
+        let ys = [2.0, 4.0, 0.0, 6.0];
+        let fit = theil_sen(xs, ys, 0.95);
+        assert_eq!(fit.n, 3);
+        assert_eq!(fit.slope, 2.0);
+        assert!(fit.residuals[2].is_nan());
+    }
+
+    #[test]
+    fn test_theil_sen_matches_naive() {
+        for n in [2, 3, 10, 101, 400] {
+            let xs: Vec<f64> = (0..n).map(|i| ((i * 7919) % 53) as f64).collect();
+            let ys: Vec<f64> = (0..n).map(|i| ((i * 104729) % 31) as f64 * 0.5 + xs[i]).collect();
+            let fit = theil_sen(&xs, &ys, 0.95);
+            assert_eq!(fit.slope, theil_sen_naive(&xs, &ys), "n = {}", n);
+        }
+    }
+
+    #[test]
+    fn test_theil_sen_collinear() {
+        let xs: Vec<f64> = (0..500).map(|i| i as f64).collect();
+        let ys: Vec<f64> = xs.iter().map(|x| 0.5 * x + 1.0).collect();
+        let fit = theil_sen(&xs, &ys, 0.95);
+        assert_eq!(fit.slope, 0.5);
+        assert_eq!(fit.slope_lower, 0.5);
+        assert_eq!(fit.slope_upper, 0.5);
+        assert_eq!(fit.intercept, 1.0);
+    }
+
+    #[test]
+    fn test_theil_sen_bounds() {
+        let xs: Vec<f64> = (0..60).map(|i| i as f64).collect();
+        let ys: Vec<f64> = xs.iter().map(|x| x + ((x * 7.0) % 11.0)).collect();
+        let fit = theil_sen(&xs, &ys, 0.95);
+        let wide = theil_sen(&xs, &ys, 0.99);
+        assert!(fit.slope_lower < fit.slope && fit.slope < fit.slope_upper);
+        assert!(wide.slope_lower <= fit.slope_lower && fit.slope_upper <= wide.slope_upper);
+        assert_eq!(fit.p_value, wide.p_value);
+    }
+
+    #[test]
+    fn test_theil_sen_p_value() {
+        // S = 8 - 2 = 6 of 10 pairs, with variance 50 / 3 and no ties.
+        let xs = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let ys = [2.0, 1.0, 4.0, 3.0, 5.0];
+        let fit = theil_sen(xs, ys, 0.95);
+        assert!((fit.p_value - 0.2206713619198468).abs() < 1e-14);
+        // Reversing y negates S.
+        let reversed: Vec<f64> = ys.iter().map(|y| -y).collect();
+        assert_eq!(theil_sen(xs, &reversed, 0.95).p_value, fit.p_value);
+        // Ties in x and in y: S = 4 - 0 of 5 pairs with unequal x, with
+        // variance (156 - 18 - 18) / 18 = 20 / 3.
+        let xs = [1.0, 1.0, 2.0, 3.0];
+        let ys = [1.0, 2.0, 2.0, 3.0];
+        let fit = theil_sen(xs, ys, f64::NAN);
+        assert!((fit.p_value - 0.24527811680677283).abs() < 1e-14);
+        assert!(fit.slope_lower.is_nan());
+        // A line of 500 points, with S at its greatest.
+        let xs: Vec<f64> = (0..500).map(|i| i as f64).collect();
+        assert!(theil_sen(&xs, &xs, 0.95).p_value < 1e-100);
+        // No p-value if every y is equal.
+        assert!(theil_sen(&xs, vec![1.0; 500], 0.95).p_value.is_nan());
+    }
+
+    #[test]
+    fn test_siegel() {
+        let xs = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0];
+        let ys = [3.0, 5.0, 7.0, -100.0, 11.0, 100.0, 15.0];
+        let fit = siegel_repeated_medians(xs, ys);
+        assert_eq!(fit.slope, 2.0);
+        assert_eq!(fit.intercept, 1.0);
+        assert!(fit.slope_lower.is_nan());
+        assert!(fit.p_value.is_nan());
+    }
+
+    #[test]
+    fn test_huber_without_outliers_is_ols() {
+        let rows = [[1.0], [2.0], [3.0], [4.0], [5.0]];
+        let ys = [2.0, 4.0, 5.0, 4.0, 5.0];
+        let fit = huber(rows, ys, 100.0, 10);
+        let expected = ols(rows, ys);
+        assert!(fit.converged);
+        assert!((fit.coefficients[1] - expected.coefficients[1]).abs() < 1e-12);
+        assert!(fit.weights.iter().all(|w| *w == 1.0));
+    }
+
+}
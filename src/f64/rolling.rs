@@ -0,0 +1,399 @@
+//! Rolling window statistics over the last N samples: sum, mean,
+//! variance, standard deviation, min, and max.
+//!
+//! Each push is O(1) amortized: the sum and the variance use a windowed
+//! Welford update that adds the new sample and removes the oldest sample,
+//! and the min and the max use monotonic deques. Infinite samples are
+//! counted rather than added, so the sums recover once they leave the window.
+//!
+//! Use the `Rolling` struct for streaming, or the slice functions such as
+//! `rolling_mean` that return one result per input position. A position
+//! before the first full window has the result NaN.
+//!
+//! # Example
+//!
+//! ```rust
+//! use numeric_statistics::f64::rolling::*;
+//!
+//! let mut rolling = Rolling::new(3);
+//! for x in [1.0, 2.0, 4.0, 8.0] {
+//!     rolling.push(x);
+//! }
+//! assert_eq!(rolling.mean(), 14.0 / 3.0);
+//! assert_eq!(rolling.min(), 2.0);
+//! assert_eq!(rolling.max(), 8.0);
+//!
+//! let means = rolling_mean(&[1.0, 2.0, 4.0, 8.0], 2);
+//! assert!(means[0].is_nan());
+//! assert_eq!(&means[1..], &[1.5, 3.0, 6.0]);
+//! ```
+
+use std::collections::VecDeque;
+use super::summary::Moments;
+
+/// Streaming rolling window statistics over the last `window` samples.
+///
+/// NaN samples take a position in the window, but are filtered from the
+/// statistics, so a window of only NaN samples has NaN statistics.
+#[derive(Debug, Clone)]
+pub struct Rolling {
+    window: usize,
+    samples: VecDeque<f64>,
+    /// Count of pushes, which is the index of the next sample.
+    pushed: usize,
+    /// Count of non-NaN samples in the window.
+    len: usize,
+    /// Counts of positive and negative infinite samples in the window,
+    /// which are not in the sum or the moments.
+    positive_infinities: usize,
+    negative_infinities: usize,
+    sum: f64,
+    /// Moments of the finite samples in the window.
+    moments: Moments,
+    /// Indices and values of candidates for the min, with ascending values.
+    mins: VecDeque<(usize, f64)>,
+    /// Indices and values of candidates for the max, with descending values.
+    maxs: VecDeque<(usize, f64)>,
+}
+
+impl Rolling {
+    /// Create rolling statistics over the last `window` samples.
+    ///
+    /// # Panics
+    ///
+    /// Panic if the window is 0.
+    ///
+    pub fn new(window: usize) -> Rolling {
+        assert!(window > 0, "window must be positive");
+        Rolling {
+            window,
+            samples: VecDeque::with_capacity(window),
+            pushed: 0,
+            len: 0,
+            positive_infinities: 0,
+            negative_infinities: 0,
+            sum: 0.0,
+            moments: Moments::default(),
+            mins: VecDeque::new(),
+            maxs: VecDeque::new(),
+        }
+    }
+
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// True if the window has `window` samples.
+    pub fn is_full(&self) -> bool {
+        self.samples.len() == self.window
+    }
+
+    /// Count of non-NaN samples in the window.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// True if the window has no non-NaN samples.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Push a sample, evicting the oldest sample if the window is full.
+    pub fn push(&mut self, x: f64) {
+        if self.is_full() {
+            let oldest = self.samples.pop_front().unwrap();
+            self.remove(oldest);
+        }
+        let index = self.pushed;
+        self.pushed += 1;
+        self.samples.push_back(x);
+        if x.is_nan() { return; }
+        self.len += 1;
+        while self.mins.back().is_some_and(|(_, m)| *m >= x) { self.mins.pop_back(); }
+        self.mins.push_back((index, x));
+        while self.maxs.back().is_some_and(|(_, m)| *m <= x) { self.maxs.pop_back(); }
+        self.maxs.push_back((index, x));
+        if x == f64::INFINITY {
+            self.positive_infinities += 1;
+            return;
+        }
+        if x == f64::NEG_INFINITY {
+            self.negative_infinities += 1;
+            return;
+        }
+        self.sum += x;
+        self.moments.push(x);
+    }
+
+    /// Remove the oldest sample from the statistics.
+    fn remove(&mut self, x: f64) {
+        let index = self.pushed - self.window;
+        if self.mins.front().is_some_and(|(i, _)| *i == index) { self.mins.pop_front(); }
+        if self.maxs.front().is_some_and(|(i, _)| *i == index) { self.maxs.pop_front(); }
+        if x.is_nan() { return; }
+        self.len -= 1;
+        if x == f64::INFINITY {
+            self.positive_infinities -= 1;
+            return;
+        }
+        if x == f64::NEG_INFINITY {
+            self.negative_infinities -= 1;
+            return;
+        }
+        self.moments.remove(x);
+        self.sum = if self.moments.count() == 0 { 0.0 } else { self.sum - x };
+    }
+
+    /// Infinity of the sign of the infinite samples in the window, NaN if
+    /// both signs, or None if the window has no infinite samples.
+    fn infinity(&self) -> Option<f64> {
+        match (self.positive_infinities > 0, self.negative_infinities > 0) {
+            (false, false) => None,
+            (true, false) => Some(f64::INFINITY),
+            (false, true) => Some(f64::NEG_INFINITY),
+            (true, true) => Some(f64::NAN),
+        }
+    }
+
+    /// Sum of the window, or NaN if the window has no non-NaN samples.
+    pub fn sum(&self) -> f64 {
+        if self.len == 0 { f64::NAN } else { self.infinity().unwrap_or(self.sum) }
+    }
+
+    /// Average of the window, or NaN if the window has no non-NaN samples.
+    pub fn mean(&self) -> f64 {
+        if self.len == 0 { f64::NAN } else { self.infinity().unwrap_or(self.moments.average()) }
+    }
+
+    /// Sample variance of the window, or NaN if the window has no non-NaN
+    /// samples or has an infinite sample.
+    pub fn variance(&self) -> f64 {
+        if self.infinity().is_some() { f64::NAN } else { self.moments.variance() }
+    }
+
+    /// Sample standard deviation of the window, or NaN if the window has no non-NaN samples.
+    pub fn standard_deviation(&self) -> f64 {
+        self.variance().sqrt()
+    }
+
+    /// Min of the window, or NaN if the window has no non-NaN samples.
+    pub fn min(&self) -> f64 {
+        self.mins.front().map_or(f64::NAN, |(_, x)| *x)
+    }
+
+    /// Max of the window, or NaN if the window has no non-NaN samples.
+    pub fn max(&self) -> f64 {
+        self.maxs.front().map_or(f64::NAN, |(_, x)| *x)
+    }
+}
+
+/// Apply a statistic of the rolling window at each position.
+fn rolling_map<T: AsRef<[f64]>>(values: T, window: usize, statistic: fn(&Rolling) -> f64) -> Vec<f64> {
+    let values = values.as_ref();
+    if window == 0 { return vec![f64::NAN; values.len()]; }
+    let mut rolling = Rolling::new(window);
+    values
+        .iter()
+        .map(|x| {
+            rolling.push(*x);
+            if rolling.is_full() { statistic(&rolling) } else { f64::NAN }
+        })
+        .collect()
+}
+
+/// Calculate rolling sum for values, over each window of `window` values.
+///
+/// Return one result per position, NaN before the first full window.
+/// Return all NaN if the window is 0.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::rolling::*;
+/// let sums = rolling_sum(&[1.0, 2.0, 4.0, 8.0], 2);
+/// assert_eq!(&sums[1..], &[3.0, 6.0, 12.0]);
+/// ```
+///
+pub fn rolling_sum<T: AsRef<[f64]>>(values: T, window: usize) -> Vec<f64> {
+    rolling_map(values, window, Rolling::sum)
+}
+
+/// Calculate rolling mean for values, over each window of `window` values.
+///
+/// Return one result per position, NaN before the first full window.
+/// Return all NaN if the window is 0.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::rolling::*;
+/// let means = rolling_mean(&[1.0, 2.0, 4.0, 8.0], 2);
+/// assert_eq!(&means[1..], &[1.5, 3.0, 6.0]);
+/// ```
+///
+pub fn rolling_mean<T: AsRef<[f64]>>(values: T, window: usize) -> Vec<f64> {
+    rolling_map(values, window, Rolling::mean)
+}
+
+/// Calculate rolling sample variance for values, over each window of `window` values.
+///
+/// Return one result per position, NaN before the first full window.
+/// Return all NaN if the window is 0.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::rolling::*;
+/// let variances = rolling_variance(&[1.0, 2.0, 4.0, 8.0], 2);
+/// assert_eq!(&variances[1..], &[0.5, 2.0, 8.0]);
+/// ```
+///
+pub fn rolling_variance<T: AsRef<[f64]>>(values: T, window: usize) -> Vec<f64> {
+    rolling_map(values, window, Rolling::variance)
+}
+
+/// Calculate rolling sample standard deviation for values, over each window of `window` values.
+///
+/// Return one result per position, NaN before the first full window.
+/// Return all NaN if the window is 0.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::rolling::*;
+/// let deviations = rolling_standard_deviation(&[1.0, 3.0, 5.0], 2);
+/// assert_eq!(&deviations[1..], &[2f64.sqrt(), 2f64.sqrt()]);
+/// ```
+///
+pub fn rolling_standard_deviation<T: AsRef<[f64]>>(values: T, window: usize) -> Vec<f64> {
+    rolling_map(values, window, Rolling::standard_deviation)
+}
+
+/// Calculate rolling min for values, over each window of `window` values.
+///
+/// Return one result per position, NaN before the first full window.
+/// Return all NaN if the window is 0.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::rolling::*;
+/// let mins = rolling_min(&[4.0, 2.0, 8.0, 1.0], 2);
+/// assert_eq!(&mins[1..], &[2.0, 2.0, 1.0]);
+/// ```
+///
+pub fn rolling_min<T: AsRef<[f64]>>(values: T, window: usize) -> Vec<f64> {
+    rolling_map(values, window, Rolling::min)
+}
+
+/// Calculate rolling max for values, over each window of `window` values.
+///
+/// Return one result per position, NaN before the first full window.
+/// Return all NaN if the window is 0.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::rolling::*;
+/// let maxs = rolling_max(&[4.0, 2.0, 8.0, 1.0], 2);
+/// assert_eq!(&maxs[1..], &[4.0, 8.0, 8.0]);
+/// ```
+///
+pub fn rolling_max<T: AsRef<[f64]>>(values: T, window: usize) -> Vec<f64> {
+    rolling_map(values, window, Rolling::max)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::f64::{average, variance, min, max};
+
+    #[test]
+    fn test_empty() {
+        let x: &[f64] = &[];
+        assert!(rolling_mean(x, 3).is_empty());
+        let rolling = Rolling::new(3);
+        assert!(rolling.mean().is_nan());
+        assert!(rolling.min().is_nan());
+    }
+
+    #[test]
+    fn test_window_zero() {
+        let x = &[1.0, 2.0];
+        assert!(rolling_sum(x, 0).iter().all(|x| x.is_nan()));
+    }
+
+    #[test]
+    fn test_nan() {
+        let x = &[1.0, f64::NAN, f64::NAN, 4.0];
+        let means = rolling_mean(x, 2);
+        assert_eq!(means[1], 1.0);
+        assert!(means[2].is_nan());
+        assert_eq!(means[3], 4.0);
+        assert!(rolling_max(x, 2)[2].is_nan());
+    }
+
+    #[test]
+    fn test_infinity() {
+        let x = &[1.0, f64::INFINITY, 2.0, 3.0, 4.0, 5.0];
+        let means = rolling_mean(x, 2);
+        assert!(means[0].is_nan());
+        assert_eq!(&means[1..], &[f64::INFINITY, f64::INFINITY, 2.5, 3.5, 4.5]);
+        let variances = rolling_variance(x, 2);
+        assert!(variances[2].is_nan());
+        assert_eq!(variances[3], 0.5);
+        let y = &[f64::NEG_INFINITY, 1.0, f64::INFINITY, 2.0, 3.0, 4.0];
+        let sums = rolling_sum(y, 3);
+        assert!(sums[2].is_nan());
+        assert_eq!(sums[3], f64::INFINITY);
+        assert_eq!(sums[4], f64::INFINITY);
+        assert_eq!(sums[5], 9.0);
+        assert_eq!(rolling_max(x, 2)[2], f64::INFINITY);
+    }
+
+    #[test]
+    fn test_matches_naive() {
+        let x: Vec<f64> = (0..200).map(|i| ((i * 7919) % 101) as f64 * 0.37 + 1e6).collect();
+        let window = 17;
+        let sums = rolling_sum(&x, window);
+        let means = rolling_mean(&x, window);
+        let variances = rolling_variance(&x, window);
+        let mins = rolling_min(&x, window);
+        let maxs = rolling_max(&x, window);
+        for i in window - 1..x.len() {
+            let w = &x[i + 1 - window..=i];
+            assert!((sums[i] - w.iter().sum::<f64>()).abs() < 1e-6);
+            assert!((means[i] - average(w)).abs() < 1e-8);
+            assert!((variances[i] - variance(w)).abs() < 1e-6);
+            assert_eq!(mins[i], min(w));
+            assert_eq!(maxs[i], max(w));
+        }
+    }
+
+    #[test]
+    fn test_streaming_evicts() {
+        let mut rolling = Rolling::new(2);
+        rolling.push(10.0);
+        rolling.push(1.0);
+        assert!(rolling.is_full());
+        assert_eq!(rolling.max(), 10.0);
+        rolling.push(2.0);
+        assert_eq!(rolling.max(), 2.0);
+        assert_eq!(rolling.min(), 1.0);
+        assert_eq!(rolling.len(), 2);
+        assert_eq!(rolling.variance(), 0.5);
+    }
+
+}
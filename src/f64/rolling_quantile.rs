@@ -0,0 +1,354 @@
+//! Rolling window median and quantiles, with insertion and deletion.
+//!
+//! The window keeps its samples in an order-statistic tree, which is a
+//! randomized balanced binary search tree (a treap) where each node knows
+//! its subtree size. Each push inserts the new sample and deletes the
+//! oldest sample in O(log w), and any quantile takes O(log w).
+//!
+//! Quantiles use the same linear interpolation as `quantile`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use numeric_statistics::f64::rolling_quantile::*;
+//!
+//! let mut rolling = RollingQuantile::new(3);
+//! for x in [1.0, 100.0, 2.0, 3.0] {
+//!     rolling.push(x);
+//! }
+//! assert_eq!(rolling.median(), 3.0);
+//!
+//! let medians = rolling_median(&[1.0, 100.0, 2.0, 3.0, 4.0], 3);
+//! assert_eq!(&medians[2..], &[2.0, 3.0, 3.0]);
+//! ```
+
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+
+/// Index of no node.
+const NIL: usize = usize::MAX;
+
+#[derive(Debug, Clone)]
+struct Node {
+    value: f64,
+    /// Unique id, so equal values have distinct keys.
+    id: u64,
+    priority: u64,
+    size: usize,
+    left: usize,
+    right: usize,
+}
+
+/// Order-statistic tree of values, which supports insert, remove, and
+/// select by rank, each in O(log n) expected time.
+#[derive(Debug, Clone)]
+pub struct OrderStatisticTree {
+    nodes: Vec<Node>,
+    free: Vec<usize>,
+    root: usize,
+    seed: u64,
+}
+
+impl Default for OrderStatisticTree {
+    fn default() -> Self {
+        OrderStatisticTree::new()
+    }
+}
+
+impl OrderStatisticTree {
+    pub fn new() -> OrderStatisticTree {
+        OrderStatisticTree { nodes: Vec::new(), free: Vec::new(), root: NIL, seed: 0x9E37_79B9_7F4A_7C15 }
+    }
+
+    /// Count of values.
+    pub fn len(&self) -> usize {
+        self.size(self.root)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root == NIL
+    }
+
+    /// Insert a value with an id that is unique among the values in the tree.
+    pub fn insert(&mut self, value: f64, id: u64) {
+        let priority = self.next_priority();
+        let node = Node { value, id, priority, size: 1, left: NIL, right: NIL };
+        let index = match self.free.pop() {
+            Some(index) => { self.nodes[index] = node; index }
+            None => { self.nodes.push(node); self.nodes.len() - 1 }
+        };
+        let (left, right) = self.split(self.root, (value, id), false);
+        let left = self.merge(left, index);
+        self.root = self.merge(left, right);
+    }
+
+    /// Remove the value with the id. Return true if it was in the tree.
+    pub fn remove(&mut self, value: f64, id: u64) -> bool {
+        let (left, rest) = self.split(self.root, (value, id), false);
+        let (middle, right) = self.split(rest, (value, id), true);
+        let found = middle != NIL;
+        if found { self.free.push(middle); }
+        self.root = self.merge(left, right);
+        found
+    }
+
+    /// Select the value with the rank, where rank 0 is the least value.
+    ///
+    /// Return None if the rank is not less than the count of values.
+    pub fn select(&self, rank: usize) -> Option<f64> {
+        let mut t = self.root;
+        let mut rank = rank;
+        while t != NIL {
+            let left = self.size(self.nodes[t].left);
+            match rank.cmp(&left) {
+                Ordering::Less => t = self.nodes[t].left,
+                Ordering::Equal => return Some(self.nodes[t].value),
+                Ordering::Greater => {
+                    rank -= left + 1;
+                    t = self.nodes[t].right;
+                }
+            }
+        }
+        None
+    }
+
+    /// Calculate the quantile with linear interpolation between ranks.
+    ///
+    /// Return NaN if the tree is empty, or if p is not within 0.0 to 1.0.
+    pub fn quantile(&self, p: f64) -> f64 {
+        let n = self.len();
+        if n == 0 || !(0.0..=1.0).contains(&p) { return f64::NAN; }
+        let h = (n - 1) as f64 * p;
+        let lo = self.select(h.floor() as usize).unwrap();
+        let hi = self.select(h.ceil() as usize).unwrap();
+        lo + (h - h.floor()) * (hi - lo)
+    }
+
+    fn size(&self, t: usize) -> usize {
+        if t == NIL { 0 } else { self.nodes[t].size }
+    }
+
+    fn update(&mut self, t: usize) {
+        self.nodes[t].size = 1 + self.size(self.nodes[t].left) + self.size(self.nodes[t].right);
+    }
+
+    /// Compare the node key with a key, by value in total order, then by id.
+    fn cmp(&self, t: usize, key: (f64, u64)) -> Ordering {
+        self.nodes[t].value.total_cmp(&key.0).then(self.nodes[t].id.cmp(&key.1))
+    }
+
+    /// Split a subtree into keys less than the key, and the other keys;
+    /// or if inclusive, into keys less than or equal to the key, and the other keys.
+    fn split(&mut self, t: usize, key: (f64, u64), inclusive: bool) -> (usize, usize) {
+        if t == NIL { return (NIL, NIL); }
+        let goes_left = match self.cmp(t, key) {
+            Ordering::Less => true,
+            Ordering::Equal => inclusive,
+            Ordering::Greater => false,
+        };
+        if goes_left {
+            let (left, right) = self.split(self.nodes[t].right, key, inclusive);
+            self.nodes[t].right = left;
+            self.update(t);
+            (t, right)
+        } else {
+            let (left, right) = self.split(self.nodes[t].left, key, inclusive);
+            self.nodes[t].left = right;
+            self.update(t);
+            (left, t)
+        }
+    }
+
+    /// Merge two subtrees, where every key in the left is less than every key in the right.
+    fn merge(&mut self, left: usize, right: usize) -> usize {
+        if left == NIL { return right; }
+        if right == NIL { return left; }
+        if self.nodes[left].priority > self.nodes[right].priority {
+            self.nodes[left].right = self.merge(self.nodes[left].right, right);
+            self.update(left);
+            left
+        } else {
+            self.nodes[right].left = self.merge(left, self.nodes[right].left);
+            self.update(right);
+            right
+        }
+    }
+
+    /// Next pseudorandom priority, with xorshift64, so results are deterministic.
+    fn next_priority(&mut self) -> u64 {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 7;
+        self.seed ^= self.seed << 17;
+        self.seed
+    }
+}
+
+/// Streaming rolling window quantiles over the last `window` samples.
+///
+/// NaN samples take a position in the window, but are filtered from the
+/// quantiles, so a window of only NaN samples has NaN quantiles.
+#[derive(Debug, Clone)]
+pub struct RollingQuantile {
+    window: usize,
+    samples: VecDeque<(f64, u64)>,
+    tree: OrderStatisticTree,
+    pushed: u64,
+}
+
+impl RollingQuantile {
+    /// Create rolling quantiles over the last `window` samples.
+    ///
+    /// # Panics
+    ///
+    /// Panic if the window is 0.
+    ///
+    pub fn new(window: usize) -> RollingQuantile {
+        assert!(window > 0, "window must be positive");
+        RollingQuantile {
+            window,
+            samples: VecDeque::with_capacity(window),
+            tree: OrderStatisticTree::new(),
+            pushed: 0,
+        }
+    }
+
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// True if the window has `window` samples.
+    pub fn is_full(&self) -> bool {
+        self.samples.len() == self.window
+    }
+
+    /// Count of non-NaN samples in the window.
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    /// True if the window has no non-NaN samples.
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    /// Push a sample, evicting the oldest sample if the window is full.
+    pub fn push(&mut self, x: f64) {
+        if self.is_full() {
+            let (oldest, id) = self.samples.pop_front().unwrap();
+            if !oldest.is_nan() { self.tree.remove(oldest, id); }
+        }
+        let id = self.pushed;
+        self.pushed += 1;
+        self.samples.push_back((x, id));
+        if !x.is_nan() { self.tree.insert(x, id); }
+    }
+
+    /// Quantile of the window, such as 0.5 for the median.
+    ///
+    /// Return NaN if the window has no non-NaN samples, or if p is not within 0.0 to 1.0.
+    pub fn quantile(&self, p: f64) -> f64 {
+        self.tree.quantile(p)
+    }
+
+    /// Median of the window, or NaN if the window has no non-NaN samples.
+    pub fn median(&self) -> f64 {
+        self.tree.quantile(0.5)
+    }
+}
+
+/// Calculate rolling quantile for values, over each window of `window` values.
+///
+/// Return one result per position, NaN before the first full window.
+/// Return all NaN if the window is 0.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::rolling_quantile::*;
+/// let maxs = rolling_quantile(&[1.0, 3.0, 2.0, 5.0], 2, 1.0);
+/// assert_eq!(&maxs[1..], &[3.0, 3.0, 5.0]);
+/// ```
+///
+pub fn rolling_quantile<T: AsRef<[f64]>>(values: T, window: usize, p: f64) -> Vec<f64> {
+    let values = values.as_ref();
+    if window == 0 { return vec![f64::NAN; values.len()]; }
+    let mut rolling = RollingQuantile::new(window);
+    values
+        .iter()
+        .map(|x| {
+            rolling.push(*x);
+            if rolling.is_full() { rolling.quantile(p) } else { f64::NAN }
+        })
+        .collect()
+}
+
+/// Calculate rolling median for values, over each window of `window` values,
+/// such as to remove spikes from a series.
+///
+/// Return one result per position, NaN before the first full window.
+/// Return all NaN if the window is 0.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::rolling_quantile::*;
+/// let medians = rolling_median(&[1.0, 2.0, 90.0, 3.0, 4.0], 3);
+/// assert_eq!(&medians[2..], &[2.0, 3.0, 4.0]);
+/// ```
+///
+pub fn rolling_median<T: AsRef<[f64]>>(values: T, window: usize) -> Vec<f64> {
+    rolling_quantile(values, window, 0.5)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::f64::quantile::quantile;
+
+    #[test]
+    fn test_empty() {
+        let x: &[f64] = &[];
+        assert!(rolling_median(x, 3).is_empty());
+        assert!(RollingQuantile::new(3).median().is_nan());
+    }
+
+    #[test]
+    fn test_nan() {
+        let x = &[1.0, f64::NAN, 3.0, f64::NAN, f64::NAN];
+        let medians = rolling_median(x, 2);
+        assert_eq!(medians[1], 1.0);
+        assert_eq!(medians[2], 3.0);
+        assert!(medians[4].is_nan());
+    }
+
+    #[test]
+    fn test_tree() {
+        let mut tree = OrderStatisticTree::new();
+        for (id, x) in [5.0, 1.0, 3.0, 3.0, 2.0].iter().enumerate() {
+            tree.insert(*x, id as u64);
+        }
+        assert_eq!(tree.len(), 5);
+        assert_eq!((0..5).map(|r| tree.select(r).unwrap()).collect::<Vec<_>>(), vec![1.0, 2.0, 3.0, 3.0, 5.0]);
+        assert!(tree.remove(3.0, 3));
+        assert!(!tree.remove(3.0, 3));
+        assert_eq!(tree.select(2), Some(3.0));
+        assert_eq!(tree.select(4), None);
+    }
+
+    #[test]
+    fn test_matches_naive() {
+        let x: Vec<f64> = (0..500).map(|i| ((i * 7919) % 97) as f64).collect();
+        let window = 31;
+        for p in [0.0, 0.1, 0.5, 0.99, 1.0] {
+            let rolling = rolling_quantile(&x, window, p);
+            for i in window - 1..x.len() {
+                assert_eq!(rolling[i], quantile(&x[i + 1 - window..=i], p));
+            }
+        }
+    }
+
+}
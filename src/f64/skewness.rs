@@ -0,0 +1,132 @@
+use super::average;
+
+/// Calculate statistical skewness for values, as the adjusted
+/// Fisher-Pearson standardized moment coefficient G1.
+///
+/// This is the sample skewness used by spreadsheets and pandas.
+///
+/// Return NaN if there are fewer than 3 values, or if the values are all equal.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// #[macro_use]
+/// use numeric_statistics::assert_eq_f64;
+/// use numeric_statistics::f64::skewness::*;
+/// let values = &[1.0, 2.0, 4.0];
+/// let skewness = skewness(values);
+/// assert_eq_f64!(skewness, 0.9352195295828237);
+/// ```
+///
+pub fn skewness<T: AsRef<[f64]>>(values: T) -> f64 {
+    let values = values.as_ref();
+    let n = values.iter().filter(|x| !x.is_nan()).count() as f64;
+    if n < 3.0 { return f64::NAN; }
+    skewness_population(values) * (n * (n - 1.0)).sqrt() / (n - 2.0)
+}
+
+/// Calculate statistical skewness for values, as the biased
+/// Fisher-Pearson coefficient of skewness g1 = m3 / m2^(3/2).
+///
+/// This is the population skewness used by SciPy by default,
+/// and by tests such as Jarque-Bera.
+///
+/// Return NaN if the values are empty, or if the values are all equal.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// #[macro_use]
+/// use numeric_statistics::assert_eq_f64;
+/// use numeric_statistics::f64::skewness::*;
+/// let values = &[1.0, 2.0, 4.0];
+/// let skewness = skewness_population(values);
+/// assert_eq_f64!(skewness, 0.38180177416060584);
+/// ```
+///
+pub fn skewness_population<T: AsRef<[f64]>>(values: T) -> f64 {
+    let values = values.as_ref();
+    let (m2, m3, _) = central_moments(values, average(values));
+    if m2 == 0.0 { return f64::NAN; }
+    m3 / m2.powf(1.5)
+}
+
+/// Calculate the 2nd, 3rd, and 4th central moments for values,
+/// given a pre-calculated average value, each divided by n.
+///
+/// Return NaN moments if the values are empty.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::skewness::*;
+/// let values = &[1.0, 2.0, 3.0];
+/// let (m2, m3, m4) = central_moments(values, 2.0);
+/// assert_eq!((m2, m3, m4), (2.0 / 3.0, 0.0, 2.0 / 3.0));
+/// ```
+///
+pub fn central_moments<T: AsRef<[f64]>>(values: T, average: f64) -> (f64, f64, f64) {
+    let values = values.as_ref();
+    let mut m2: f64 = 0.0;
+    let mut m3: f64 = 0.0;
+    let mut m4: f64 = 0.0;
+    let mut len: usize = 0;
+    values.iter().for_each(|x|
+        if !x.is_nan() {
+            let delta = *x - average;
+            let delta2 = delta * delta;
+            m2 += delta2;
+            m3 += delta2 * delta;
+            m4 += delta2 * delta2;
+            len += 1;
+        }
+    );
+    if len == 0 { return (f64::NAN, f64::NAN, f64::NAN); }
+    let n = len as f64;
+    (m2 / n, m3 / n, m4 / n)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::assert_eq_f64;
+
+    #[test]
+    fn test_empty() {
+        let x: &[f64] = &[];
+        assert!(skewness(x).is_nan());
+        assert!(skewness_population(x).is_nan());
+    }
+
+    #[test]
+    fn test_nan() {
+        let x: &[f64] = &[f64::NAN];
+        assert!(skewness(x).is_nan());
+    }
+
+    #[test]
+    fn test_values_equal() {
+        let x: &[f64] = &[2.0, 2.0, 2.0];
+        assert!(skewness(x).is_nan());
+    }
+
+    #[test]
+    fn test_values_symmetric() {
+        let x = &[1.0, 2.0, 3.0, 4.0, 5.0];
+        assert_eq_f64!(skewness(x), 0.0);
+    }
+
+    #[test]
+    fn test_values_ascending_and_nans() {
+        let x = &[1.0, f64::NAN, 2.0, f64::NAN, 4.0];
+        assert_eq_f64!(skewness(x), 0.9352195295828237);
+        assert_eq_f64!(skewness_population(x), 0.38180177416060584);
+    }
+
+}
@@ -0,0 +1,237 @@
+//! Streaming summary of samples: count, min, max, average, and variance,
+//! with the Welford update for each sample and the parallel variance
+//! formula for merging.
+//!
+//! `Summary` is shared by the time buckets and the log histogram, and its
+//! `Moments` are shared by the rolling window, which removes samples and
+//! so keeps its min and max elsewhere.
+//!
+//! # Example
+//!
+//! ```rust
+//! use numeric_statistics::f64::summary::*;
+//!
+//! let mut a = Summary::new();
+//! let mut b = Summary::new();
+//! a.push(1.0);
+//! b.push(3.0);
+//! a.merge(&b);
+//! assert_eq!(a.count(), 2);
+//! assert_eq!(a.average(), 2.0);
+//! assert_eq!(a.variance(), 2.0);
+//! ```
+
+use super::all::All;
+
+/// Streaming count, average, and delta square sum of samples.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Moments {
+    count: u64,
+    average: f64,
+    delta_square_sum: f64,
+}
+
+impl Moments {
+    /// Push a sample, with the Welford update.
+    pub(crate) fn push(&mut self, x: f64) {
+        self.count += 1;
+        let delta = x - self.average;
+        self.average += delta / self.count as f64;
+        self.delta_square_sum += delta * (x - self.average);
+    }
+
+    /// Push a sample n times, with the parallel variance formula.
+    pub(crate) fn push_n(&mut self, x: f64, n: u64) {
+        self.merge(&Moments { count: n, average: x, delta_square_sum: 0.0 });
+    }
+
+    /// Remove a pushed sample, with the Welford update in reverse.
+    pub(crate) fn remove(&mut self, x: f64) {
+        self.count -= 1;
+        if self.count == 0 {
+            *self = Moments::default();
+            return;
+        }
+        let delta = x - self.average;
+        self.average -= delta / self.count as f64;
+        self.delta_square_sum = (self.delta_square_sum - delta * (x - self.average)).max(0.0);
+    }
+
+    /// Merge other moments into these, with the parallel variance formula.
+    pub(crate) fn merge(&mut self, other: &Moments) {
+        if other.count == 0 { return; }
+        let (count, n) = (self.count as f64, other.count as f64);
+        let total = count + n;
+        let delta = other.average - self.average;
+        self.average += delta * n / total;
+        self.delta_square_sum += other.delta_square_sum + delta * delta * count * n / total;
+        self.count += other.count;
+    }
+
+    pub(crate) fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Average of samples, or NaN if empty.
+    pub(crate) fn average(&self) -> f64 {
+        if self.count == 0 { f64::NAN } else { self.average }
+    }
+
+    /// Sample variance of samples, or NaN if empty.
+    pub(crate) fn variance(&self) -> f64 {
+        match self.count {
+            0 => f64::NAN,
+            1 => 0.0,
+            n => self.delta_square_sum / (n - 1) as f64,
+        }
+    }
+}
+
+/// Mergeable summary of samples: count, min, max, average, and variance.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    moments: Moments,
+    min: f64,
+    max: f64,
+}
+
+impl Default for Summary {
+    fn default() -> Self {
+        Summary::new()
+    }
+}
+
+impl Summary {
+    pub fn new() -> Summary {
+        Summary { moments: Moments::default(), min: f64::NAN, max: f64::NAN }
+    }
+
+    /// Push a sample.
+    ///
+    /// Filter NaN values in the stream.
+    pub fn push(&mut self, x: f64) {
+        if x.is_nan() { return; }
+        self.moments.push(x);
+        self.min = self.min.min(x);
+        self.max = self.max.max(x);
+    }
+
+    /// Push a sample n times.
+    ///
+    /// Filter NaN values in the stream.
+    pub fn push_n(&mut self, x: f64, n: u64) {
+        if x.is_nan() || n == 0 { return; }
+        self.moments.push_n(x, n);
+        self.min = self.min.min(x);
+        self.max = self.max.max(x);
+    }
+
+    /// Merge another summary into this one, with the parallel variance formula.
+    pub fn merge(&mut self, other: &Summary) {
+        if other.count() == 0 { return; }
+        self.moments.merge(&other.moments);
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+    }
+
+    /// Count of samples.
+    pub fn count(&self) -> u64 {
+        self.moments.count()
+    }
+
+    /// Min sample, or NaN if empty.
+    pub fn min(&self) -> f64 {
+        self.min
+    }
+
+    /// Max sample, or NaN if empty.
+    pub fn max(&self) -> f64 {
+        self.max
+    }
+
+    /// Average of samples, or NaN if empty.
+    pub fn average(&self) -> f64 {
+        self.moments.average()
+    }
+
+    /// Sample variance of samples, or NaN if empty.
+    pub fn variance(&self) -> f64 {
+        self.moments.variance()
+    }
+
+    /// Sample standard deviation of samples, or NaN if empty.
+    pub fn standard_deviation(&self) -> f64 {
+        self.variance().sqrt()
+    }
+
+    /// Convert to `All`.
+    pub fn all(&self) -> All {
+        All::from(self)
+    }
+}
+
+impl From<&Summary> for All {
+    fn from(summary: &Summary) -> All {
+        let variance = summary.variance();
+        All {
+            min: summary.min(),
+            max: summary.max(),
+            average: summary.average(),
+            variance,
+            standard_deviation: variance.sqrt(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_empty() {
+        let summary = Summary::new();
+        assert_eq!(summary.count(), 0);
+        assert!(summary.average().is_nan());
+        assert!(summary.variance().is_nan());
+        assert!(summary.all().min.is_nan());
+    }
+
+    #[test]
+    fn test_merge() {
+        let x = [1.0, 2.0, 4.0, 8.0, 16.0];
+        let (mut a, mut b, mut c) = (Summary::new(), Summary::new(), Summary::new());
+        x[..2].iter().for_each(|x| a.push(*x));
+        x[2..].iter().for_each(|x| b.push(*x));
+        x.iter().for_each(|x| c.push(*x));
+        a.merge(&b);
+        assert_eq!(a.count(), c.count());
+        assert!((a.average() - c.average()).abs() < 1e-12);
+        assert!((a.variance() - c.variance()).abs() < 1e-12);
+        assert_eq!(a.min(), 1.0);
+        assert_eq!(a.max(), 16.0);
+    }
+
+    #[test]
+    fn test_push_n() {
+        let (mut a, mut b) = (Summary::new(), Summary::new());
+        a.push_n(3.0, 4);
+        a.push(7.0);
+        (0..4).for_each(|_| b.push(3.0));
+        b.push(7.0);
+        assert_eq!(a.count(), 5);
+        assert!((a.average() - b.average()).abs() < 1e-12);
+        assert!((a.variance() - b.variance()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut moments = Moments::default();
+        [2.0, 4.0, 6.0].iter().for_each(|x| moments.push(*x));
+        moments.remove(6.0);
+        assert_eq!(moments.average(), 3.0);
+        assert_eq!(moments.variance(), 2.0);
+        moments.remove(4.0);
+        moments.remove(2.0);
+        assert_eq!(moments, Moments::default());
+    }
+}
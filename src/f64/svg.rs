@@ -0,0 +1,568 @@
+//! SVG charts for histograms, box plots, ECDF curves, and line charts,
+//! with no external dependencies.
+//!
+//! Each chart is a self-contained SVG document. The output is
+//! deterministic, with coordinates rounded to 2 decimal places, so
+//! charts can be snapshot-tested and diffed.
+//!
+//! # Example
+//!
+//! ```rust
+//! use numeric_statistics::f64::{histogram::*, svg::*};
+//!
+//! let values = &[1.0, 2.0, 2.0, 3.0, 3.0, 3.0, 4.0];
+//! let histogram = Histogram::new(values, &BinRule::Sturges);
+//! let options = SvgOptions::default().title("Latency").x_label("ms").y_label("count");
+//! let svg = histogram_svg(&histogram, &options);
+//! assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+//! assert!(svg.contains("<title>Latency</title>"));
+//! ```
+
+use std::fmt::{self, Write};
+use super::{
+    box_plot::BoxPlotStats,
+    five_number_summary::FiveNumberSummary,
+    histogram::Histogram,
+    quantile::*,
+    rolling::rolling_mean,
+};
+
+/// Options for SVG charts.
+///
+/// * `width`, `height`: dimensions in pixels.
+///
+/// * `title`, `x_label`, `y_label`: optional text.
+///
+/// * `x_ticks`, `y_ticks`: approximate tick count for numeric axes.
+///
+/// * `tukey`: draw box plot whiskers to Tukey's inner fences, and mark
+///   outliers, rather than whiskers to the min and max.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct SvgOptions {
+    pub width: f64,
+    pub height: f64,
+    pub title: Option<String>,
+    pub x_label: Option<String>,
+    pub y_label: Option<String>,
+    pub x_ticks: usize,
+    pub y_ticks: usize,
+    pub tukey: bool,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        SvgOptions {
+            width: 640.0,
+            height: 400.0,
+            title: None,
+            x_label: None,
+            y_label: None,
+            x_ticks: 5,
+            y_ticks: 5,
+            tukey: false,
+        }
+    }
+}
+
+impl SvgOptions {
+    pub fn width(mut self, width: f64) -> Self {
+        self.width = width;
+        self
+    }
+
+    pub fn height(mut self, height: f64) -> Self {
+        self.height = height;
+        self
+    }
+
+    pub fn title<S: Into<String>>(mut self, title: S) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    pub fn x_label<S: Into<String>>(mut self, x_label: S) -> Self {
+        self.x_label = Some(x_label.into());
+        self
+    }
+
+    pub fn y_label<S: Into<String>>(mut self, y_label: S) -> Self {
+        self.y_label = Some(y_label.into());
+        self
+    }
+
+    pub fn x_ticks(mut self, x_ticks: usize) -> Self {
+        self.x_ticks = x_ticks.max(1);
+        self
+    }
+
+    pub fn y_ticks(mut self, y_ticks: usize) -> Self {
+        self.y_ticks = y_ticks.max(1);
+        self
+    }
+
+    pub fn tukey(mut self, tukey: bool) -> Self {
+        self.tukey = tukey;
+        self
+    }
+}
+
+/// Margins around the plot area, in pixels.
+const MARGIN_LEFT: f64 = 60.0;
+const MARGIN_RIGHT: f64 = 20.0;
+const MARGIN_TOP: f64 = 40.0;
+const MARGIN_BOTTOM: f64 = 50.0;
+
+/// Colors for data, overlays, and axes.
+const COLOR_DATA: &str = "#4878a8";
+const COLOR_OVERLAY: &str = "#d8603c";
+const COLOR_AXIS: &str = "#333333";
+
+/// Plot area in pixels, with the data ranges it maps from.
+struct Frame {
+    left: f64,
+    top: f64,
+    right: f64,
+    bottom: f64,
+    x: (f64, f64),
+    y: (f64, f64),
+}
+
+impl Frame {
+    fn new(options: &SvgOptions, x: (f64, f64), y: (f64, f64)) -> Frame {
+        Frame {
+            left: MARGIN_LEFT,
+            top: MARGIN_TOP,
+            right: (options.width - MARGIN_RIGHT).max(MARGIN_LEFT + 1.0),
+            bottom: (options.height - MARGIN_BOTTOM).max(MARGIN_TOP + 1.0),
+            x: widen(x),
+            y: widen(y),
+        }
+    }
+
+    /// Map a data x to a pixel x.
+    fn px(&self, x: f64) -> f64 {
+        self.left + (x - self.x.0) / (self.x.1 - self.x.0) * (self.right - self.left)
+    }
+
+    /// Map a data y to a pixel y, with larger values higher.
+    fn py(&self, y: f64) -> f64 {
+        self.bottom - (y - self.y.0) / (self.y.1 - self.y.0) * (self.bottom - self.top)
+    }
+}
+
+/// Widen an empty or invalid range so it can map to pixels.
+fn widen((lo, hi): (f64, f64)) -> (f64, f64) {
+    if !lo.is_finite() || !hi.is_finite() { return (0.0, 1.0); }
+    if lo == hi { return (lo - 0.5, hi + 0.5); }
+    (lo, hi)
+}
+
+/// Format a coordinate deterministically, without negative zero.
+fn num(x: f64) -> String {
+    format!("{:.2}", x + 0.0)
+}
+
+/// Escape text for XML.
+fn escape(s: &str) -> String {
+    s.chars().fold(String::new(), |mut out, c| {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            c => out.push(c),
+        }
+        out
+    })
+}
+
+/// Calculate evenly spaced tick values at 1, 2, or 5 times a power of 10,
+/// within lo to hi, with the number of decimal places to show them.
+fn ticks(lo: f64, hi: f64, count: usize) -> (Vec<f64>, usize) {
+    let raw = (hi - lo) / count.max(1) as f64;
+    if !(raw > 0.0 && raw.is_finite()) { return (vec![lo], 0); }
+    let exponent = raw.log10().floor() as i32;
+    let multiple = [1.0, 2.0, 5.0, 10.0]
+        .into_iter()
+        .find(|m| m * 10f64.powi(exponent) >= raw * (1.0 - 1e-9))
+        .unwrap_or(10.0);
+    // Divide by an exact power of 10 for negative exponents, so 3 * 0.1 shows as 0.3.
+    let tick = |i: i64| if exponent < 0 {
+        i as f64 * multiple / 10f64.powi(-exponent)
+    } else {
+        i as f64 * multiple * 10f64.powi(exponent)
+    };
+    let step = tick(1);
+    let decimals = (-step.log10().floor()).max(0.0) as usize;
+    let first = (lo / step - 1e-9).ceil() as i64;
+    let last = (hi / step + 1e-9).floor() as i64;
+    ((first..=last).map(tick).collect(), decimals)
+}
+
+/// Write the SVG start tag, background, and title.
+fn write_start<W: fmt::Write>(w: &mut W, options: &SvgOptions) -> fmt::Result {
+    writeln!(
+        w,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\" font-family=\"sans-serif\" font-size=\"12\">",
+        num(options.width), num(options.height)
+    )?;
+    if let Some(title) = &options.title {
+        writeln!(w, "<title>{}</title>", escape(title))?;
+    }
+    writeln!(w, "<rect width=\"100%\" height=\"100%\" fill=\"white\"/>")?;
+    if let Some(title) = &options.title {
+        writeln!(w, "<text x=\"{}\" y=\"24\" text-anchor=\"middle\" font-size=\"16\">{}</text>", num(options.width / 2.0), escape(title))?;
+    }
+    Ok(())
+}
+
+/// Write the SVG end tag.
+fn write_end<W: fmt::Write>(w: &mut W) -> fmt::Result {
+    writeln!(w, "</svg>")
+}
+
+/// Write the axes with numeric y ticks, and with x ticks that are either
+/// numeric, or categorical labels at given data positions.
+fn write_axes<W: fmt::Write>(w: &mut W, frame: &Frame, options: &SvgOptions, categories: Option<&[(f64, String)]>) -> fmt::Result {
+    writeln!(w, "<g stroke=\"{}\" fill=\"none\">", COLOR_AXIS)?;
+    writeln!(w, "<line x1=\"{0}\" y1=\"{1}\" x2=\"{2}\" y2=\"{1}\"/>", num(frame.left), num(frame.bottom), num(frame.right))?;
+    writeln!(w, "<line x1=\"{0}\" y1=\"{1}\" x2=\"{0}\" y2=\"{2}\"/>", num(frame.left), num(frame.bottom), num(frame.top))?;
+    writeln!(w, "</g>")?;
+    writeln!(w, "<g fill=\"{}\">", COLOR_AXIS)?;
+    let x_ticks: Vec<(f64, String)> = match categories {
+        Some(categories) => categories.to_vec(),
+        None => {
+            let (values, decimals) = ticks(frame.x.0, frame.x.1, options.x_ticks);
+            values.into_iter().map(|x| (x, format!("{:.*}", decimals, x + 0.0))).collect()
+        }
+    };
+    for (x, label) in x_ticks {
+        let px = num(frame.px(x));
+        writeln!(w, "<line x1=\"{0}\" y1=\"{1}\" x2=\"{0}\" y2=\"{2}\" stroke=\"{3}\"/>", px, num(frame.bottom), num(frame.bottom + 5.0), COLOR_AXIS)?;
+        writeln!(w, "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{}</text>", px, num(frame.bottom + 18.0), escape(&label))?;
+    }
+    let (y_ticks, decimals) = ticks(frame.y.0, frame.y.1, options.y_ticks);
+    for y in y_ticks {
+        let py = num(frame.py(y));
+        writeln!(w, "<line x1=\"{0}\" y1=\"{1}\" x2=\"{2}\" y2=\"{1}\" stroke=\"{3}\"/>", num(frame.left - 5.0), py, num(frame.left), COLOR_AXIS)?;
+        writeln!(w, "<text x=\"{}\" y=\"{}\" text-anchor=\"end\" dominant-baseline=\"middle\">{:.*}</text>", num(frame.left - 8.0), py, decimals, y + 0.0)?;
+    }
+    if let Some(x_label) = &options.x_label {
+        writeln!(w, "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{}</text>", num((frame.left + frame.right) / 2.0), num(frame.bottom + 40.0), escape(x_label))?;
+    }
+    if let Some(y_label) = &options.y_label {
+        let (x, y) = (num(16.0), num((frame.top + frame.bottom) / 2.0));
+        writeln!(w, "<text x=\"{0}\" y=\"{1}\" text-anchor=\"middle\" transform=\"rotate(-90 {0} {1})\">{2}</text>", x, y, escape(y_label))?;
+    }
+    writeln!(w, "</g>")
+}
+
+/// Write an SVG histogram, with one bar per bin.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::{histogram::*, svg::*};
+/// let histogram = Histogram::new(&[1.0, 2.0, 2.0, 3.0], &BinRule::Count(2));
+/// let svg = histogram_svg(&histogram, &SvgOptions::default());
+/// assert_eq!(svg.matches("<rect x=").count(), 2);
+/// ```
+///
+pub fn write_histogram_svg<W: fmt::Write>(w: &mut W, histogram: &Histogram, options: &SvgOptions) -> fmt::Result {
+    let x = (
+        histogram.edges.first().copied().unwrap_or(f64::NAN),
+        histogram.edges.last().copied().unwrap_or(f64::NAN),
+    );
+    let max_count = histogram.counts.iter().copied().max().unwrap_or(0);
+    let frame = Frame::new(options, x, (0.0, max_count.max(1) as f64));
+    write_start(w, options)?;
+    write_axes(w, &frame, options, None)?;
+    writeln!(w, "<g fill=\"{}\" stroke=\"white\">", COLOR_DATA)?;
+    for (i, count) in histogram.counts.iter().enumerate() {
+        let (x0, x1) = (frame.px(histogram.edges[i]), frame.px(histogram.edges[i + 1]));
+        let y = frame.py(*count as f64);
+        writeln!(
+            w,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"><title>[{}, {}]: {}</title></rect>",
+            num(x0), num(y), num(x1 - x0), num(frame.bottom - y),
+            histogram.edges[i], histogram.edges[i + 1], count
+        )?;
+    }
+    writeln!(w, "</g>")?;
+    write_end(w)
+}
+
+/// Render an SVG histogram to a string. See `write_histogram_svg`.
+pub fn histogram_svg(histogram: &Histogram, options: &SvgOptions) -> String {
+    let mut s = String::new();
+    write_histogram_svg(&mut s, histogram, options).expect("write to string");
+    s
+}
+
+/// Write an SVG box plot of one group of values. See `write_grouped_box_plot_svg`.
+pub fn write_box_plot_svg<W: fmt::Write, T: AsRef<[f64]>>(w: &mut W, values: T, options: &SvgOptions) -> fmt::Result {
+    write_grouped_box_plot_svg(w, &[("", values)], options)
+}
+
+/// Render an SVG box plot of one group of values to a string.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::svg::*;
+/// let svg = box_plot_svg(&[1.0, 2.0, 3.0, 4.0, 10.0], &SvgOptions::default());
+/// assert!(svg.contains("class=\"box\""));
+/// assert_eq!(svg.matches("<circle").count(), 0);
+/// let svg = box_plot_svg(&[1.0, 2.0, 3.0, 4.0, 10.0], &SvgOptions::default().tukey(true));
+/// assert_eq!(svg.matches("<circle").count(), 1);
+/// ```
+///
+pub fn box_plot_svg<T: AsRef<[f64]>>(values: T, options: &SvgOptions) -> String {
+    let mut s = String::new();
+    write_box_plot_svg(&mut s, values, options).expect("write to string");
+    s
+}
+
+/// Write an SVG box plot with one vertical box per named group, side by side.
+///
+/// Each box spans the first quartile to the third quartile, with a line
+/// at the median; the whiskers span the min to the max.
+///
+/// With the `tukey` option, the whiskers reach the most extreme values
+/// within the inner fences instead. Mild outliers show as hollow circles,
+/// and extreme outliers show as filled circles. See `BoxPlotStats`.
+///
+/// Filter NaN values in the stream. Skip groups with no values.
+///
+pub fn write_grouped_box_plot_svg<W, S, T>(w: &mut W, groups: &[(S, T)], options: &SvgOptions) -> fmt::Result
+where
+    W: fmt::Write,
+    S: AsRef<str>,
+    T: AsRef<[f64]>,
+{
+    let stats: Vec<BoxPlotStats> = groups.iter().map(|(_, values)| BoxPlotStats::new(values)).collect();
+    let lo = stats.iter().fold(f64::NAN, |a, s| a.min(s.summary.min));
+    let hi = stats.iter().fold(f64::NAN, |a, s| a.max(s.summary.max));
+    let slots = groups.len().max(1) as f64;
+    let frame = Frame::new(options, (0.0, slots), (lo, hi));
+    let categories: Vec<(f64, String)> = groups
+        .iter()
+        .enumerate()
+        .map(|(i, (name, _))| (i as f64 + 0.5, name.as_ref().to_string()))
+        .collect();
+    write_start(w, options)?;
+    write_axes(w, &frame, options, Some(&categories))?;
+    let half = (frame.px(1.0) - frame.px(0.0)) * 0.25;
+    for (i, stats) in stats.iter().enumerate() {
+        let FiveNumberSummary { min, q1, median, q3, max } = stats.summary;
+        if min.is_nan() { continue; }
+        let (low, high) = if options.tukey { (stats.whisker_low, stats.whisker_high) } else { (min, max) };
+        let cx = frame.px(i as f64 + 0.5);
+        writeln!(w, "<g class=\"box\" stroke=\"{}\">", COLOR_AXIS)?;
+        writeln!(w, "<line x1=\"{0}\" y1=\"{1}\" x2=\"{0}\" y2=\"{2}\"/>", num(cx), num(frame.py(low)), num(frame.py(q1)))?;
+        writeln!(w, "<line x1=\"{0}\" y1=\"{1}\" x2=\"{0}\" y2=\"{2}\"/>", num(cx), num(frame.py(q3)), num(frame.py(high)))?;
+        for y in [low, high] {
+            writeln!(w, "<line x1=\"{0}\" y1=\"{2}\" x2=\"{1}\" y2=\"{2}\"/>", num(cx - half / 2.0), num(cx + half / 2.0), num(frame.py(y)))?;
+        }
+        writeln!(
+            w,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+            num(cx - half), num(frame.py(q3)), num(2.0 * half), num(frame.py(q1) - frame.py(q3)), COLOR_DATA
+        )?;
+        writeln!(w, "<line x1=\"{0}\" y1=\"{2}\" x2=\"{1}\" y2=\"{2}\" stroke-width=\"2\"/>", num(cx - half), num(cx + half), num(frame.py(median)))?;
+        if options.tukey {
+            for outlier in stats.mild_outliers.iter() {
+                writeln!(w, "<circle cx=\"{}\" cy=\"{}\" r=\"3\" fill=\"none\"/>", num(cx), num(frame.py(outlier.value)))?;
+            }
+            for outlier in stats.extreme_outliers.iter() {
+                writeln!(w, "<circle cx=\"{}\" cy=\"{}\" r=\"3\" fill=\"{}\"/>", num(cx), num(frame.py(outlier.value)), COLOR_AXIS)?;
+            }
+        }
+        writeln!(w, "</g>")?;
+    }
+    write_end(w)
+}
+
+/// Render an SVG grouped box plot to a string. See `write_grouped_box_plot_svg`.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::svg::*;
+/// let groups = [("us-east", vec![1.0, 2.0, 3.0]), ("eu-west", vec![2.0, 4.0, 6.0])];
+/// let svg = grouped_box_plot_svg(&groups, &SvgOptions::default());
+/// assert_eq!(svg.matches("class=\"box\"").count(), 2);
+/// assert!(svg.contains(">eu-west</text>"));
+/// ```
+///
+pub fn grouped_box_plot_svg<S: AsRef<str>, T: AsRef<[f64]>>(groups: &[(S, T)], options: &SvgOptions) -> String {
+    let mut s = String::new();
+    write_grouped_box_plot_svg(&mut s, groups, options).expect("write to string");
+    s
+}
+
+/// Write an SVG empirical cumulative distribution function (ECDF) curve,
+/// as a step function from 0 to 1.
+///
+/// Filter NaN values in the stream.
+///
+pub fn write_ecdf_svg<W: fmt::Write, T: AsRef<[f64]>>(w: &mut W, values: T, options: &SvgOptions) -> fmt::Result {
+    let sorted = sorted(values);
+    let x = (
+        sorted.first().copied().unwrap_or(f64::NAN),
+        sorted.last().copied().unwrap_or(f64::NAN),
+    );
+    let frame = Frame::new(options, x, (0.0, 1.0));
+    write_start(w, options)?;
+    write_axes(w, &frame, options, None)?;
+    if !sorted.is_empty() {
+        let n = sorted.len() as f64;
+        let mut path = format!("M{},{}", num(frame.px(sorted[0])), num(frame.py(0.0)));
+        for (i, x) in sorted.iter().enumerate() {
+            write!(path, " H{} V{}", num(frame.px(*x)), num(frame.py((i + 1) as f64 / n)))?;
+        }
+        writeln!(w, "<path d=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"2\"/>", path, COLOR_DATA)?;
+    }
+    write_end(w)
+}
+
+/// Render an SVG ECDF curve to a string. See `write_ecdf_svg`.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::svg::*;
+/// let svg = ecdf_svg(&[1.0, 2.0, 3.0], &SvgOptions::default());
+/// assert!(svg.contains("<path d=\"M"));
+/// ```
+///
+pub fn ecdf_svg<T: AsRef<[f64]>>(values: T, options: &SvgOptions) -> String {
+    let mut s = String::new();
+    write_ecdf_svg(&mut s, values, options).expect("write to string");
+    s
+}
+
+/// Write an SVG line chart of a series by index, with an overlay of the
+/// rolling mean over the window, or no overlay if the window is 0.
+///
+/// Show NaN values as gaps in the line.
+///
+pub fn write_line_svg<W: fmt::Write, T: AsRef<[f64]>>(w: &mut W, values: T, window: usize, options: &SvgOptions) -> fmt::Result {
+    let values = values.as_ref();
+    let lo = values.iter().fold(f64::NAN, |a, x| a.min(*x));
+    let hi = values.iter().fold(f64::NAN, |a, x| a.max(*x));
+    let frame = Frame::new(options, (0.0, values.len().saturating_sub(1) as f64), (lo, hi));
+    write_start(w, options)?;
+    write_axes(w, &frame, options, None)?;
+    write_line_path(w, &frame, values, COLOR_DATA)?;
+    if window > 0 {
+        write_line_path(w, &frame, &rolling_mean(values, window), COLOR_OVERLAY)?;
+    }
+    write_end(w)
+}
+
+/// Render an SVG line chart to a string. See `write_line_svg`.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::svg::*;
+/// let values = &[1.0, 3.0, 2.0, 5.0, 4.0];
+/// let svg = line_svg(values, 2, &SvgOptions::default());
+/// assert_eq!(svg.matches("<path").count(), 2);
+/// ```
+///
+pub fn line_svg<T: AsRef<[f64]>>(values: T, window: usize, options: &SvgOptions) -> String {
+    let mut s = String::new();
+    write_line_svg(&mut s, values, window, options).expect("write to string");
+    s
+}
+
+/// Write a path through the series by index, with gaps at NaN values.
+fn write_line_path<W: fmt::Write>(w: &mut W, frame: &Frame, values: &[f64], color: &str) -> fmt::Result {
+    let mut path = String::new();
+    let mut pen_down = false;
+    for (i, y) in values.iter().enumerate() {
+        if y.is_nan() {
+            pen_down = false;
+            continue;
+        }
+        if !path.is_empty() { path.push(' '); }
+        write!(path, "{}{},{}", if pen_down { 'L' } else { 'M' }, num(frame.px(i as f64)), num(frame.py(*y)))?;
+        pen_down = true;
+    }
+    if path.is_empty() { return Ok(()); }
+    writeln!(w, "<path d=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"2\"/>", path, color)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::f64::histogram::BinRule;
+
+    #[test]
+    fn test_empty() {
+        let x: &[f64] = &[];
+        let options = SvgOptions::default();
+        for svg in [
+            histogram_svg(&Histogram::new(x, &BinRule::Sturges), &options),
+            box_plot_svg(x, &options),
+            ecdf_svg(x, &options),
+            line_svg(x, 3, &options),
+        ] {
+            assert!(svg.starts_with("<svg"));
+            assert!(svg.ends_with("</svg>\n"));
+        }
+    }
+
+    #[test]
+    fn test_ticks() {
+        assert_eq!(ticks(0.0, 10.0, 5), (vec![0.0, 2.0, 4.0, 6.0, 8.0, 10.0], 0));
+        assert_eq!(ticks(0.13, 0.52, 4), (vec![0.2, 0.3, 0.4, 0.5], 1));
+        assert_eq!(ticks(3.0, 3.0, 5), (vec![3.0], 0));
+    }
+
+    #[test]
+    fn test_escape() {
+        let svg = ecdf_svg([1.0], &SvgOptions::default().title("a < b & c"));
+        assert!(svg.contains("<title>a &lt; b &amp; c</title>"));
+    }
+
+    #[test]
+    fn test_deterministic_snapshot() {
+        let options = SvgOptions::default().width(200.0).height(150.0).x_ticks(2).y_ticks(1);
+        let svg = ecdf_svg([1.0, 2.0], &options);
+        assert_eq!(svg, concat!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"200.00\" height=\"150.00\" viewBox=\"0 0 200.00 150.00\" font-family=\"sans-serif\" font-size=\"12\">\n",
+            "<rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n",
+            "<g stroke=\"#333333\" fill=\"none\">\n",
+            "<line x1=\"60.00\" y1=\"100.00\" x2=\"180.00\" y2=\"100.00\"/>\n",
+            "<line x1=\"60.00\" y1=\"100.00\" x2=\"60.00\" y2=\"40.00\"/>\n",
+            "</g>\n",
+            "<g fill=\"#333333\">\n",
+            "<line x1=\"60.00\" y1=\"100.00\" x2=\"60.00\" y2=\"105.00\" stroke=\"#333333\"/>\n",
+            "<text x=\"60.00\" y=\"118.00\" text-anchor=\"middle\">1.0</text>\n",
+            "<line x1=\"120.00\" y1=\"100.00\" x2=\"120.00\" y2=\"105.00\" stroke=\"#333333\"/>\n",
+            "<text x=\"120.00\" y=\"118.00\" text-anchor=\"middle\">1.5</text>\n",
+            "<line x1=\"180.00\" y1=\"100.00\" x2=\"180.00\" y2=\"105.00\" stroke=\"#333333\"/>\n",
+            "<text x=\"180.00\" y=\"118.00\" text-anchor=\"middle\">2.0</text>\n",
+            "<line x1=\"55.00\" y1=\"100.00\" x2=\"60.00\" y2=\"100.00\" stroke=\"#333333\"/>\n",
+            "<text x=\"52.00\" y=\"100.00\" text-anchor=\"end\" dominant-baseline=\"middle\">0</text>\n",
+            "<line x1=\"55.00\" y1=\"40.00\" x2=\"60.00\" y2=\"40.00\" stroke=\"#333333\"/>\n",
+            "<text x=\"52.00\" y=\"40.00\" text-anchor=\"end\" dominant-baseline=\"middle\">1</text>\n",
+            "</g>\n",
+            "<path d=\"M60.00,100.00 H60.00 V70.00 H180.00 V40.00\" fill=\"none\" stroke=\"#4878a8\" stroke-width=\"2\"/>\n",
+            "</svg>\n",
+        ));
+    }
+
+    #[test]
+    fn test_line_nan_gap() {
+        let svg = line_svg([1.0, 2.0, f64::NAN, 3.0], 0, &SvgOptions::default());
+        assert_eq!(svg.matches("<path").count(), 1);
+        assert_eq!(svg.matches('M').count(), 2);
+    }
+
+}
@@ -0,0 +1,269 @@
+//! Student's t-tests of averages: one-sample, paired, and two-sample with
+//! pooled variance (Student) or unpooled variance (Welch).
+//!
+//! Each test takes an alternative hypothesis and a confidence level, such
+//! as 0.95, and returns a `TestResult` with t, the degrees of freedom, the
+//! p-value, a confidence interval of the average or the difference of
+//! averages, and Cohen's d as the effect size.
+//!
+//! Each test returns NaN bounds if the confidence is not within 0.0 to 1.0
+//! exclusive.
+//!
+//! # Example
+//!
+//! ```rust
+//! use numeric_statistics::f64::{hypothesis::*, t_test::*};
+//!
+//! // Did a deploy change latency?
+//! let before = [20.1, 19.8, 20.5, 21.0, 19.9, 20.3, 20.7, 20.2];
+//! let after = [21.2, 20.9, 21.8, 21.5, 20.6, 21.9, 21.1, 21.4];
+//! let result = welch_t_test(after, before, Alternative::TwoSided, 0.95);
+//! assert!(result.p_value < 0.001);
+//! assert!(result.confidence_lower > 0.0);
+//! ```
+
+use super::{average, variance, distribution::StudentT, hypothesis::*};
+
+/// Test result of an estimate with a standard error and t degrees of freedom.
+fn t_result(estimate: f64, standard_error: f64, df: f64, effect_size: f64, alternative: Alternative, confidence: f64) -> TestResult {
+    if df.is_nan() || df <= 0.0 { return TestResult::nan(); }
+    let distribution = StudentT::new(df);
+    let statistic = estimate / standard_error;
+    let (confidence_lower, confidence_upper) =
+        symmetric_confidence_interval(&distribution, estimate, standard_error, alternative, confidence);
+    TestResult {
+        statistic,
+        degrees_of_freedom: df,
+        p_value: symmetric_p_value(&distribution, statistic, alternative),
+        estimate,
+        confidence_lower,
+        confidence_upper,
+        effect_size,
+    }
+}
+
+/// Calculate the one-sample t-test of whether the average of values
+/// differs from mu.
+///
+/// The estimate is the average, and the confidence interval is of the
+/// average. The effect size is Cohen's d, (average - mu) / standard deviation.
+///
+/// Return NaN statistics if there are fewer than 2 values.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::{hypothesis::*, t_test::*};
+/// let result = one_sample_t_test([5.1, 4.9, 5.3, 5.0, 5.2], 5.0, Alternative::TwoSided, 0.95);
+/// assert!((result.statistic - 1.414213562373095).abs() < 1e-12);
+/// assert_eq!(result.degrees_of_freedom, 4.0);
+/// ```
+///
+pub fn one_sample_t_test<T: AsRef<[f64]>>(values: T, mu: f64, alternative: Alternative, confidence: f64) -> TestResult {
+    let values = without_nan(values.as_ref());
+    let n = values.len();
+    if n < 2 { return TestResult::nan(); }
+    let standard_deviation = variance(&values).sqrt();
+    let average = average(&values);
+    let mut result = t_result(
+        average - mu,
+        standard_deviation / (n as f64).sqrt(),
+        (n - 1) as f64,
+        (average - mu) / standard_deviation,
+        alternative,
+        confidence,
+    );
+    result.estimate += mu;
+    result.confidence_lower += mu;
+    result.confidence_upper += mu;
+    result
+}
+
+/// Calculate the paired t-test of whether the average of the differences
+/// xs - ys differs from 0, such as for the same hosts before and after a change.
+///
+/// The estimate is the average difference. The effect size is Cohen's d_z,
+/// the average difference divided by the standard deviation of the differences.
+///
+/// Return NaN statistics if there are fewer than 2 pairs, or if xs and ys
+/// have different lengths.
+///
+/// Filter pairs with a NaN value in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::{hypothesis::*, t_test::*};
+/// let before = [10.0, 12.0, 11.0, 13.0];
+/// let after = [11.0, 13.5, 11.5, 14.0];
+/// let result = paired_t_test(after, before, Alternative::Greater, 0.95);
+/// assert_eq!(result.estimate, 1.0);
+/// assert!(result.p_value < 0.05);
+/// ```
+///
+pub fn paired_t_test<X: AsRef<[f64]>, Y: AsRef<[f64]>>(xs: X, ys: Y, alternative: Alternative, confidence: f64) -> TestResult {
+    let (xs, ys) = (xs.as_ref(), ys.as_ref());
+    if xs.len() != ys.len() { return TestResult::nan(); }
+    let differences: Vec<f64> = xs.iter().zip(ys).map(|(x, y)| x - y).collect();
+    one_sample_t_test(differences, 0.0, alternative, confidence)
+}
+
+/// Calculate Student's two-sample t-test of whether the averages of xs and
+/// ys differ, assuming equal variances, with pooled variance and
+/// n_x + n_y - 2 degrees of freedom.
+///
+/// The estimate is average(xs) - average(ys). The effect size is Cohen's d,
+/// the difference divided by the pooled standard deviation.
+///
+/// Return NaN statistics if there are fewer than 3 values in all, or if
+/// either side has no values.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::{hypothesis::*, t_test::*};
+/// let result = student_t_test([1.0, 2.0, 3.0], [4.0, 5.0, 6.0], Alternative::TwoSided, 0.95);
+/// assert!((result.statistic + 3.674234614174767).abs() < 1e-12);
+/// assert_eq!(result.degrees_of_freedom, 4.0);
+/// ```
+///
+pub fn student_t_test<X: AsRef<[f64]>, Y: AsRef<[f64]>>(xs: X, ys: Y, alternative: Alternative, confidence: f64) -> TestResult {
+    let (xs, ys) = (without_nan(xs.as_ref()), without_nan(ys.as_ref()));
+    let (nx, ny) = (xs.len() as f64, ys.len() as f64);
+    if xs.is_empty() || ys.is_empty() || nx + ny < 3.0 { return TestResult::nan(); }
+    let pooled = ((nx - 1.0) * variance(&xs) + (ny - 1.0) * variance(&ys)) / (nx + ny - 2.0);
+    let difference = average(&xs) - average(&ys);
+    t_result(
+        difference,
+        (pooled * (1.0 / nx + 1.0 / ny)).sqrt(),
+        nx + ny - 2.0,
+        difference / pooled.sqrt(),
+        alternative,
+        confidence,
+    )
+}
+
+/// Calculate Welch's two-sample t-test of whether the averages of xs and ys
+/// differ, without assuming equal variances, with the Welch–Satterthwaite
+/// degrees of freedom, which may be fractional.
+///
+/// Prefer this to Student's t-test unless the variances are known to be
+/// equal, since it is as good when they are, and better when they are not.
+///
+/// The estimate is average(xs) - average(ys). The effect size is Cohen's d,
+/// the difference divided by the root mean square of the two standard deviations.
+///
+/// Return NaN statistics if either side has fewer than 2 values, or if
+/// both sides have all values equal.
+///
+/// Filter NaN values in the stream.
+///
+/// # Example
+///
+/// ```rust
+/// use numeric_statistics::f64::{hypothesis::*, t_test::*};
+/// let result = welch_t_test([1.0, 2.0, 3.0, 4.0], [2.0, 4.0, 6.0, 8.0, 10.0], Alternative::TwoSided, 0.95);
+/// assert_eq!(result.estimate, -3.5);
+/// assert!(result.degrees_of_freedom > 4.0 && result.degrees_of_freedom < 7.0);
+/// ```
+///
+pub fn welch_t_test<X: AsRef<[f64]>, Y: AsRef<[f64]>>(xs: X, ys: Y, alternative: Alternative, confidence: f64) -> TestResult {
+    let (xs, ys) = (without_nan(xs.as_ref()), without_nan(ys.as_ref()));
+    let (nx, ny) = (xs.len() as f64, ys.len() as f64);
+    if xs.len() < 2 || ys.len() < 2 { return TestResult::nan(); }
+    let (vx, vy) = (variance(&xs), variance(&ys));
+    let (sx, sy) = (vx / nx, vy / ny);
+    let df = (sx + sy).powi(2) / (sx * sx / (nx - 1.0) + sy * sy / (ny - 1.0));
+    let difference = average(&xs) - average(&ys);
+    t_result(
+        difference,
+        (sx + sy).sqrt(),
+        df,
+        difference / ((vx + vy) / 2.0).sqrt(),
+        alternative,
+        confidence,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::f64::assert_eq_f64::assert_close;
+
+    /// Student's sleep data: extra hours of sleep for two drugs, on the same 10 patients.
+    const DRUG_1: [f64; 10] = [0.7, -1.6, -0.2, -1.2, -0.1, 3.4, 3.7, 0.8, 0.0, 2.0];
+    const DRUG_2: [f64; 10] = [1.9, 0.8, 1.1, 0.1, -0.1, 4.4, 5.5, 1.6, 4.6, 3.4];
+
+    #[test]
+    fn test_empty() {
+        let x: &[f64] = &[];
+        assert!(one_sample_t_test(x, 0.0, Alternative::TwoSided, 0.95).p_value.is_nan());
+        assert!(paired_t_test(x, x, Alternative::TwoSided, 0.95).p_value.is_nan());
+        assert!(student_t_test(x, x, Alternative::TwoSided, 0.95).p_value.is_nan());
+        assert!(welch_t_test([1.0], [2.0, 3.0], Alternative::TwoSided, 0.95).p_value.is_nan());
+    }
+
+    #[test]
+    fn test_nan() {
+        let mut xs = DRUG_1.to_vec();
+        xs.push(f64::NAN);
+        let with_nan = one_sample_t_test(&xs, 0.0, Alternative::TwoSided, 0.95);
+        assert_eq!(with_nan, one_sample_t_test(DRUG_1, 0.0, Alternative::TwoSided, 0.95));
+    }
+
+    #[test]
+    fn test_one_sample() {
+        let result = one_sample_t_test(DRUG_1, 0.0, Alternative::TwoSided, 0.95);
+        assert_close!(result.statistic, 1.3257101407138213, 1e-12);
+        assert_close!(result.p_value, 0.21759778006844894, 1e-12);
+        assert_close!(result.confidence_lower, -0.5297804135262326, 1e-12);
+        assert_close!(result.confidence_upper, 2.0297804135262326, 1e-12);
+        assert_close!(result.effect_size, 0.41922635618379957, 1e-12);
+        let greater = one_sample_t_test(DRUG_1, 0.0, Alternative::Greater, 0.95);
+        assert_close!(greater.p_value, 0.10879889003422447, 1e-12);
+        assert_close!(greater.confidence_lower, -0.2870552787292596, 1e-12);
+        assert_eq!(greater.confidence_upper, f64::INFINITY);
+    }
+
+    #[test]
+    fn test_paired() {
+        let result = paired_t_test(DRUG_1, DRUG_2, Alternative::TwoSided, 0.95);
+        assert_close!(result.statistic, -4.062127683382036, 1e-12);
+        assert_eq!(result.degrees_of_freedom, 9.0);
+        assert_close!(result.p_value, 0.002832890197384273, 1e-12);
+        assert_close!(result.confidence_lower, -2.459885763276983, 1e-12);
+        assert_close!(result.confidence_upper, -0.7001142367230172, 1e-12);
+        assert_close!(result.effect_size, -1.2845575625910544, 1e-12);
+    }
+
+    #[test]
+    fn test_student() {
+        let result = student_t_test(DRUG_1, DRUG_2, Alternative::TwoSided, 0.95);
+        assert_close!(result.statistic, -1.860813467486853, 1e-12);
+        assert_eq!(result.degrees_of_freedom, 18.0);
+        assert_close!(result.p_value, 0.0791867142159381, 1e-12);
+        assert_close!(result.confidence_lower, -3.3638740322875986, 1e-12);
+        assert_close!(result.confidence_upper, 0.20387403228759855, 1e-12);
+        assert_close!(result.effect_size, -0.8321810813495397, 1e-12);
+    }
+
+    #[test]
+    fn test_welch() {
+        let result = welch_t_test(DRUG_1, DRUG_2, Alternative::TwoSided, 0.95);
+        assert_close!(result.statistic, -1.860813467486853, 1e-12);
+        assert_close!(result.degrees_of_freedom, 17.77647351617849, 1e-12);
+        assert_close!(result.p_value, 0.07939414018735814, 1e-12);
+        assert_close!(result.confidence_lower, -3.365483230711711, 1e-12);
+        assert_close!(result.confidence_upper, 0.20548323071171097, 1e-12);
+        let less = welch_t_test(DRUG_1, DRUG_2, Alternative::Less, 0.95);
+        assert_close!(less.p_value, 0.03969707009367907, 1e-12);
+        assert_eq!(less.confidence_lower, f64::NEG_INFINITY);
+        assert_close!(less.confidence_upper, -0.10661850266839357, 1e-12);
+        assert!(less.rejects(0.05));
+    }
+
+}
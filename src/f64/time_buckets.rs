@@ -0,0 +1,273 @@
+//! Time-bucketed aggregation of timestamped samples, like a rollup per
+//! second, minute, or hour.
+//!
+//! Each bucket keeps a mergeable `Summary` with count, min, max, average,
+//! and variance. The buckets are in a ring buffer with a fixed capacity,
+//! so the oldest buckets expire as time advances. Query a time range,
+//! such as the last 5 minutes, by merging its buckets.
+//!
+//! Timestamps can be `Instant`, `SystemTime`, or integers, by the
+//! `Timestamp` trait.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::time::Duration;
+//! use numeric_statistics::f64::time_buckets::*;
+//!
+//! // Per-second buckets for 1 hour, with integer timestamps in seconds.
+//! let mut buckets = TimeBuckets::per_second(3600);
+//! for t in 0..600u64 {
+//!     buckets.record(t, t as f64);
+//! }
+//! let summary = buckets.last(Duration::from_secs(300));
+//! assert_eq!(summary.count(), 300);
+//! assert_eq!(summary.min(), 300.0);
+//! assert_eq!(summary.max(), 599.0);
+//! ```
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+pub use super::summary::Summary;
+
+/// Timestamp that can be placed in a time bucket.
+///
+/// Bucket boundaries are aligned to the epoch of the type, so a
+/// per-minute bucket of `SystemTime` starts on a wall clock minute.
+pub trait Timestamp: Copy {
+    /// Signed nanoseconds since the epoch of the type.
+    ///
+    /// Types without an epoch, such as `Instant`, use the first
+    /// recorded timestamp as their epoch.
+    fn nanos_since_epoch(self, first: Self) -> i128;
+}
+
+impl Timestamp for Instant {
+    fn nanos_since_epoch(self, first: Self) -> i128 {
+        match self.checked_duration_since(first) {
+            Some(d) => d.as_nanos() as i128,
+            None => -(first.duration_since(self).as_nanos() as i128),
+        }
+    }
+}
+
+impl Timestamp for SystemTime {
+    /// Use the Unix epoch.
+    fn nanos_since_epoch(self, _first: Self) -> i128 {
+        match self.duration_since(UNIX_EPOCH) {
+            Ok(d) => d.as_nanos() as i128,
+            Err(e) => -(e.duration().as_nanos() as i128),
+        }
+    }
+}
+
+/// Integer timestamps in seconds, such as Unix time.
+macro_rules! timestamp_seconds {
+    ($($t:ty),*) => {$(
+        impl Timestamp for $t {
+            fn nanos_since_epoch(self, _first: Self) -> i128 {
+                self as i128 * 1_000_000_000
+            }
+        }
+    )*};
+}
+
+timestamp_seconds!(i32, i64, u32, u64);
+
+/// One time bucket, where the bucket covers the time range
+/// [index × width, (index + 1) × width) since the epoch.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeBucket {
+    pub index: i64,
+    pub summary: Summary,
+}
+
+/// Ring buffer of time buckets, each with a summary of its samples.
+#[derive(Debug, Clone)]
+pub struct TimeBuckets<T: Timestamp> {
+    width: Duration,
+    capacity: usize,
+    first: Option<T>,
+    /// Buckets in ascending order of index, without gaps, ending at the latest bucket.
+    buckets: VecDeque<TimeBucket>,
+}
+
+impl<T: Timestamp> TimeBuckets<T> {
+    /// Create time buckets with the bucket width, keeping the latest
+    /// `capacity` buckets, such as width 1 minute and capacity 60 for 1 hour.
+    ///
+    /// # Panics
+    ///
+    /// Panic if the width is zero, or if the capacity is 0.
+    ///
+    pub fn new(width: Duration, capacity: usize) -> TimeBuckets<T> {
+        assert!(!width.is_zero(), "width must be positive");
+        assert!(capacity > 0, "capacity must be positive");
+        TimeBuckets { width, capacity, first: None, buckets: VecDeque::with_capacity(capacity) }
+    }
+
+    /// Create per-second buckets, keeping the latest `capacity` buckets.
+    pub fn per_second(capacity: usize) -> TimeBuckets<T> {
+        TimeBuckets::new(Duration::from_secs(1), capacity)
+    }
+
+    /// Create per-minute buckets, keeping the latest `capacity` buckets.
+    pub fn per_minute(capacity: usize) -> TimeBuckets<T> {
+        TimeBuckets::new(Duration::from_secs(60), capacity)
+    }
+
+    /// Create per-hour buckets, keeping the latest `capacity` buckets.
+    pub fn per_hour(capacity: usize) -> TimeBuckets<T> {
+        TimeBuckets::new(Duration::from_secs(3600), capacity)
+    }
+
+    pub fn width(&self) -> Duration {
+        self.width
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Index of the bucket for the time.
+    fn index(&mut self, time: T) -> i64 {
+        let first = *self.first.get_or_insert(time);
+        time.nanos_since_epoch(first).div_euclid(self.width.as_nanos() as i128) as i64
+    }
+
+    /// Index of the latest bucket, or None if nothing is recorded.
+    pub fn latest_index(&self) -> Option<i64> {
+        self.buckets.back().map(|bucket| bucket.index)
+    }
+
+    /// Record a sample at a time.
+    ///
+    /// Return false if the sample is filtered, because it is NaN,
+    /// or because its bucket has expired.
+    pub fn record(&mut self, time: T, value: f64) -> bool {
+        if value.is_nan() { return false; }
+        let index = self.index(time);
+        self.advance_to(index);
+        let latest = self.latest_index().unwrap();
+        let oldest = latest - self.buckets.len() as i64 + 1;
+        if index < oldest { return false; }
+        self.buckets[(index - oldest) as usize].summary.push(value);
+        true
+    }
+
+    /// Advance the latest bucket to the time, expiring buckets that are
+    /// older than the capacity, such as before a query at the current time.
+    pub fn advance(&mut self, time: T) {
+        let index = self.index(time);
+        self.advance_to(index);
+    }
+
+    fn advance_to(&mut self, index: i64) {
+        let latest = match self.latest_index() {
+            Some(latest) if index <= latest => return,
+            Some(latest) => latest,
+            None => index - 1,
+        };
+        // Skip buckets that would expire at once.
+        let start = (latest + 1).max(index - self.capacity as i64 + 1);
+        if start > latest + 1 { self.buckets.clear(); }
+        for i in start..=index {
+            if self.buckets.len() == self.capacity { self.buckets.pop_front(); }
+            self.buckets.push_back(TimeBucket { index: i, summary: Summary::new() });
+        }
+    }
+
+    /// Iterate the buckets that have samples, from oldest to latest.
+    pub fn iter_buckets(&self) -> impl Iterator<Item = &TimeBucket> + '_ {
+        self.buckets.iter().filter(|bucket| bucket.summary.count() > 0)
+    }
+
+    /// Summary of the latest buckets that cover the duration, such as
+    /// the last 5 minutes, ending with the latest bucket.
+    ///
+    /// The duration rounds up to whole buckets, and is limited to the capacity.
+    pub fn last(&self, duration: Duration) -> Summary {
+        let count = duration.as_nanos().div_ceil(self.width.as_nanos()).min(self.capacity as u128) as usize;
+        let mut summary = Summary::new();
+        self.buckets.iter().rev().take(count).for_each(|bucket| summary.merge(&bucket.summary));
+        summary
+    }
+
+    /// Summary of every bucket in the ring buffer.
+    pub fn total(&self) -> Summary {
+        let mut summary = Summary::new();
+        self.buckets.iter().for_each(|bucket| summary.merge(&bucket.summary));
+        summary
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_empty() {
+        let buckets: TimeBuckets<u64> = TimeBuckets::per_minute(5);
+        let summary = buckets.last(Duration::from_secs(300));
+        assert_eq!(summary.count(), 0);
+        assert!(summary.average().is_nan());
+        assert!(summary.all().min.is_nan());
+    }
+
+    #[test]
+    fn test_nan() {
+        let mut buckets = TimeBuckets::per_second(5);
+        assert!(!buckets.record(1u64, f64::NAN));
+        assert!(buckets.record(1u64, 2.0));
+        assert_eq!(buckets.total().count(), 1);
+    }
+
+    #[test]
+    fn test_buckets_and_expiry() {
+        let mut buckets = TimeBuckets::per_minute(3);
+        buckets.record(0u64, 1.0);
+        buckets.record(59u64, 3.0);
+        buckets.record(60u64, 10.0);
+        let indices: Vec<i64> = buckets.iter_buckets().map(|bucket| bucket.index).collect();
+        assert_eq!(indices, vec![0, 1]);
+        assert_eq!(buckets.iter_buckets().next().unwrap().summary.average(), 2.0);
+        // Minute 3 expires minute 0.
+        buckets.record(180u64, 20.0);
+        assert_eq!(buckets.total().count(), 2);
+        assert!(!buckets.record(30u64, 5.0));
+        // A late sample in a kept bucket still counts.
+        assert!(buckets.record(150u64, 30.0));
+        assert_eq!(buckets.last(Duration::from_secs(120)).max(), 30.0);
+        assert_eq!(buckets.last(Duration::from_secs(60)).count(), 1);
+    }
+
+    #[test]
+    fn test_advance_far() {
+        let mut buckets = TimeBuckets::per_second(3);
+        buckets.record(0i64, 1.0);
+        buckets.advance(1000i64);
+        assert_eq!(buckets.total().count(), 0);
+        assert_eq!(buckets.latest_index(), Some(1000));
+        buckets.record(999i64, 2.0);
+        assert_eq!(buckets.total().count(), 1);
+    }
+
+    #[test]
+    fn test_system_time_aligned() {
+        let mut buckets = TimeBuckets::per_minute(10);
+        let t = UNIX_EPOCH + Duration::from_secs(120 + 59);
+        buckets.record(t, 1.0);
+        assert_eq!(buckets.latest_index(), Some(2));
+    }
+
+    #[test]
+    fn test_instant() {
+        let mut buckets = TimeBuckets::per_second(10);
+        let t = Instant::now();
+        buckets.record(t, 1.0);
+        buckets.record(t + Duration::from_millis(2500), 3.0);
+        assert_eq!(buckets.latest_index(), Some(2));
+        assert_eq!(buckets.last(Duration::from_secs(10)).average(), 2.0);
+    }
+
+}
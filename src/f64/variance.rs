@@ -56,8 +56,8 @@ pub fn variance_with_average<T: AsRef<[f64]>>(values: T, average: f64) -> f64 {
         }
     );
     match len {
-        0 => return f64::NAN,
-        1 => return 0.0,
+        0 => f64::NAN,
+        1 => 0.0,
         x => delta_square_sum / (x - 1) as f64
     }
 }
@@ -59,6 +59,30 @@
 //! standard deviation: 1.527525231651946
 //! ```
 //! 
+//! ## Display
+//! 
+//! You can format all the numeric statistics with precision, significant figures, units, and alignment:
+//! 
+//! ```rust
+//! use numeric_statistics::f64::all::All;
+//! 
+//! let values = &[1.0, 2.0, 4.0];
+//! let all = All::new(&values);
+//! println!("{}", all.display().precision(3).significant(4).unit("ms").align(true));
+//! ```
+//! 
+//! Output:
+//! 
+//! ```stdout
+//! min:                1.000 ms
+//! max:                4.000 ms
+//! average:            2.333 ms
+//! variance:           2.333 ms²
+//! standard deviation: 1.528 ms
+//! ```
+//! 
+//! Time units (ns, µs, ms, s) scale automatically, such as 2500 µs to 2.50 ms.
+//! 
 //! ## Num Command
 //! 
 //! This is a work-in-progress to translate the Num Command software from POSIX into Rust.